@@ -1,6 +1,1197 @@
 import test from 'ava'
-import { getDisplays, getPrimaryDisplay, startListen } from '../index'
+import {
+  Automation,
+  awaitSimulationDrained,
+  blockUserInput,
+  buttonCode,
+  buttonFromCode,
+  ButtonJs,
+  canSimulateGestures,
+  canSimulateKey,
+  captureInputState,
+  checkAccessibilityPermission,
+  checkInputMonitoringPermission,
+  describeEvent,
+  describeMask,
+  diffDisplays,
+  diffSystemSettings,
+  EVENT_MASK_ALL,
+  EVENT_MASK_KEYBOARD,
+  eventToKeyboard,
+  eventToMouseButton,
+  eventTypeCode,
+  eventTypeFromCode,
+  EventTypeJs,
+  getAllKeyDisplayInfo,
+  getDefaultSettleMs,
+  getDisplayForFocusedWindow,
+  getDisplays,
+  getDisplaysOrEmpty,
+  getEventTypeDisplayName,
+  getHighestRefreshRate,
+  getKeyCategory,
+  getKeyCategoryEnum,
+  getKeyDisplayName,
+  getKeyDisplayNameAscii,
+  getLockState,
+  getMousePosition,
+  getPrimaryDisplay,
+  getPrimaryDisplayOrNull,
+  getSimulatableKeys,
+  hotkeyFromString,
+  hotkeyToString,
+  InputHook,
+  isMaskSubset,
+  KEY_JS_COUNT,
+  KeyCategoryJs,
+  keyFromString,
+  KeyJs,
+  keyToChar,
+  keyToString,
+  maskHas,
+  maskToEventTypes,
+  maskWith,
+  maskWithout,
+  normalizeCoordinateForSimulation,
+  normalizeKey,
+  planReplay,
+  releaseAllSimulated,
+  replayEvents,
+  restoreInputState,
+  ScrollDirectionJs,
+  setDefaultSettleMs,
+  setReleaseSimulatedOnStop,
+  setRequireForegroundApp,
+  setSimulationQueue,
+  simulateBinary,
+  simulateClickToWindow,
+  simulateKeyCombo,
+  simulateKeyPress,
+  simulateKeysPress,
+  simulateKeyTap,
+  simulateKeyToWindow,
+  simulateMouseMove,
+  simulateMouseMoveClamped,
+  simulateMouseMoveToDisplayCenter,
+  simulatePinch,
+  simulateRotate,
+  simulateScancodePress,
+  simulateScancodeRelease,
+  simulateScancodeTap,
+  SimulationStatusJs,
+  splitTextUnits,
+  startListen,
+  typeText,
+  unblockUserInput,
+  verifyKeyTables,
+} from '../index'
 
 test('sync function from native code', (t) => {
   t.is(1, 1)
 })
+
+test('ASCII key display names are ASCII for every key', (t) => {
+  for (const { key } of getAllKeyDisplayInfo()) {
+    t.regex(getKeyDisplayNameAscii(key), /^[\x20-\x7e]+$/)
+  }
+})
+
+test('Meta and Alt display names follow the platform', (t) => {
+  const [meta, metaAscii, alt] = { darwin: ['\u2318', 'Cmd', 'Option'], win32: ['Win', 'Win', 'Alt'] }[
+    process.platform as string
+  ] ?? ['Super', 'Super', 'Alt']
+  t.is(getKeyDisplayName(KeyJs.MetaLeft), meta)
+  t.is(getKeyDisplayNameAscii(KeyJs.MetaRight), metaAscii)
+  t.is(getKeyDisplayName(KeyJs.AltRight), alt)
+})
+
+test('key category enum agrees with the string category', (t) => {
+  t.is(getKeyCategoryEnum(KeyJs.KeyA), KeyCategoryJs.Letter)
+  t.is(getKeyCategoryEnum(KeyJs.ArrowUp), KeyCategoryJs.Arrow)
+  for (const { key } of getAllKeyDisplayInfo()) {
+    t.is(KeyCategoryJs[getKeyCategoryEnum(key)].toLowerCase(), getKeyCategory(key))
+  }
+})
+
+test('the key table covers every KeyJs value with a name and category', (t) => {
+  const info = getAllKeyDisplayInfo()
+  t.notThrows(() => verifyKeyTables())
+  t.is(info.length, KEY_JS_COUNT)
+  t.deepEqual(
+    info.map(({ key }) => key),
+    info.map((_, i) => i),
+  )
+  for (const { key, displayName, category } of info) {
+    t.is(keyFromString(keyToString(key)), key)
+    if (key === KeyJs.Unknown) continue
+    t.not(displayName, 'Unknown', keyToString(key))
+    t.not(category, 'unknown', keyToString(key))
+  }
+})
+
+test('every event type has a code and a display name', (t) => {
+  const types = maskToEventTypes(EVENT_MASK_ALL)
+  t.is(types.length, 11)
+  for (const type of types) {
+    t.is(eventTypeFromCode(eventTypeCode(type)), type)
+    t.not(getEventTypeDisplayName(type), '')
+  }
+})
+
+test('generic events convert to typed payloads of their own kind only', (t) => {
+  const press = { eventType: EventTypeJs.MousePressed, time: 1, mouse: { x: 5, y: 6 } }
+  t.like(eventToMouseButton(press), { x: 5, y: 6, button: ButtonJs.Left, clickCount: 1 })
+  t.is(eventToKeyboard(press), null)
+})
+
+test('device ids are never reported and cannot be set on simulated input', (t) => {
+  const press = { eventType: EventTypeJs.MousePressed, time: 1, mouse: { x: 5, y: 6 } }
+  t.is(eventToMouseButton(press)?.deviceId ?? null, null)
+  t.throws(() => simulateKeyTap(KeyJs.KeyA, 1), { message: /\[Unsupported\] deviceId 1/ })
+  t.throws(() => simulateMouseMove(0, 0, 1), { message: /\[Unsupported\]/ })
+})
+
+test('binary scripts are validated before anything runs', (t) => {
+  t.throws(() => simulateBinary(Buffer.from([2])), { message: /unsupported version 2/ })
+  t.throws(() => simulateBinary(Buffer.from([1, 0x07, 0, 0, 0, 0x7f])), { message: /at byte 5: unknown opcode/ })
+  t.throws(() => simulateBinary(Buffer.from([1, 0x01, 0, 0])), { message: /unexpected end/ })
+})
+
+test('replay plans bracket drags and drop covered clicks', (t) => {
+  const mouse = (eventType: EventTypeJs, x: number, button?: ButtonJs) => ({ eventType, time: x, mouse: { x, y: 0, button } })
+  const wheel = { x: 9, y: 0, direction: ScrollDirectionJs.Left, delta: 2.5 }
+  const plan = planReplay([
+    mouse(EventTypeJs.MousePressed, 1, ButtonJs.Right),
+    mouse(EventTypeJs.MouseReleased, 2, ButtonJs.Right),
+    mouse(EventTypeJs.MouseClicked, 2, ButtonJs.Right),
+    mouse(EventTypeJs.MouseClicked, 3, ButtonJs.Middle),
+    mouse(EventTypeJs.MouseDragged, 4),
+    mouse(EventTypeJs.MouseDragged, 5),
+    mouse(EventTypeJs.MouseMoved, 6),
+    { eventType: EventTypeJs.MouseWheel, time: 7, wheel },
+    { eventType: EventTypeJs.HookDisabled, time: 8 },
+  ])
+  t.deepEqual(
+    plan.map((e) => [e.eventType, e.time, e.mouse?.button]),
+    [
+      [EventTypeJs.MouseMoved, 1, undefined],
+      [EventTypeJs.MousePressed, 1, ButtonJs.Right],
+      [EventTypeJs.MouseDragged, 2, undefined],
+      [EventTypeJs.MouseReleased, 2, ButtonJs.Right],
+      [EventTypeJs.MouseMoved, 3, undefined],
+      [EventTypeJs.MousePressed, 3, ButtonJs.Middle],
+      [EventTypeJs.MouseReleased, 3, ButtonJs.Middle],
+      [EventTypeJs.MouseMoved, 4, undefined],
+      [EventTypeJs.MousePressed, 4, ButtonJs.Left],
+      [EventTypeJs.MouseDragged, 4, undefined],
+      [EventTypeJs.MouseDragged, 5, undefined],
+      [EventTypeJs.MouseReleased, 6, ButtonJs.Left],
+      [EventTypeJs.MouseMoved, 6, undefined],
+      [EventTypeJs.MouseWheel, 7, undefined],
+    ],
+  )
+  // Every button op happens where the pointer was last replayed to
+  let x: number | undefined
+  for (const e of plan) {
+    if (e.eventType === EventTypeJs.MousePressed || e.eventType === EventTypeJs.MouseReleased) t.is(e.mouse?.x, x)
+    else if (e.mouse) x = e.mouse.x
+  }
+  t.deepEqual(plan[13].wheel, wheel)
+  t.deepEqual(
+    planReplay(plan).map((e) => e.eventType),
+    plan.map((e) => e.eventType),
+  )
+  t.throws(() => replayEvents([], 0), { message: /positive number/ })
+})
+
+test('replay plans leave out keys that map to Unknown', (t) => {
+  const key = (eventType: EventTypeJs, key: KeyJs, rawCode: number) => ({ eventType, time: 1, keyboard: { key, rawCode } })
+  const plan = planReplay([
+    key(EventTypeJs.KeyPressed, KeyJs.Unknown, 0xff),
+    key(EventTypeJs.KeyPressed, KeyJs.KeyA, 0x41),
+    key(EventTypeJs.KeyReleased, KeyJs.Unknown, 0xff),
+    key(EventTypeJs.KeyReleased, KeyJs.KeyA, 0x41),
+  ])
+  t.deepEqual(
+    plan.map((e) => [e.eventType, e.keyboard?.key]),
+    [
+      [EventTypeJs.KeyPressed, KeyJs.KeyA],
+      [EventTypeJs.KeyReleased, KeyJs.KeyA],
+    ],
+  )
+})
+
+test('the default settle delay is a global setting', (t) => {
+  const before = getDefaultSettleMs()
+  t.is(before, 15)
+  setDefaultSettleMs(40)
+  t.is(getDefaultSettleMs(), 40)
+  setDefaultSettleMs(before)
+})
+
+test('gesture simulation validates input, then reports Unsupported', (t) => {
+  t.false(canSimulateGestures())
+  t.throws(() => simulatePinch(0, 0, 0), { message: /positive number/ })
+  t.throws(() => simulateRotate(45, NaN, 0), { message: /must be finite/ })
+  t.throws(() => simulatePinch(2, 0, 0), { message: /^\[Unsupported\]/ })
+  t.throws(() => simulateRotate(45, 0, 0), { message: /^\[Unsupported\]/ })
+})
+
+test('blockUserInput requires a bounded timeout', (t) => {
+  t.throws(() => blockUserInput(0), { message: /between 1 and 60000/ })
+  t.throws(() => blockUserInput(60001), { message: /between 1 and 60000/ })
+  if (process.platform !== 'win32') {
+    t.throws(() => blockUserInput(), { message: /^\[Unsupported\]/ })
+  }
+  t.notThrows(() => unblockUserInput())
+})
+
+test('the secure-context guard only detects prompts on Windows', (t) => {
+  setRequireForegroundApp(true)
+  try {
+    if (process.platform !== 'win32') {
+      t.throws(() => simulateKeyToWindow(0, KeyJs.KeyA), { message: /not supported on this platform/ })
+      t.throws(() => simulateClickToWindow(0, 0, 0, ButtonJs.Left), { message: /not supported on this platform/ })
+    } else {
+      t.pass()
+    }
+  } finally {
+    setRequireForegroundApp(false)
+  }
+})
+
+test('Automation chains steps and runs waits on a background thread', async (t) => {
+  const automation = new Automation()
+  t.is(automation.move(1, 2).click().type('hi').wait(10).key(KeyJs.Enter), automation)
+  t.is(automation.length, 5)
+  t.throws(() => automation.loop(0), { message: /at least 1/ })
+  t.is(await new Automation().wait(5).loop(2).run(), SimulationStatusJs.Completed)
+})
+
+test('the simulation queue still validates synchronously and drains when idle', async (t) => {
+  setSimulationQueue(true)
+  try {
+    t.throws(() => simulateKeyCombo([KeyJs.KeyA, KeyJs.KeyB]), { message: /only the last key/ })
+    await t.notThrowsAsync(awaitSimulationDrained())
+  } finally {
+    setSimulationQueue(false)
+  }
+})
+
+test('queued input runs in call order and its failures surface from the drain', async (t) => {
+  if (process.platform === 'win32') {
+    t.pass('scan code injection succeeds on Windows')
+    return
+  }
+  setSimulationQueue(true)
+  try {
+    t.notThrows(() => simulateScancodeRelease(0x30))
+    t.notThrows(() => simulateScancodePress(0x1e))
+    await t.throwsAsync(awaitSimulationDrained(), { message: /release scancode 0x30/ })
+    t.notThrows(() => simulateScancodeTap(0x1e))
+    await t.throwsAsync(awaitSimulationDrained(), { message: /press scancode 0x1e/ })
+    await t.notThrowsAsync(awaitSimulationDrained())
+  } finally {
+    setSimulationQueue(false)
+  }
+})
+
+test('normalizeKey folds only what it is asked to', (t) => {
+  t.is(normalizeKey(KeyJs.Numpad1), KeyJs.Numpad1)
+  t.is(normalizeKey(KeyJs.Numpad1, { foldNumpad: true }), KeyJs.Num1)
+  t.is(normalizeKey(KeyJs.NumpadEnter, { foldNumpad: true }), KeyJs.Enter)
+  t.is(normalizeKey(KeyJs.NumpadAdd, { foldNumpad: true }), KeyJs.NumpadAdd)
+  t.is(normalizeKey(KeyJs.ShiftRight, { foldNumpad: true }), KeyJs.ShiftRight)
+  t.is(normalizeKey(KeyJs.ShiftRight, { foldModifierSides: true }), KeyJs.ShiftLeft)
+})
+
+test('hotkey strings are canonical and round-trip', (t) => {
+  const str = hotkeyToString([KeyJs.KeyS, KeyJs.ShiftLeft, KeyJs.ControlLeft])
+  t.is(str, 'ControlLeft+ShiftLeft+KeyS')
+  t.deepEqual(hotkeyFromString(str), [KeyJs.ControlLeft, KeyJs.ShiftLeft, KeyJs.KeyS])
+  t.is(hotkeyToString(hotkeyFromString(str)), str)
+  t.throws(() => hotkeyFromString('ControlLeft+Bogus'), { message: /"Bogus"/ })
+})
+
+test('splitTextUnits keeps surrogate pairs and combining sequences whole', (t) => {
+  t.deepEqual(splitTextUnits('aé中'), ['a', 'é', '中'])
+  t.deepEqual(splitTextUnits('😀!'), ['😀', '!'])
+  t.deepEqual(splitTextUnits('e\u0301x'), ['e\u0301', 'x'])
+  t.deepEqual(splitTextUnits('👍🏽👨\u200d👩\u200d👧'), ['👍🏽', '👨\u200d👩\u200d👧'])
+  t.deepEqual(splitTextUnits(''), [])
+})
+
+test('typeText rejects characters no key produces on the active layout', (t) => {
+  if (process.platform === 'win32') {
+    t.pass('Windows types every unit as Unicode input')
+    return
+  }
+  t.throws(() => typeText('\u{1F600}'), { message: /no key produces "\u{1F600}"/u })
+})
+
+test('keyToChar is null for keys without a character and never invents one', (t) => {
+  for (const key of [KeyJs.ShiftLeft, KeyJs.ArrowUp, KeyJs.F1, KeyJs.Enter, KeyJs.Tab]) {
+    t.is(keyToChar(key, false), null)
+  }
+  // Whatever the layout, a character key yields one unit of text or nothing
+  const a = keyToChar(KeyJs.KeyA, false)
+  t.true(a === null || splitTextUnits(a).length === 1)
+})
+
+test('permission checks are always granted off macOS', (t) => {
+  t.is(typeof checkAccessibilityPermission(), 'boolean')
+  t.is(typeof checkInputMonitoringPermission(), 'boolean')
+  if (process.platform !== 'darwin') {
+    t.true(checkAccessibilityPermission())
+    t.true(checkInputMonitoringPermission())
+  }
+})
+
+test('display fallbacks never throw', (t) => {
+  t.true(Array.isArray(getDisplaysOrEmpty()))
+  t.notThrows(() => getPrimaryDisplayOrNull())
+  t.notThrows(() => getHighestRefreshRate())
+  t.notThrows(() => getDisplayForFocusedWindow())
+})
+
+test('normalized coordinates land on a display and are stable', (t) => {
+  const displays = getDisplaysOrEmpty()
+  if (displays.length === 0) {
+    t.pass('no displays')
+    return
+  }
+  const { x, y, width, height } = displays[0].bounds
+  for (const [px, py] of [
+    [x + 0.3, y + 0.3],
+    [x + width / 2 + 0.5, y + height / 2 - 0.25],
+    [x - 5000, y - 5000],
+  ]) {
+    const once = normalizeCoordinateForSimulation(px, py)
+    t.true(displays.some(({ bounds: b }) => once.x >= b.x && once.x < b.x + b.width && once.y >= b.y && once.y < b.y + b.height))
+    t.deepEqual(normalizeCoordinateForSimulation(once.x, once.y), once)
+  }
+})
+
+test('mask helpers agree with the EVENT_MASK constants', (t) => {
+  const mask = maskWith(maskWith(maskWith(0, EventTypeJs.KeyPressed), EventTypeJs.KeyReleased), EventTypeJs.KeyTyped)
+  t.is(mask, EVENT_MASK_KEYBOARD)
+  t.true(maskHas(mask, EventTypeJs.KeyTyped))
+  t.false(maskHas(maskWithout(mask, EventTypeJs.KeyTyped), EventTypeJs.KeyTyped))
+  t.deepEqual(maskToEventTypes(mask), [EventTypeJs.KeyPressed, EventTypeJs.KeyReleased, EventTypeJs.KeyTyped])
+})
+
+test('describeMask names the event types in a mask', (t) => {
+  t.deepEqual(describeMask(0x84), ['Key Pressed', 'Mouse Clicked'])
+  t.deepEqual(describeMask(0), [])
+  t.true(isMaskSubset(0x84, EVENT_MASK_KEYBOARD | 0x80))
+  t.false(isMaskSubset(0x84, EVENT_MASK_KEYBOARD))
+})
+
+test('describeEvent renders a compact log line', (t) => {
+  t.is(
+    describeEvent({
+      eventType: EventTypeJs.KeyPressed,
+      time: 12345.678,
+      keyboard: { key: KeyJs.KeyA, rawCode: 65 },
+    }),
+    'KeyPressed key=A raw=65 t=12345.678',
+  )
+  t.is(
+    describeEvent({ eventType: EventTypeJs.MousePressed, time: 1, mouse: { x: 100, y: 200, button: ButtonJs.Left } }),
+    'MousePressed x=100 y=200 button=MouseL t=1.000',
+  )
+  t.is(
+    describeEvent({
+      eventType: EventTypeJs.MouseWheel,
+      time: 0,
+      wheel: { x: 5, y: 6, direction: ScrollDirectionJs.Down, delta: 3 },
+    }),
+    'MouseWheel x=5 y=6 dir=down delta=3 t=0.000',
+  )
+})
+
+test('button and event type codes round-trip', (t) => {
+  const buttons = [ButtonJs.Left, ButtonJs.Right, ButtonJs.Middle, ButtonJs.Button4, ButtonJs.Button5, ButtonJs.Unknown]
+  t.deepEqual(buttons.map(buttonCode), [0, 1, 2, 3, 4, 255])
+  for (const button of buttons) t.is(buttonFromCode(buttonCode(button)), button)
+  t.is(buttonFromCode(5), null)
+
+  for (let code = 0; code <= 10; code++) t.is(eventTypeCode(eventTypeFromCode(code)!), code)
+  t.is(eventTypeCode(EventTypeJs.MouseWheel), 10)
+  t.is(eventTypeFromCode(11), null)
+})
+
+test('simulateKeyCombo rejects malformed combos before pressing anything', (t) => {
+  t.throws(() => simulateKeyCombo([KeyJs.KeyA, KeyJs.KeyB]), { message: /only the last key/ })
+  t.throws(() => simulateKeyCombo([KeyJs.ControlLeft, KeyJs.ControlLeft]), { message: /more than once/ })
+  t.throws(() => simulateKeyCombo([]), { message: /no keys/ })
+})
+
+test('diffDisplays matches displays by id', (t) => {
+  const display = (id: number, x: number, isPrimary = false) => ({
+    id,
+    bounds: { x, y: 0, width: 1920, height: 1080 },
+    scaleFactor: 1,
+    isPrimary,
+    index: 0,
+  })
+  const old = [display(1, 0, true), display(2, 1920)]
+  t.deepEqual(diffDisplays(old, [display(1, 1e-9, true), display(2, 1920)]), { added: [], removed: [], changed: [] })
+  t.deepEqual(diffDisplays(old, [display(1, 0), display(3, 1920, true)]), { added: [3], removed: [2], changed: [1] })
+})
+
+test('diffSystemSettings reports changed fields', (t) => {
+  const base = { mouseSensitivity: NaN, doubleClickTime: 500, keyboardLayout: 'us' }
+  t.deepEqual(diffSystemSettings(base, { ...base }), [])
+  t.deepEqual(diffSystemSettings(base, { ...base, doubleClickTime: 400, keyboardLayout: undefined }), [
+    'doubleClickTime',
+    'keyboardLayout',
+  ])
+  t.deepEqual(diffSystemSettings(base, { ...base, mouseSensitivity: 1 }), ['mouseSensitivity'])
+})
+
+test('cloneConfig copies settings but not listeners', (t) => {
+  const hook = new InputHook()
+  hook.setWheelCoalesceGap(30)
+  hook.onKeyDown(() => {})
+  const clone = hook.cloneConfig()
+  t.is(clone.wheelCoalesceGapMs, 30)
+  t.is(clone.eventMask, 0)
+  hook.setWheelCoalesceGap(null)
+  clone.setWheelCoalesceGap(null)
+})
+
+test('resetSettings restores defaults but keeps listeners', (t) => {
+  const hook = new InputHook()
+  hook.setWheelCoalesceGap(30)
+  hook.onKeyDown(() => {})
+  hook.resetSettings()
+  t.is(hook.wheelCoalesceGapMs, 0)
+  t.not(hook.eventMask & EVENT_MASK_KEYBOARD, 0)
+})
+
+// Requires `pnpm build:test` (the `test-utils` feature); skipped otherwise.
+const injectTest = 'injectEventForTest' in InputHook.prototype ? test : test.skip
+
+test('lock state is readable on Windows and macOS', (t) => {
+  const state = getLockState()
+  if (process.platform === 'linux' && state === null) return t.pass() // no X display
+  t.is(typeof state?.capsLock, 'boolean')
+  if (process.platform === 'darwin') t.like(state, { numLock: false, scrollLock: false })
+})
+
+test('media and volume keys are simulatable everywhere, browser keys not on macOS', (t) => {
+  const systemDefined = [KeyJs.VolumeUp, KeyJs.VolumeDown, KeyJs.VolumeMute, KeyJs.MediaPlayPause, KeyJs.MediaNext]
+  const keycodeless = [KeyJs.ContextMenu, KeyJs.MediaStop, KeyJs.BrowserBack, KeyJs.LaunchMail]
+  const simulatable = new Set(getSimulatableKeys())
+  for (const key of systemDefined) {
+    t.true(canSimulateKey(key), keyToString(key))
+    t.true(simulatable.has(key), keyToString(key))
+  }
+  for (const key of keycodeless) {
+    t.is(canSimulateKey(key), process.platform !== 'darwin', keyToString(key))
+    t.is(simulatable.has(key), canSimulateKey(key), keyToString(key))
+  }
+})
+
+test('the source pid filter is Windows only', (t) => {
+  const hook = new InputHook()
+  if (process.platform === 'win32') {
+    t.notThrows(() => hook.setSourcePidFilter(process.pid))
+  } else {
+    t.throws(() => hook.setSourcePidFilter(process.pid), { message: /^\[Unsupported\]/ })
+  }
+  t.notThrows(() => hook.setSourcePidFilter(null))
+})
+
+test('shutdown keeps the wheel coalescing setting', async (t) => {
+  const hook = new InputHook()
+  hook.setWheelCoalesceGap(40)
+  await hook.shutdown()
+  t.is(hook.wheelCoalesceGapMs, 40)
+})
+
+injectTest('injected events go through typed dispatch', async (t) => {
+  const hook = new InputHook() as InputHook & { injectEventForTest(event: unknown): void }
+  const received = new Promise<number>((resolve) => hook.onKeyDown((data) => resolve(data.key)))
+  hook.injectEventForTest({
+    eventType: EventTypeJs.KeyPressed,
+    time: 0,
+    keyboard: { key: KeyJs.KeyA, rawCode: 0 },
+  })
+  t.is(await received, KeyJs.KeyA)
+})
+
+injectTest('injected modifier presses are not taken as physically held', async (t) => {
+  const hook = new InputHook() as InputHook & { injectEventForTest(event: unknown): void }
+  const seen = new Promise<number>((resolve) => hook.onKeyDown((data) => resolve(data.modifiers)))
+  hook.injectEventForTest({ eventType: EventTypeJs.KeyPressed, time: 0, keyboard: { key: KeyJs.ShiftLeft, rawCode: 0 } })
+  t.not(await seen, 0)
+  t.false(captureInputState().pressedModifiers.includes(KeyJs.ShiftLeft))
+})
+
+test('restoreInputState rejects an invalid snapshot', (t) => {
+  const at = { x: 0, y: 0 }
+  t.throws(() => restoreInputState({ mousePosition: at, pressedModifiers: [KeyJs.KeyA] }), {
+    message: /Invalid input state/,
+  })
+  t.throws(() => restoreInputState({ mousePosition: at, pressedModifiers: [KeyJs.ShiftLeft, KeyJs.ShiftLeft] }), {
+    message: /Invalid input state/,
+  })
+  t.throws(() => restoreInputState({ mousePosition: { x: NaN, y: 0 }, pressedModifiers: [] }), {
+    message: /Invalid input state/,
+  })
+})
+
+injectTest('a key whose release was missed can fire its hotkey again', async (t) => {
+  const hook = new InputHook() as InputHook & { injectEventForTest(event: unknown): void }
+  const fired: number[] = []
+  const done = new Promise<void>((resolve) =>
+    hook.hotkeys().register([KeyJs.KeyH], (data) => fired.push(data.time) === 2 && resolve()),
+  )
+  const press = (time: number) =>
+    hook.injectEventForTest({ eventType: EventTypeJs.KeyPressed, time, keyboard: { key: KeyJs.KeyH, rawCode: 0 } })
+  press(0)
+  press(0.5) // autorepeat
+  press(30) // no release in between, and no repeats for 30 s
+  await done
+  t.is(fired.length, 2)
+})
+
+injectTest('raw native fields are attached only when asked for', async (t) => {
+  const hook = new InputHook() as InputHook & { injectEventForTest(event: unknown): void }
+  const raws: unknown[] = []
+  const done = new Promise<void>((resolve) => hook.onKeyDown((data) => raws.push(data.raw ?? null) === 2 && resolve()))
+  const press = { eventType: EventTypeJs.KeyPressed, time: 0, keyboard: { key: KeyJs.KeyA, rawCode: 65 } }
+  hook.injectEventForTest(press)
+  hook.setIncludeRaw(true)
+  hook.injectEventForTest(press)
+  await done
+  t.is(raws[0], null)
+  t.like(raws[1], { fields: { rawCode: 65 } })
+})
+
+injectTest('keys, buttons and scroll directions survive the round trip through monio', async (t) => {
+  const hook = new InputHook() as InputHook & { injectEventForTest(event: unknown): void }
+  const keys = getAllKeyDisplayInfo().map(({ key }) => key as KeyJs)
+  const buttons = [ButtonJs.Left, ButtonJs.Right, ButtonJs.Middle, ButtonJs.Button4, ButtonJs.Button5, ButtonJs.Unknown]
+  const directions = [ScrollDirectionJs.Up, ScrollDirectionJs.Down, ScrollDirectionJs.Left, ScrollDirectionJs.Right]
+  const seen = { keys: [] as KeyJs[], buttons: [] as ButtonJs[], directions: [] as ScrollDirectionJs[] }
+  const done = new Promise<void>((resolve) => {
+    hook.onKeyDown((data) => seen.keys.push(data.key))
+    hook.onMouseDown((data) => seen.buttons.push(data.button))
+    hook.onWheel((data) => seen.directions.push(data.direction) === directions.length && resolve())
+  })
+  keys.forEach((key, i) =>
+    hook.injectEventForTest({ eventType: EventTypeJs.KeyPressed, time: i, keyboard: { key, rawCode: 0 } }),
+  )
+  for (const button of buttons) {
+    hook.injectEventForTest({ eventType: EventTypeJs.MousePressed, time: 0, mouse: { x: 0, y: 0, button } })
+  }
+  for (const direction of directions) {
+    hook.injectEventForTest({ eventType: EventTypeJs.MouseWheel, time: 0, wheel: { x: 0, y: 0, direction, delta: 1 } })
+  }
+  await done
+  t.deepEqual(seen, { keys, buttons, directions })
+})
+
+injectTest('every listener fires and removeListener drops just one', async (t) => {
+  const hook = new InputHook() as InputHook & { injectEventForTest(event: unknown): void }
+  const seen: string[] = []
+  const first = hook.onKeyDown(() => seen.push('first'))
+  const second = new Promise<void>((resolve) =>
+    hook.onKeyDown(() => {
+      seen.push('second')
+      resolve()
+    }),
+  )
+  const press = { eventType: EventTypeJs.KeyPressed, time: 0, keyboard: { key: KeyJs.KeyA, rawCode: 0 } }
+  hook.injectEventForTest(press)
+  await second
+  t.deepEqual(seen, ['first', 'second'])
+  t.true(hook.removeListener(first))
+  t.false(hook.removeListener(first))
+  t.not(hook.eventMask & EVENT_MASK_KEYBOARD, 0)
+})
+
+injectTest('folded modifier sides are reported as the left key', async (t) => {
+  const hook = new InputHook() as InputHook & { injectEventForTest(event: unknown): void }
+  hook.setFoldModifierSides(true)
+  const received = new Promise<number>((resolve) => hook.onKeyDown((data) => resolve(data.key)))
+  hook.injectEventForTest({
+    eventType: EventTypeJs.KeyPressed,
+    time: 0,
+    keyboard: { key: KeyJs.ShiftRight, rawCode: 0 },
+  })
+  t.is(await received, KeyJs.ShiftLeft)
+})
+
+injectTest('integer coordinates round halves to even', async (t) => {
+  const hook = new InputHook() as InputHook & { injectEventForTest(event: unknown): void }
+  hook.setIntegerCoordinates(true)
+  const received = new Promise<[number, number]>((resolve) => hook.onMouseMove((data) => resolve([data.x, data.y])))
+  hook.injectEventForTest({ eventType: EventTypeJs.MouseMoved, time: 0, mouse: { x: 2.5, y: 3.5 } })
+  t.deepEqual(await received, [2, 4])
+})
+
+injectTest('event counts include events no callback listens for', (t) => {
+  const hook = new InputHook() as InputHook & { injectEventForTest(event: unknown): void }
+  hook.injectEventForTest({ eventType: EventTypeJs.MouseMoved, time: 0, mouse: { x: 1, y: 2 } })
+  hook.injectEventForTest({ eventType: EventTypeJs.MouseMoved, time: 0, mouse: { x: 3, y: 4 } })
+  t.is(hook.getEventCounts().mouseMoved, 2)
+  hook.resetCounts()
+  t.is(hook.getEventCounts().mouseMoved, 0)
+})
+
+injectTest('dedup moves drops moves to the last reported position', async (t) => {
+  const hook = new InputHook() as InputHook & { injectEventForTest(event: unknown): void }
+  hook.setDedupMoves(true)
+  const xs: number[] = []
+  const done = new Promise<void>((resolve) =>
+    hook.onMouseMove((data) => {
+      xs.push(data.x)
+      if (data.x === 2) resolve()
+    }),
+  )
+  for (const x of [1, 1, 2]) {
+    hook.injectEventForTest({ eventType: EventTypeJs.MouseMoved, time: 0, mouse: { x, y: 0 } })
+  }
+  await done
+  t.deepEqual(xs, [1, 2])
+})
+
+injectTest('per-type rate limits only shed that event type', async (t) => {
+  const hook = new InputHook() as InputHook & { injectEventForTest(event: unknown): void }
+  hook.setRateLimitFor(EventTypeJs.MouseMoved, 2)
+  let moves = 0
+  hook.onMouseMove(() => moves++)
+  const keys = new Promise<void>((resolve) => {
+    let n = 0
+    hook.onKeyDown(() => ++n === 5 && resolve())
+  })
+  for (let i = 0; i < 5; i++) {
+    hook.injectEventForTest({ eventType: EventTypeJs.MouseMoved, time: 0, mouse: { x: i, y: 0 } })
+    hook.injectEventForTest({ eventType: EventTypeJs.KeyPressed, time: 0, keyboard: { key: KeyJs.KeyA, rawCode: 0 } })
+  }
+  await keys
+  t.is(moves, 2)
+})
+
+injectTest('deduplicated moves do not use up the rate limit', async (t) => {
+  const hook = new InputHook() as InputHook & { injectEventForTest(event: unknown): void }
+  hook.setDedupMoves(true)
+  hook.setRateLimit(2)
+  const xs: number[] = []
+  const done = new Promise<void>((resolve) =>
+    hook.onMouseMove((data) => {
+      xs.push(data.x)
+      if (data.x === 2) resolve()
+    }),
+  )
+  for (const x of [1, 1, 1, 2]) {
+    hook.injectEventForTest({ eventType: EventTypeJs.MouseMoved, time: 0, mouse: { x, y: 0 } })
+  }
+  await done
+  t.deepEqual(xs, [1, 2])
+})
+
+injectTest('events the shared rate limit sheds do not use up their type limit', async (t) => {
+  const hook = new InputHook() as InputHook & { injectEventForTest(event: unknown): void }
+  hook.setRateLimitFor(EventTypeJs.MouseMoved, 3)
+  hook.setRateLimit(2)
+  let moves = 0
+  hook.onMouseMove(() => moves++)
+  const keys = new Promise<void>((resolve) => {
+    let n = 0
+    hook.onKeyDown(() => ++n === 3 && resolve())
+  })
+  const key = () =>
+    hook.injectEventForTest({ eventType: EventTypeJs.KeyPressed, time: 0, keyboard: { key: KeyJs.KeyA, rawCode: 0 } })
+  const move = (x: number) => hook.injectEventForTest({ eventType: EventTypeJs.MouseMoved, time: 0, mouse: { x, y: 0 } })
+  // The keys spend the shared budget, so these moves are all shed
+  key()
+  key()
+  for (let x = 0; x < 3; x++) move(x)
+  hook.setRateLimit(null)
+  for (let x = 3; x < 8; x++) move(x)
+  key()
+  await keys
+  t.is(moves, 3)
+})
+
+injectTest('key dedup drops a second press of the same key inside the window', async (t) => {
+  const hook = new InputHook() as InputHook & { injectEventForTest(event: unknown): void }
+  hook.setDedupKeysWindowMs(20)
+  const seen: [number, number][] = []
+  const done = new Promise<void>((resolve) =>
+    hook.onKeyDown((data) => {
+      seen.push([data.key, data.rawCode])
+      if (data.rawCode === 4) resolve()
+    }),
+  )
+  const inject = (eventType: EventTypeJs, key: KeyJs, time: number, rawCode: number) =>
+    hook.injectEventForTest({ eventType, time, keyboard: { key, rawCode } })
+  inject(EventTypeJs.KeyPressed, KeyJs.KeyA, 1, 1)
+  inject(EventTypeJs.KeyPressed, KeyJs.KeyB, 1.005, 2)
+  inject(EventTypeJs.KeyReleased, KeyJs.KeyB, 1.007, 2)
+  inject(EventTypeJs.KeyReleased, KeyJs.KeyA, 1.008, 1)
+  inject(EventTypeJs.KeyPressed, KeyJs.KeyA, 1.01, 0)
+  inject(EventTypeJs.KeyReleased, KeyJs.KeyA, 1.012, 0)
+  inject(EventTypeJs.KeyPressed, KeyJs.KeyA, 1.05, 3)
+  inject(EventTypeJs.KeyPressed, KeyJs.KeyB, 1.06, 4)
+  await done
+  t.deepEqual(seen, [
+    [KeyJs.KeyA, 1],
+    [KeyJs.KeyB, 2],
+    [KeyJs.KeyA, 3],
+    [KeyJs.KeyB, 4],
+  ])
+})
+
+injectTest('key dedup swallows the release of a dropped press', async (t) => {
+  const hook = new InputHook() as InputHook & { injectEventForTest(event: unknown): void }
+  hook.setDedupKeysWindowMs(20)
+  const released: [number, number][] = []
+  const done = new Promise<void>((resolve) =>
+    hook.onKeyUp((data) => {
+      released.push([data.key, data.rawCode])
+      if (data.rawCode === 3) resolve()
+    }),
+  )
+  const inject = (eventType: EventTypeJs, key: KeyJs, time: number, rawCode: number) =>
+    hook.injectEventForTest({ eventType, time, keyboard: { key, rawCode } })
+  inject(EventTypeJs.KeyPressed, KeyJs.KeyA, 1, 1)
+  inject(EventTypeJs.KeyReleased, KeyJs.KeyA, 1.003, 1)
+  // Two dropped presses still swallow only one release
+  inject(EventTypeJs.KeyPressed, KeyJs.KeyA, 1.005, 0)
+  inject(EventTypeJs.KeyPressed, KeyJs.KeyA, 1.007, 0)
+  inject(EventTypeJs.KeyReleased, KeyJs.KeyA, 1.01, 0)
+  inject(EventTypeJs.KeyPressed, KeyJs.KeyA, 1.05, 2)
+  inject(EventTypeJs.KeyReleased, KeyJs.KeyA, 1.06, 2)
+  inject(EventTypeJs.KeyPressed, KeyJs.KeyB, 1.07, 3)
+  inject(EventTypeJs.KeyReleased, KeyJs.KeyB, 1.08, 3)
+  await done
+  t.deepEqual(released, [
+    [KeyJs.KeyA, 1],
+    [KeyJs.KeyA, 2],
+    [KeyJs.KeyB, 3],
+  ])
+})
+
+injectTest('key dedup lets autorepeat of a held key through and reports its release', async (t) => {
+  const hook = new InputHook() as InputHook & { injectEventForTest(event: unknown): void }
+  hook.setDedupKeysWindowMs(50)
+  let presses = 0
+  hook.onKeyDown(() => presses++)
+  const released = new Promise<number>((resolve) => hook.onKeyUp((data) => resolve(data.rawCode)))
+  const inject = (eventType: EventTypeJs, time: number) =>
+    hook.injectEventForTest({ eventType, time, keyboard: { key: KeyJs.KeyA, rawCode: 1 } })
+  for (const time of [1, 1.01, 1.02, 1.03]) inject(EventTypeJs.KeyPressed, time)
+  inject(EventTypeJs.KeyReleased, 1.04)
+  t.is(await released, 1)
+  t.is(presses, 4)
+})
+
+injectTest('window context is only attached when enabled', async (t) => {
+  const hook = new InputHook() as InputHook & { injectEventForTest(event: unknown): void }
+  const titles: (string | null)[] = []
+  const done = new Promise<void>((resolve) =>
+    hook.onKeyDown((data) => titles.push(data.windowTitle ?? null) === 2 && resolve()),
+  )
+  const press = () =>
+    hook.injectEventForTest({ eventType: EventTypeJs.KeyPressed, time: 0, keyboard: { key: KeyJs.KeyA, rawCode: 0 } })
+  press()
+  hook.setIncludeWindowContext(true)
+  press()
+  await done
+  t.is(titles[0], null)
+  if (process.platform !== 'win32') t.is(titles[1], null)
+})
+
+injectTest('sequence numbers count events that were not delivered', async (t) => {
+  const hook = new InputHook() as InputHook & { injectEventForTest(event: unknown): void }
+  const seqs: number[] = []
+  const done = new Promise<void>((resolve) =>
+    hook.onMouseMove((data) => {
+      seqs.push(data.seq)
+      if (seqs.length === 2) resolve()
+    }),
+  )
+  hook.injectEventForTest({ eventType: EventTypeJs.MouseMoved, time: 0, mouse: { x: 1, y: 0 } })
+  hook.injectEventForTest({ eventType: EventTypeJs.KeyPressed, time: 0, keyboard: { key: KeyJs.KeyA, rawCode: 0 } })
+  hook.injectEventForTest({ eventType: EventTypeJs.MouseMoved, time: 0, mouse: { x: 2, y: 0 } })
+  await done
+  t.is(seqs[1] - seqs[0], 2)
+})
+
+injectTest('wheel events with a non-finite delta are dropped', async (t) => {
+  const hook = new InputHook() as InputHook & { injectEventForTest(event: unknown): void }
+  const received = new Promise<number>((resolve) => hook.onWheel((data) => resolve(data.delta)))
+  const wheel = (delta: number) => ({
+    eventType: EventTypeJs.MouseWheel,
+    time: 0,
+    wheel: { x: 0, y: 0, direction: ScrollDirectionJs.Down, delta },
+  })
+  hook.injectEventForTest(wheel(NaN))
+  hook.injectEventForTest(wheel(Infinity))
+  hook.injectEventForTest(wheel(3))
+  t.is(await received, 3)
+})
+
+injectTest('modifier changes ignore the second side and autorepeat', async (t) => {
+  const hook = new InputHook() as InputHook & { injectEventForTest(event: unknown): void }
+  const seen: boolean[] = []
+  const done = new Promise<void>((resolve) =>
+    hook.onModifierChange((data) => {
+      t.is(data.changed, 'shift')
+      seen.push(data.pressed)
+      if (!data.pressed) resolve()
+    }),
+  )
+  const key = (eventType: EventTypeJs, key: KeyJs) => ({ eventType, time: 0, keyboard: { key, rawCode: 0 } })
+  hook.injectEventForTest(key(EventTypeJs.KeyPressed, KeyJs.ShiftLeft))
+  hook.injectEventForTest(key(EventTypeJs.KeyPressed, KeyJs.ShiftLeft))
+  hook.injectEventForTest(key(EventTypeJs.KeyPressed, KeyJs.ShiftRight))
+  hook.injectEventForTest(key(EventTypeJs.KeyReleased, KeyJs.ShiftLeft))
+  hook.injectEventForTest(key(EventTypeJs.KeyReleased, KeyJs.ShiftRight))
+  await done
+  t.deepEqual(seen, [true, false])
+})
+
+injectTest('hotkeys fire once per press and respect their cooldown', async (t) => {
+  const hook = new InputHook() as InputHook & { injectEventForTest(event: unknown): void }
+  const hotkeys = hook.hotkeys()
+  t.throws(() => hotkeys.register([KeyJs.KeyA, KeyJs.KeyB], () => {}), { message: /only the last key/ })
+  let cooled = 0
+  hotkeys.register([KeyJs.ControlLeft, KeyJs.KeyS], () => cooled++, 60_000)
+  let plain = 0
+  const twice = new Promise<void>((resolve) =>
+    hotkeys.register([KeyJs.ControlLeft, KeyJs.KeyS], () => {
+      if (++plain === 2) resolve()
+    }),
+  )
+  const key = (eventType: EventTypeJs, key: KeyJs) => ({ eventType, time: 0, keyboard: { key, rawCode: 0 } })
+  hook.injectEventForTest(key(EventTypeJs.KeyPressed, KeyJs.ControlRight))
+  hook.injectEventForTest(key(EventTypeJs.KeyPressed, KeyJs.KeyS))
+  hook.injectEventForTest(key(EventTypeJs.KeyPressed, KeyJs.KeyS))
+  hook.injectEventForTest(key(EventTypeJs.KeyReleased, KeyJs.KeyS))
+  hook.injectEventForTest(key(EventTypeJs.KeyPressed, KeyJs.KeyS))
+  await twice
+  t.is(cooled, 1)
+})
+
+test('a heartbeat interval needs a callback', (t) => {
+  const hook = new InputHook()
+  t.throws(() => hook.setHeartbeatMs(100), { message: /needs a callback/ })
+  t.notThrows(() => hook.setHeartbeatMs(0))
+})
+
+injectTest('healthcheck ignores injected events when judging the hook thread', (t) => {
+  const hook = new InputHook() as InputHook & { injectEventForTest(event: unknown): void }
+  hook.injectEventForTest({ eventType: EventTypeJs.KeyPressed, time: 1, keyboard: { key: KeyJs.KeyA, rawCode: 0 } })
+  const health = hook.healthcheck()
+  t.not(health.lastEventAgoMs, null)
+  t.is(health.lastDispatchAgoMs, null)
+  t.false(health.threadAlive)
+  t.false(health.stale)
+})
+
+test('hotkey conflicts fold numpad twins and modifier sides', (t) => {
+  const hotkeys = new InputHook().hotkeys()
+  const id = hotkeys.register([KeyJs.ControlLeft, KeyJs.Num1], () => {})
+  hotkeys.register([KeyJs.ControlLeft, KeyJs.Num2], () => {})
+  t.deepEqual(hotkeys.findConflicts([KeyJs.ControlRight, KeyJs.Numpad1]), [id])
+  t.deepEqual(hotkeys.findConflicts([KeyJs.Num1]), [])
+  t.true(hotkeys.unregister(id))
+  t.deepEqual(hotkeys.findConflicts([KeyJs.ControlLeft, KeyJs.Num1]), [])
+})
+
+test('hotkey conflicts need the same trigger and a valid chord', (t) => {
+  const hotkeys = new InputHook().hotkeys()
+  const id = hotkeys.register([KeyJs.ShiftLeft, KeyJs.ControlLeft], () => {})
+  t.deepEqual(hotkeys.findConflicts([KeyJs.ShiftRight, KeyJs.ControlRight]), [id])
+  t.deepEqual(hotkeys.findConflicts([KeyJs.ControlLeft, KeyJs.ShiftLeft]), [])
+  t.throws(() => hotkeys.findConflicts([KeyJs.Num1, KeyJs.ControlLeft]), { message: /not a modifier/ })
+  t.throws(() => hotkeys.findConflicts([]), { message: /no keys/ })
+})
+
+injectTest('mouse trail keeps the last N positions without listeners', (t) => {
+  const hook = new InputHook() as InputHook & { injectEventForTest(event: unknown): void }
+  hook.setTrailCapacity(2)
+  for (const x of [1, 2, 3]) {
+    hook.injectEventForTest({ eventType: EventTypeJs.MouseMoved, time: 0, mouse: { x, y: 0 } })
+  }
+  t.deepEqual(
+    hook.getMouseTrail().map((p) => p.x),
+    [2, 3],
+  )
+})
+
+injectTest('key releases report how long the key was held', async (t) => {
+  const hook = new InputHook() as InputHook & { injectEventForTest(event: unknown): void }
+  const releases: Array<{ heldMs?: number; synthetic?: boolean }> = []
+  const done = new Promise<void>((resolve) =>
+    hook.onKeyUp((data) => {
+      releases.push(data)
+      if (releases.length === 2) resolve()
+    }),
+  )
+  hook.injectEventForTest({ eventType: EventTypeJs.KeyPressed, time: 1, keyboard: { key: KeyJs.KeyA, rawCode: 0 } })
+  hook.injectEventForTest({ eventType: EventTypeJs.KeyReleased, time: 1.25, keyboard: { key: KeyJs.KeyA, rawCode: 0 } })
+  hook.injectEventForTest({ eventType: EventTypeJs.KeyReleased, time: 2, keyboard: { key: KeyJs.KeyB, rawCode: 0 } })
+  await done
+  t.is(releases[0].heldMs, 250)
+  t.false(releases[0].synthetic)
+  t.deepEqual([releases[1].heldMs, releases[1].synthetic], [0, true])
+})
+
+injectTest('ring buffer records follow the documented layout', (t) => {
+  const hook = new InputHook() as InputHook & { injectEventForTest(event: unknown): void }
+  const sab = new SharedArrayBuffer(16 + 32 * 4)
+  hook.attachRingBuffer(new Int32Array(sab))
+  hook.injectEventForTest({ eventType: EventTypeJs.MouseMoved, time: 1.5, mouse: { x: 10, y: 20 } })
+
+  const header = new Int32Array(sab, 0, 4)
+  t.deepEqual(Array.from(header), [1, 1, 4, 32])
+  const record = new DataView(sab, 16, 32)
+  t.is(record.getUint32(0, true), EventTypeJs.MouseMoved)
+  t.is(record.getFloat64(8, true), 1.5)
+  t.is(record.getFloat32(16, true), 10)
+  t.is(record.getFloat32(20, true), 20)
+  hook.detachRingBuffer()
+})
+
+// Installs a real hook and moves the pointer, so it needs input permissions and
+// a display; opt in with MONIO_LIVE_TESTS=1.
+const liveTest = process.env.MONIO_LIVE_TESTS ? test.serial : test.skip
+
+liveTest('events still flow after rapid start/stop cycling', async (t) => {
+  const hook = new InputHook()
+  for (let i = 0; i < 300; i++) {
+    hook.start()
+    hook.stop()
+  }
+  const moved = new Promise<void>((resolve) => hook.onMouseMove(() => resolve()))
+  await hook.startAsync()
+  t.true(hook.isRunning)
+  simulateMouseMove(100, 100)
+  simulateMouseMove(120, 120)
+  await moved
+  hook.stop()
+})
+
+liveTest('a startAsync timeout leaves the hook stopped', async (t) => {
+  const hook = new InputHook()
+  try {
+    await hook.startAsync(0)
+    t.true(hook.isRunning) // ready before the first check
+  } catch (e) {
+    t.regex(String(e), /Timed out/)
+    t.false(hook.isRunning)
+  }
+  hook.stop()
+})
+
+liveTest('replayed keys and wheel are captured as recorded', async (t) => {
+  const key = (eventType: EventTypeJs, time: number) => ({ eventType, time, keyboard: { key: KeyJs.ShiftLeft, rawCode: 0 } })
+  const wheel = { x: 100, y: 100, direction: ScrollDirectionJs.Down, delta: 3 }
+  const hook = new InputHook()
+  const seen: string[] = []
+  hook.onKeyDown((data) => seen.push(`down ${data.key}`))
+  hook.onKeyUp((data) => seen.push(`up ${data.key}`))
+  const scrolled = new Promise<void>((resolve) =>
+    hook.onWheel((data) => {
+      seen.push(`wheel ${data.direction} ${data.delta}`)
+      resolve()
+    }),
+  )
+  await hook.startAsync()
+  await replayEvents([
+    key(EventTypeJs.KeyPressed, 0),
+    key(EventTypeJs.KeyReleased, 0.02),
+    { eventType: EventTypeJs.MouseWheel, time: 0.04, wheel },
+  ])
+  await scrolled
+  hook.stop()
+  t.deepEqual(seen, [`down ${KeyJs.ShiftLeft}`, `up ${KeyJs.ShiftLeft}`, `wheel ${ScrollDirectionJs.Down} 3`])
+})
+
+liveTest('queued mouse moves land in call order', async (t) => {
+  setSimulationQueue(true)
+  try {
+    simulateMouseMove(100, 100)
+    simulateMouseMove(200, 150)
+    simulateMouseMove(300, 200)
+    await awaitSimulationDrained()
+  } finally {
+    setSimulationQueue(false)
+  }
+  const { x, y } = getMousePosition()
+  t.deepEqual([Math.round(x), Math.round(y)], [300, 200])
+})
+
+liveTest('heartbeats tick while the hook runs', async (t) => {
+  const hook = new InputHook()
+  let beats = 0
+  const ticked = new Promise<void>((resolve) =>
+    hook.setHeartbeatMs(20, () => {
+      if (++beats === 3) resolve()
+    }),
+  )
+  await hook.startAsync()
+  await ticked
+  hook.stop()
+  t.is(beats, 3)
+})
+
+liveTest('heartbeats pause while the tap is disabled and stop with the hook', async (t) => {
+  const hook = new InputHook() as InputHook & { injectEventForTest?(event: unknown): void }
+  if (!hook.injectEventForTest) {
+    t.pass('needs a build with injectEventForTest')
+    return
+  }
+  let beats = 0
+  hook.setHeartbeatMs(20, () => beats++)
+  const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms))
+  await hook.startAsync()
+  await sleep(120)
+  t.true(beats > 0)
+
+  hook.injectEventForTest({ eventType: EventTypeJs.HookDisabled, time: 0 })
+  await sleep(40)
+  const paused = beats
+  await sleep(120)
+  t.is(beats, paused)
+
+  hook.injectEventForTest({ eventType: EventTypeJs.HookEnabled, time: 0 })
+  await sleep(120)
+  t.true(beats > paused)
+
+  hook.stop()
+  await sleep(40)
+  const stopped = beats
+  await sleep(120)
+  t.is(beats, stopped)
+})
+
+liveTest('healthcheck reports a silent hook thread as stale', async (t) => {
+  const hook = new InputHook()
+  await hook.startAsync()
+  await new Promise((resolve) => setTimeout(resolve, 20))
+  t.true(hook.healthcheck(60_000).threadAlive)
+  const health = hook.healthcheck(0)
+  t.true(health.stale)
+  t.false(health.threadAlive)
+  t.true(health.lastDispatchAgoMs! > 0)
+  hook.stop()
+  t.is(hook.healthcheck().lastDispatchAgoMs, null)
+})
+
+liveTest('moving to a normalized coordinate reads back the same point', (t) => {
+  for (const { bounds } of getDisplaysOrEmpty()) {
+    const target = normalizeCoordinateForSimulation(bounds.x + bounds.width / 3 + 0.4, bounds.y + bounds.height / 3 + 0.6)
+    simulateMouseMove(target.x, target.y)
+    const { x, y } = getMousePosition()
+    t.deepEqual({ x, y }, target)
+  }
+})
+
+liveTest('simulateKeyTapConfirmed sees its own tap', async (t) => {
+  const hook = new InputHook()
+  await t.throwsAsync(async () => hook.simulateKeyTapConfirmed(KeyJs.ShiftLeft), { message: /not running/ })
+  await hook.startAsync()
+  t.true(await hook.simulateKeyTapConfirmed(KeyJs.ShiftLeft, 1000))
+  hook.stop()
+})
+
+liveTest('simulatable consumer keys inject without error', async (t) => {
+  // Muting twice leaves the volume as it was
+  simulateKeyTap(KeyJs.VolumeMute)
+  simulateKeyTap(KeyJs.VolumeMute)
+  await awaitSimulationDrained()
+  t.pass()
+})
+
+liveTest('a failed simulateKeysPress keeps keys that were already held', (t) => {
+  simulateKeyPress(KeyJs.ShiftLeft)
+  try {
+    // Unknown has no native key, so its press should fail after the others
+    let failed = false
+    try {
+      simulateKeysPress([KeyJs.ShiftLeft, KeyJs.ControlLeft, KeyJs.Unknown])
+    } catch {
+      failed = true
+    }
+    const held = captureInputState().pressedModifiers
+    t.true(held.includes(KeyJs.ShiftLeft))
+    if (failed) t.false(held.includes(KeyJs.ControlLeft))
+  } finally {
+    releaseAllSimulated()
+  }
+})
+
+liveTest('shutdown runs queued simulation and then releases held keys', async (t) => {
+  const hook = new InputHook()
+  hook.setWheelCoalesceGap(40)
+  hook.start()
+  setSimulationQueue(true)
+  try {
+    simulateKeyPress(KeyJs.ShiftLeft)
+    await hook.shutdown()
+    t.false(hook.isRunning)
+    t.false(captureInputState().pressedModifiers.includes(KeyJs.ShiftLeft))
+  } finally {
+    setSimulationQueue(false)
+    releaseAllSimulated()
+  }
+})
+
+liveTest('setReleaseSimulatedOnStop releases held keys when a hook stops', (t) => {
+  const hook = new InputHook()
+  setReleaseSimulatedOnStop(true)
+  try {
+    hook.start()
+    simulateKeyPress(KeyJs.ShiftLeft)
+    hook.stop()
+    t.false(captureInputState().pressedModifiers.includes(KeyJs.ShiftLeft))
+  } finally {
+    setReleaseSimulatedOnStop(false)
+    releaseAllSimulated()
+  }
+})
+
+liveTest('restoreInputState returns to a captured snapshot', (t) => {
+  const before = captureInputState()
+  try {
+    simulateKeyPress(KeyJs.ShiftLeft)
+    simulateMouseMove(before.mousePosition.x + 10, before.mousePosition.y + 10)
+    t.true(captureInputState().pressedModifiers.includes(KeyJs.ShiftLeft))
+    restoreInputState(before)
+    const after = captureInputState()
+    t.deepEqual(after.pressedModifiers.slice().sort(), before.pressedModifiers.slice().sort())
+    t.like(after.mousePosition, before.mousePosition)
+  } finally {
+    releaseAllSimulated()
+  }
+})
+
+liveTest('display centers are whole pixels wherever each display sits', (t) => {
+  for (const { id, bounds } of getDisplaysOrEmpty()) {
+    const center = simulateMouseMoveToDisplayCenter(id)
+    t.deepEqual(center, { x: Math.floor(bounds.x + bounds.width / 2), y: Math.floor(bounds.y + bounds.height / 2) })
+    t.like(getMousePosition(), center)
+  }
+})
+
+liveTest('clamped moves land on the nearest display', (t) => {
+  const displays = getDisplaysOrEmpty()
+  const onSome = ({ x, y }: { x: number; y: number }) =>
+    displays.some(({ bounds: b }) => x >= b.x && x < b.x + b.width && y >= b.y && y < b.y + b.height)
+  for (const { bounds } of displays) {
+    t.deepEqual(simulateMouseMoveClamped(bounds.x + 1, bounds.y + 1), { x: bounds.x + 1, y: bounds.y + 1, clamped: false })
+  }
+  const left = Math.min(...displays.map(({ bounds }) => bounds.x))
+  const far = simulateMouseMoveClamped(left - 10_000, -10_000)
+  t.true(far.clamped)
+  t.true(onSome(far))
+})
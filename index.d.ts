@@ -1,7 +1,79 @@
 /* auto-generated by NAPI-RS */
 /* eslint-disable */
+/**
+ * A reusable sequence of input steps, built fluently and run in order on a
+ * background thread:
+ *
+ * ```js
+ * await new Automation().move(100, 200).click().type('hello').wait(500).key(KeyJs.Enter).run()
+ * ```
+ *
+ * Steps run back to back, so add `wait` wherever the target application
+ * needs time to react. Running does not consume the builder.
+ */
+export declare class Automation {
+  constructor()
+  /** Move the mouse to absolute coordinates. */
+  move(x: number, y: number): this
+  /** Click a mouse button (default `Left`). */
+  click(button?: ButtonJs | undefined | null): this
+  /** Type text, as `typeText`. */
+  type(text: string): this
+  /** Tap a key. */
+  key(key: KeyJs): this
+  /** Pause for `ms` before the next step. */
+  wait(ms: number): this
+  /** Run the whole sequence `times` times (default 1). Throws if `times` is 0. */
+  loop(times: number): this
+  /** Number of steps one pass runs, waits included. */
+  get length(): number
+  /**
+   * Run the steps on a background thread, resolving `Completed` or, if
+   * `token` is cancelled, `Cancelled`. As with `simulateBinary`, keys and
+   * buttons are released if the run stops early.
+   */
+  run(token?: CancelToken | undefined | null): Promise<SimulationStatusJs>
+}
+
+/**
+ * Cancels Promise-returning simulation helpers (`simulateMouseMoveSmooth`,
+ * `simulateKeyHold`) it was passed to. One token can be shared by several
+ * helpers; once cancelled it stays cancelled.
+ */
+export declare class CancelToken {
+  constructor()
+  /**
+   * Stop every helper using this token at its next step boundary. Helpers
+   * then resolve with `SimulationStatusJs.Cancelled`.
+   */
+  cancel(): void
+  get isCancelled(): boolean
+}
+
 export declare class HookJs {
+  /**
+   * Try installing the hook again. `startListen` returns a hook that is not
+   * running (see `installError`) when installation failed with
+   * `[TemporaryFailure]`; other failures throw immediately since retrying
+   * won't help. A no-op while the hook is installed.
+   */
+  retry(): void
+  /** Error message of the last failed install attempt, or `null`. */
+  get installError(): string | null
+  /** Stop listening. */
   stop(): void
+  /**
+   * Tear down in order, resolving once done: wait for input queued by
+   * `setSimulationQueue` to run, release keys and buttons held via
+   * simulation (see `releaseAllSimulated`), then stop the listener. Safe to
+   * call more than once. `startListen` buffers nothing natively and runs no
+   * background threads, so there is nothing else to flush or join.
+   */
+  shutdown(): Promise<void>
+  /**
+   * Whether the hook is installed and still receiving events. Becomes `false` if
+   * the OS disables the hook (e.g. a macOS event tap timing out).
+   */
   get isRunning(): boolean
   /**
    * Update the event filter bitmask at runtime without restarting the hook.
@@ -11,6 +83,43 @@ export declare class HookJs {
   setEventMask(mask: number): void
   /** Get the current event filter bitmask. */
   get eventMask(): number
+  /**
+   * The configuration this hook is running with, including any mask change
+   * made via `setEventMask`. Can be passed back to `startListenWithConfig`.
+   */
+  get config(): HookConfigJs
+}
+
+/**
+ * Hotkey registrations on an `InputHook`, from `InputHook.hotkeys()`.
+ * Hotkeys fire only while that hook is running.
+ */
+export declare class HotkeyManager {
+  /**
+   * Register a chord, given like `simulateKeyCombo` keys: modifiers, then the
+   * key that triggers it (e.g. `[ControlLeft, ShiftLeft, KeyP]`). It fires
+   * once when the trigger goes down while exactly the other keys are held;
+   * holding it does not fire again. Numpad twins and left/right modifiers
+   * match each other (see `normalizeKey`).
+   *
+   * With `cooldownMs`, it also does not fire again until that long after it
+   * last fired, even on a fresh press. Returns an id for `unregister` (or
+   * `InputHook.removeListener`).
+   */
+  register(keys: Array<KeyJs>, callback: (data: HotkeyEventJs) => void, cooldownMs?: number | undefined | null): number
+  /** Remove one hotkey. Returns `false` if `id` is not a registered hotkey. */
+  unregister(id: number): boolean
+  /**
+   * Ids of registered hotkeys that `keys` would collide with: the same chord
+   * and the same trigger (last key), compared with the folds `register`
+   * matches with, so `[ControlRight, Numpad1]` conflicts with
+   * `[ControlLeft, Num1]`. Modifier order is ignored. `keys` must be a valid
+   * `register` chord; otherwise this throws `InvalidArg` like `register`.
+   * Hotkeys are single chords, so there are no sequence prefixes to overlap.
+   */
+  findConflicts(keys: Array<KeyJs>): Array<number>
+  /** Remove every hotkey. */
+  clear(): void
 }
 
 /**
@@ -32,13 +141,13 @@ export declare class HookJs {
  */
 export declare class InputHook {
   constructor()
-  onKeyDown(callback: (data: KeyboardEventJs) => void): void
-  onKeyUp(callback: (data: KeyboardEventJs) => void): void
-  onMouseDown(callback: (data: MouseButtonEventJs) => void): void
-  onMouseUp(callback: (data: MouseButtonEventJs) => void): void
-  onClick(callback: (data: MouseButtonEventJs) => void): void
-  onMouseMove(callback: (data: MouseMoveEventJs) => void): void
-  onWheel(callback: (data: WheelEventJs) => void): void
+  onKeyDown(callback: (data: KeyboardEventJs) => void): number
+  onKeyUp(callback: (data: KeyboardEventJs) => void): number
+  onMouseDown(callback: (data: MouseButtonEventJs) => void): number
+  onMouseUp(callback: (data: MouseButtonEventJs) => void): number
+  onClick(callback: (data: MouseButtonEventJs) => void): number
+  onMouseMove(callback: (data: MouseMoveEventJs) => void): number
+  onWheel(callback: (data: WheelEventJs) => void): number
   offKeyDown(): void
   offKeyUp(): void
   offMouseDown(): void
@@ -46,13 +155,356 @@ export declare class InputHook {
   offClick(): void
   offMouseMove(): void
   offWheel(): void
+  /**
+   * Register a callback fired when the OS disables the hook while it should be
+   * running (on macOS: the event tap timed out or permission was revoked).
+   * Afterwards `isRunning` reports `false`; call `stop()` then `start()` to reinstall.
+   */
+  onTapDisabled(callback: (data: TapDisabledEventJs) => void): number
+  offTapDisabled(): void
+  /**
+   * Register a callback fired after the hook stops itself because a limit set
+   * with `setAutoStopAfterMs` or `setAutoStopAfterEvents` was reached.
+   */
+  onAutoStop(callback: (data: AutoStopEventJs) => void): number
+  offAutoStop(): void
+  /**
+   * Register a callback fired when the held-modifier state changes, with left
+   * and right sides collapsed: pressing ShiftRight while ShiftLeft is down,
+   * or autorepeat of a held modifier, does not fire. Works without any key
+   * listener registered.
+   */
+  onModifierChange(callback: (data: ModifierChangeEventJs) => void): number
+  offModifierChange(): void
+  /**
+   * Register a callback fired when more than `setSlowConsumerThreshold`
+   * events are queued for JS callbacks that have not started running, i.e.
+   * a handler is blocking the JS thread. It fires once per episode and
+   * re-arms when the backlog halves. Since it is queued on the same stalled
+   * thread it arrives late; `setLogCallback` gets the same warning.
+   */
+  onSlowConsumer(callback: (data: SlowConsumerEventJs) => void): void
+  offSlowConsumer(): void
+  /**
+   * Set the backlog that counts as a slow consumer (see `onSlowConsumer`);
+   * `null` or 0 turns the watchdog off (the default).
+   */
+  setSlowConsumerThreshold(events?: number | undefined | null): void
+  /**
+   * Remove the single listener with the id an `on*` call returned. Returns
+   * whether it was found.
+   */
+  removeListener(id: number): boolean
   removeAllListeners(): void
   start(): void
+  /**
+   * Start the hook and resolve once the native hook reports it is active
+   * (the HookEnabled event), so no input is missed after `await`.
+   *
+   * Rejects if the hook fails to install or is not ready within `timeout_ms`
+   * (default 5000); on a timeout the hook is stopped first, unless it was
+   * stopped or restarted meanwhile. The synchronous `start()` remains
+   * available.
+   */
+  startAsync(timeoutMs?: number | undefined | null): Promise<void>
+  /** Stop the hook. */
   stop(): void
+  /**
+   * Tear everything down in order, resolving once quiesced: wait for input
+   * queued by `setSimulationQueue` to run, release keys and buttons held via
+   * simulation (see `releaseAllSimulated`), stop the native hook (flushing a
+   * coalesced wheel gesture and cancelling auto-stop timers), end and join
+   * the heartbeat and wheel coalescing threads, and wait for already-queued
+   * callbacks to reach JS (up to one second). Settings, including
+   * `setWheelCoalesceGap` and `setHeartbeatMs`, are kept, and their threads
+   * come back on the next `start()`. Safe to call more than once.
+   */
+  shutdown(): Promise<void>
+  /**
+   * Tap `key` and resolve `true` once this running hook sees it pressed, or
+   * `false` if it doesn't within `timeoutMs` (default 500) — e.g. because the
+   * OS blocked the injection. Needs no `onKeyDown` listener. Any press of the
+   * same key counts, since platforms don't mark injected events reliably.
+   * Throws if the hook is not running.
+   */
+  simulateKeyTapConfirmed(key: KeyJs, timeoutMs?: number | undefined | null): Promise<boolean>
+  /** `start()` that reports failure in the returned object instead of throwing. */
+  tryStart(): TryResultJs
+  /** `stop()` that reports failure in the returned object instead of throwing. */
+  tryStop(): TryResultJs
+  /**
+   * Whether the hook is installed and still receiving events. Becomes `false` if
+   * the OS disables the hook (see `onTapDisabled`).
+   */
   get isRunning(): boolean
   get eventMask(): number
+  /**
+   * Number of events of each type seen since `start()` (or `resetCounts()`),
+   * counted before any mask or filter so they reflect the actual input.
+   */
+  getEventCounts(): EventCountsJs
+  /** Zero the counters reported by `getEventCounts()`. */
+  resetCounts(): void
+  /**
+   * Aggregate hook state for monitoring.
+   *
+   * `threadAlive` is judged from the native hook thread's own activity: it is
+   * `false` once the thread has exited or has not delivered anything for
+   * `staleAfterMs` (default 30000). The hook thread has no idle tick, so on a
+   * quiet system it goes stale too; pick a threshold that input should always
+   * beat, or treat `stale` as a prompt to check rather than proof of failure.
+   */
+  healthcheck(staleAfterMs?: number | undefined | null): HealthJs
+  /**
+   * Report `ShiftRight`, `ControlRight`, `AltRight` and `MetaRight` as their
+   * left-side key in every callback (and the ring buffer), for consumers that
+   * don't care which side was used. Off by default. Modifier state and
+   * `heldMs` still track each side separately.
+   */
+  setFoldModifierSides(fold: boolean): void
+  /**
+   * Round `x`/`y` in mouse and wheel payloads (and the ring buffer) to whole
+   * pixels, rounding halves to even so there is no drift in either direction.
+   * Off by default, keeping sub-pixel positions.
+   */
+  setIntegerCoordinates(enabled: boolean): void
+  /**
+   * Report wheel `delta` in notches on every platform, where 1.0 is one detent
+   * of a regular mouse wheel; `rawDelta` keeps the platform value. High
+   * resolution wheels and trackpads yield fractional notches. Off by default.
+   */
+  setNormalizeWheelDelta(enabled: boolean): void
+  /**
+   * Choose how event callbacks are queued to JS (see `CallModeJs`). Defaults
+   * to `NonBlocking`.
+   */
+  setCallMode(mode: CallModeJs): void
+  /**
+   * Drop moves (and drags) to the position `onMouseMove` last reported, such
+   * as the no-op moves some platforms send on focus changes. Positions are
+   * compared after `setIntegerCoordinates` rounding. Off by default.
+   */
+  setDedupMoves(enabled: boolean): void
+  /**
+   * Attach the focused window's title and process name (`windowTitle`,
+   * `processName`) to key and mouse button payloads, for attributing input
+   * to applications. The window is looked up off the hook thread at most
+   * every 100 ms, so it may lag a focus change slightly. Off by default
+   * because of that lookup; like `getFocusedWindow`, it only yields values
+   * on Windows.
+   */
+  setIncludeWindowContext(enabled: boolean): void
+  /**
+   * Attach the native fields monio passes through (`raw`: the platform plus
+   * `rawCode`, `rawDelta` and the coordinates before any adjustment) to key,
+   * button, move and wheel payloads. Off by default because it builds an
+   * extra object per event.
+   */
+  setIncludeRaw(enabled: boolean): void
+  /**
+   * Drop a KeyPressed for a key that was released and pressed again less
+   * than `ms` after its last accepted press, such as the same key arriving
+   * from two keyboards in quick succession. Events don't say which device
+   * sent them, so this is purely time-based. Presses of a key that is still
+   * held are autorepeat and always delivered. Unlike `startListen`'s
+   * `debounceMs` it is tracked per key, so interleaved presses of different
+   * keys don't reset it. Dropped presses reach no callback, hotkey or
+   * modifier tracking, and swallow the next release of their key (one
+   * release per key, however many presses were dropped). 0 (the default)
+   * turns it off.
+   */
+  setDedupKeysWindowMs(ms: number): void
+  /**
+   * Set how far (in pixels, per axis) successive presses may drift and still
+   * count toward `clickCount`. Defaults to 4. The time window is the system
+   * double-click interval, re-read on `start()`.
+   */
+  setClickTolerance(px: number): void
+  /**
+   * Create a new, stopped `InputHook` with this hook's settings: filter,
+   * source-pid filter, click tolerance, wheel coalescing, auto-stop limits,
+   * rate limits and priorities, slow-consumer threshold, modifier-side folding,
+   * integer coordinates, wheel normalization, key dedup and window context.
+   * Callbacks (and so the event mask, which follows them), the ring buffer
+   * and counters are not copied.
+   */
+  cloneConfig(): InputHook
+  /**
+   * Put every setting `cloneConfig()` copies back to its default, keeping
+   * callbacks (and so the event mask), the ring buffer and the running state.
+   * The counterpart of `removeAllListeners()`, which keeps the settings.
+   */
+  resetSettings(): void
+  /**
+   * Only deliver events while the focused window belongs to process `pid`;
+   * pass `null` to deliver everything again.
+   *
+   * No platform reports the originating process per event, so this checks the
+   * focused window's owner, looked up off the hook thread at most every
+   * ~100ms (so a focus change may take that long to apply). That only works
+   * on Windows (see `getFocusedWindow`); elsewhere setting a pid throws
+   * `[Unsupported]`.
+   */
+  setSourcePidFilter(pid?: number | undefined | null): void
+  /**
+   * Stop automatically `ms` after `start()`; `null` or 0 disables the limit
+   * (the default). Takes effect on the next `start()`.
+   */
+  setAutoStopAfterMs(ms?: number | undefined | null): void
+  /**
+   * Stop automatically once `count` input events have been seen since
+   * `start()`, whether or not a callback was registered for them; `null` or 0
+   * disables the limit (the default). Takes effect on the next `start()`.
+   */
+  setAutoStopAfterEvents(count?: number | undefined | null): void
+  /**
+   * Write events into `buffer` — an `Int32Array` over a `SharedArrayBuffer` —
+   * instead of (or as well as) calling per-event callbacks, for capture rates
+   * where callback overhead dominates. See `RING_LAYOUT_VERSION` for the
+   * record layout; the buffer's header is initialised here.
+   *
+   * Every input event that passes `setSourcePidFilter` and `setFilter` is
+   * written, regardless of which callbacks are registered. JS tracks its own
+   * read position and has been overrun if the write count gets more than the
+   * capacity ahead of it.
+   *
+   * Native code can't `Atomics.notify` a JS waiter, so a worker blocked in
+   * `Atomics.wait(view, 0, seen, timeout)` should use a short timeout. For
+   * event-loop consumers, `onData` (if given) is called with the write count
+   * once per batch: further writes don't queue another call until it has run.
+   */
+  attachRingBuffer(buffer: Int32Array, onData?: ((written: number) => void) | undefined | null): void
+  /** Stop writing to the ring buffer set with `attachRingBuffer`. */
+  detachRingBuffer(): void
+  /**
+   * Keep the last `n` mouse positions (moves and drags) for `getMouseTrail`,
+   * whether or not any mouse listener is registered; 0 turns it off (the
+   * default) and clears the trail. Shrinking drops the oldest positions.
+   */
+  setTrailCapacity(n: number): void
+  /** Recent mouse positions, oldest first (see `setTrailCapacity`). */
+  getMouseTrail(): Array<TrailPointJs>
+  /**
+   * Limit callback delivery to `eventsPerSecond` (short bursts up to one
+   * second's worth are allowed); `null` or 0 removes the limit (the default).
+   * Which events are shed is controlled by `setEventPriority`.
+   */
+  setRateLimit(eventsPerSecond?: number | undefined | null): void
+  /**
+   * Limit one event type to `eventsPerSecond`, e.g. mouse moves to 60/s while
+   * key presses stay unlimited; `null` or 0 removes that type's limit (the
+   * default). Each type has its own bucket, checked along with
+   * `setRateLimit`'s shared one (regardless of `setEventPriority`): an event
+   * is charged to both or, if either says no, to neither.
+   */
+  setRateLimitFor(event: EventTypeJs, eventsPerSecond?: number | undefined | null): void
+  /**
+   * Set how an event type fares under `setRateLimit`: `High` always gets
+   * through (e.g. clicks, key presses), `Low` is shed first (e.g. mouse moves).
+   */
+  setEventPriority(event: EventTypeJs, priority: PriorityJs): void
+  /**
+   * Only dispatch events matching `spec` (see `FilterSpecJs` for the grammar);
+   * pass `null` to remove the filter. The spec is evaluated natively, after the
+   * callback mask, so rejected events never reach JS.
+   */
+  setFilter(spec?: FilterSpecJs | undefined | null): void
+  /**
+   * Coalesce wheel events: consecutive events in the same direction less than
+   * `gapMs` apart are delivered to `onWheel` as one event with the summed
+   * `delta`, the latest position and the first event's `time`. A gesture is
+   * delivered once the direction changes or no event arrives for `gapMs`.
+   * Summed deltas are clamped to ±1,000,000. Pass `null` or 0 to deliver
+   * every wheel event again (the default).
+   *
+   * Wheel events with a non-finite delta are always dropped, coalescing or not.
+   */
+  setWheelCoalesceGap(gapMs?: number | undefined | null): void
+  /**
+   * Call `callback` every `ms` while the hook is running, from a native
+   * timer, with the time in the events' time base. Ticks are skipped while
+   * the native hook thread has exited or the OS has disabled its tap (see
+   * `onTapDisabled`), so missing heartbeats mean no events can arrive. It
+   * stops with the hook and resumes on the next `start()`. Setting it again replaces the interval
+   * and callback; `ms` 0 turns it off. Not copied by `cloneConfig()`.
+   */
+  setHeartbeatMs(ms: number, callback?: ((data: HeartbeatEventJs) => void) | null | undefined): void
+  /** Current wheel coalescing gap in ms (0 = off). */
+  get wheelCoalesceGapMs(): number
+  /**
+   * Hotkey registrations matched against this hook's keyboard events. Every
+   * call returns a view of the same registrations.
+   */
+  hotkeys(): HotkeyManager
+}
+
+/**
+ * Chains relative cursor moves against its own tracked position instead of
+ * re-reading the OS cursor before each step, so paths stay deterministic even
+ * if the user nudges the mouse mid-gesture.
+ *
+ * ```js
+ * const mover = new RelativeMover() // starts at the current cursor position
+ * mover.moveBy(10, 0)
+ * mover.moveBy(0, 10)
+ * mover.sync() // adopt the real position again
+ * ```
+ */
+export declare class RelativeMover {
+  /**
+   * Start tracking from `(x, y)`, or from the current cursor position when
+   * omitted. Nothing is moved until `moveBy`.
+   */
+  constructor(x?: number | undefined | null, y?: number | undefined | null)
+  /**
+   * Move by `(dx, dy)` from the tracked position (not the live cursor) and
+   * return the new tracked position. If the move fails the position is kept.
+   */
+  moveBy(dx: number, dy: number): PointJs
+  /** Reset the tracked position to where the cursor really is, and return it. */
+  sync(): PointJs
+  /** The tracked position, i.e. where the last `moveBy` put the cursor. */
+  get position(): PointJs
+}
+
+/** Payload for onAutoStop callbacks. */
+export interface AutoStopEventJs {
+  /** `"duration"` or `"events"`: which limit stopped the hook. */
+  reason: string
+  /** Events dispatched during the session. */
+  events: number
+  time: number
 }
 
+/**
+ * Resolve once everything queued by `setSimulationQueue` has run. Rejects
+ * with the first failure since the previous drain, if any (later queued
+ * input still ran). Resolves immediately when nothing is queued.
+ */
+export declare function awaitSimulationDrained(): Promise<void>
+
+/**
+ * Block keyboard and mouse input from real devices so the user can't
+ * interfere with a running script; simulated input still goes through. The
+ * block lifts by itself after `timeoutMs` (default 10000, at most 60000), so
+ * a crashed script can't lock the machine. Calling again while blocked
+ * resets the deadline.
+ *
+ * Windows only, and the process must be elevated (run as Administrator).
+ * Ctrl+Alt+Del always lifts the block, and Windows lifts it if the process
+ * exits. Elsewhere this throws an `[Unsupported]` error.
+ */
+export declare function blockUserInput(timeoutMs?: number | undefined | null): void
+
+/**
+ * Stable integer for a mouse button, safe to persist. The mapping is fixed:
+ * Left = 0, Right = 1, Middle = 2, Button4 = 3, Button5 = 4, Unknown = 255.
+ */
+export declare function buttonCode(button: ButtonJs): number
+
+/** Inverse of `buttonCode`. Returns `null` for codes it never produces. */
+export declare function buttonFromCode(code: number): ButtonJs | null
+
 export declare enum ButtonJs {
   Left = 0,
   Right = 1,
@@ -62,6 +514,63 @@ export declare enum ButtonJs {
   Unknown = 5,
 }
 
+/**
+ * How event callbacks are queued to the JS thread.
+ *
+ * Each callback queue holds up to 1024 events.
+ * `NonBlocking` (the default) drops an event when its queue is full.
+ * `Blocking` waits for room instead, so nothing is dropped, but it stalls the
+ * OS hook thread meanwhile: input lags system-wide while JS is busy, and
+ * macOS disables event taps that stall too long (see `onTapDisabled`).
+ */
+export declare enum CallModeJs {
+  NonBlocking = 0,
+  Blocking = 1,
+}
+
+/**
+ * Whether `simulatePinch` and `simulateRotate` can synthesize trackpad
+ * gestures on this platform. Currently `false` everywhere: neither monio nor
+ * the platform shims can inject gesture events.
+ */
+export declare function canSimulateGestures(): boolean
+
+/**
+ * Check whether a key can be simulated on the current OS. See
+ * `getSimulatableKeys` for how this is determined.
+ */
+export declare function canSimulateKey(key: KeyJs): boolean
+
+/**
+ * Snapshot the cursor position and held modifiers, for `restoreInputState`.
+ * Physically held modifiers are only known while a hook is running (see
+ * `simulateKeyTapIsolated`).
+ */
+export declare function captureInputState(): InputStateJs
+
+/**
+ * Whether the process has the Accessibility permission, which macOS requires
+ * for simulating input. Always `true` on Windows and Linux.
+ */
+export declare function checkAccessibilityPermission(): boolean
+
+/**
+ * Whether the process has the Input Monitoring permission, which macOS
+ * requires for hooks (`startListen`, `InputHook`) to see key events. Checked
+ * separately from `checkAccessibilityPermission` because the two are granted
+ * independently. Never prompts. Always `true` on Windows and Linux.
+ */
+export declare function checkInputMonitoringPermission(): boolean
+
+/** Result of `simulateMouseMoveClamped`. */
+export interface ClampedMoveJs {
+  /** Where the cursor was actually moved. */
+  x: number
+  y: number
+  /** `true` if the requested point was off every display and had to be redirected. */
+  clamped: boolean
+}
+
 /**
  * Compute an event mask from a list of subscription pattern strings.
  *
@@ -76,12 +585,64 @@ export declare enum ButtonJs {
  */
 export declare function computeEventMask(patterns: Array<string>): number
 
+/**
+ * Describe an event on one line for logs, e.g. `KeyPressed key=A raw=65
+ * t=12345.678` or `MouseMoved x=100 y=200 t=12345.678`. Keys use the ASCII
+ * display names (see `getKeyDisplayNameAscii`) and buttons the names from
+ * `getButtonDisplayName`. The format is meant for humans and may change.
+ */
+export declare function describeEvent(event: EventJs): string
+
+/**
+ * Display names (see `getEventTypeDisplayName`) of the event types included
+ * in `mask`, in bit order, e.g. `["Key Pressed", "Mouse Clicked"]` for `0x84`.
+ */
+export declare function describeMask(mask: number): Array<string>
+
+/**
+ * Compare two display lists by `id`. Ids are listed in the order they appear
+ * in `current` (for `added` and `changed`) or `previous` (for `removed`).
+ */
+export declare function diffDisplays(previous: Array<DisplayInfoJs>, current: Array<DisplayInfoJs>): DisplayChangeSetJs
+
+/**
+ * Names (as in JS, e.g. `"doubleClickTime"`) of the fields that differ between
+ * two settings snapshots. A value appearing or disappearing counts as a change;
+ * two `NaN` readings do not.
+ */
+export declare function diffSystemSettings(a: SystemSettingsJs, b: SystemSettingsJs): Array<string>
+
+/** Display ids that differ between two `getDisplays` snapshots. */
+export interface DisplayChangeSetJs {
+  added: Array<number>
+  removed: Array<number>
+  /**
+   * Present in both, with different bounds, scale factor, refresh rate or
+   * primary flag. `index` alone changing is not a change.
+   */
+  changed: Array<number>
+}
+
 export interface DisplayInfoJs {
   id: number
   bounds: RectJs
   scaleFactor: number
   refreshRate?: number
   isPrimary: boolean
+  /**
+   * Position in the stable display order: primary first, then left to right
+   * (`bounds.x`), then top to bottom (`bounds.y`). `getDisplays` returns
+   * displays in this order.
+   */
+  index: number
+}
+
+export interface DisplaySnapshotJs {
+  displays: Array<DisplayInfoJs>
+  primaryId: number
+  /** Bounding box of all displays in the global coordinate space. */
+  virtualBounds: RectJs
+  cursor: PointJs
 }
 
 /**
@@ -94,6 +655,8 @@ export interface DisplayInfoJs {
  * - `EVENT_MASK_MOUSE_MOVEMENT` (0x300): MouseMoved | MouseDragged
  * - `EVENT_MASK_MOUSE_WHEEL` (0x400): MouseWheel
  * - `EVENT_MASK_MOUSE_ALL` (0x7E0): All mouse events
+ *
+ * Prefer `maskWith`/`maskWithout`/`maskHas` over shifting bits by hand.
  */
 export const EVENT_MASK_ALL: number
 
@@ -107,14 +670,62 @@ export const EVENT_MASK_MOUSE_MOVEMENT: number
 
 export const EVENT_MASK_MOUSE_WHEEL: number
 
+/** Per-event-type counts returned by `InputHook.getEventCounts()`. */
+export interface EventCountsJs {
+  hookEnabled: number
+  hookDisabled: number
+  keyPressed: number
+  keyReleased: number
+  keyTyped: number
+  mousePressed: number
+  mouseReleased: number
+  mouseClicked: number
+  mouseMoved: number
+  mouseDragged: number
+  mouseWheel: number
+}
+
 export interface EventJs {
   eventType: EventTypeJs
   time: number
   keyboard?: KeyboardDataJs
   mouse?: MouseDataJs
   wheel?: WheelDataJs
+  /** As on `KeyboardEventJs.deviceId`. */
+  deviceId?: number
 }
 
+/**
+ * Reshape a `KeyPressed`/`KeyReleased` event as an `onKeyDown`/`onKeyUp`
+ * payload; `null` for other events.
+ */
+export declare function eventToKeyboard(event: EventJs): KeyboardEventJs | null
+
+/**
+ * Reshape a mouse press, release or click as an `onMouseDown`/`onMouseUp`/
+ * `onClick` payload (a missing button is reported as Left, as the hook
+ * does); `null` for other events.
+ */
+export declare function eventToMouseButton(event: EventJs): MouseButtonEventJs | null
+
+/** Reshape a move or drag as an `onMouseMove` payload; `null` for other events. */
+export declare function eventToMouseMove(event: EventJs): MouseMoveEventJs | null
+
+/** Reshape a wheel event as an `onWheel` payload; `null` for other events. */
+export declare function eventToWheel(event: EventJs): WheelEventJs | null
+
+/**
+ * Stable integer for an event type, safe to persist. The mapping is fixed and
+ * matches the bit positions used by event masks: HookEnabled = 0,
+ * HookDisabled = 1, KeyPressed = 2, KeyReleased = 3, KeyTyped = 4,
+ * MousePressed = 5, MouseReleased = 6, MouseClicked = 7, MouseMoved = 8,
+ * MouseDragged = 9, MouseWheel = 10.
+ */
+export declare function eventTypeCode(eventType: EventTypeJs): number
+
+/** Inverse of `eventTypeCode`. Returns `null` for codes it never produces. */
+export declare function eventTypeFromCode(code: number): EventTypeJs | null
+
 export declare enum EventTypeJs {
   HookEnabled = 0,
   HookDisabled = 1,
@@ -129,49 +740,410 @@ export declare enum EventTypeJs {
   MouseWheel = 10,
 }
 
+/** Spec matching when every one of `specs` matches. */
+export declare function filterAll(specs: Array<FilterSpecJs>): FilterSpecJs
+
+/** Spec matching when at least one of `specs` matches. */
+export declare function filterAny(specs: Array<FilterSpecJs>): FilterSpecJs
+
+/** Spec matching mouse events for the given buttons. */
+export declare function filterButtons(buttons: Array<ButtonJs>): FilterSpecJs
+
+/** Spec matching events of the given types. */
+export declare function filterEventTypes(eventTypes: Array<EventTypeJs>): FilterSpecJs
+
+/** Spec matching keyboard events for the given keys. */
+export declare function filterKeys(keys: Array<KeyJs>): FilterSpecJs
+
+/** Spec matching mouse and wheel events inside a rectangle. */
+export declare function filterRegion(region: RectJs): FilterSpecJs
+
+/**
+ * A filter evaluated in Rust before an event is dispatched to JS.
+ *
+ * Grammar: a spec matches an event when **every** field that is set matches
+ * (fields combine with AND; unset fields are ignored, so `{}` matches all):
+ *
+ * - `eventTypes`: the event's type is one of these
+ * - `keys`: a keyboard event whose key is one of these (non-keyboard events fail)
+ * - `buttons`: a mouse event whose button is one of these (events without a
+ *   button, such as moves, fail)
+ * - `region`: a mouse or wheel event inside this rectangle (other events fail)
+ * - `all`: every sub-spec matches (AND)
+ * - `any`: at least one sub-spec matches (OR); an empty list matches nothing
+ *
+ * Build specs as plain objects or with the `filter*` helpers, e.g.
+ * `filterAny([filterKeys([KeyJs.Escape]), filterButtons([ButtonJs.Right])])`.
+ */
+export interface FilterSpecJs {
+  eventTypes?: Array<EventTypeJs>
+  keys?: Array<KeyJs>
+  buttons?: Array<ButtonJs>
+  region?: RectJs
+  all?: Array<FilterSpecJs>
+  any?: Array<FilterSpecJs>
+}
+
+/** The currently focused window, as reported by `getFocusedWindow()`. */
+export interface FocusedWindowJs {
+  title: string
+  bounds: RectJs
+  processName?: string
+  processId?: number
+  /**
+   * Native window handle (an `HWND` on Windows), for `simulateKeyToWindow`
+   * and `simulateClickToWindow`.
+   */
+  handle?: number
+}
+
 /** Get display info for all known keys. */
 export declare function getAllKeyDisplayInfo(): Array<KeyDisplayInfo>
 
+/**
+ * Get display info for all known keys, with caller-supplied labels.
+ *
+ * `overrides` maps a key code (as a string, since JS object keys are strings —
+ * e.g. `{ "49": "Leertaste" }` for `KeyJs.Space`) to the label to use instead of
+ * the built-in English one. Keys without an override keep their default label.
+ */
+export declare function getAllKeyDisplayInfoLocalized(overrides: Record<string, string>): Array<KeyDisplayInfo>
+
 /** Get the display name for a mouse button. */
 export declare function getButtonDisplayName(button: ButtonJs): string
 
+export declare function getDefaultSettleMs(): number
+
 /** Get display at a specific point */
 export declare function getDisplayAtPoint(x: number, y: number): DisplayInfoJs | null
 
-/** Get all displays */
+/**
+ * The display sharing the most area with the focused window. Falls back to
+ * the primary display when there is no focused window (see
+ * `getFocusedWindow`) or it is entirely off-screen, and returns `null` only
+ * when displays cannot be listed.
+ */
+export declare function getDisplayForFocusedWindow(): DisplayInfoJs | null
+
+/**
+ * Get all displays, primary first, then left to right, then top to bottom
+ * (see `DisplayInfoJs.index`).
+ */
 export declare function getDisplays(): Array<DisplayInfoJs>
 
-/** Get the category for a key (e.g. "letter", "modifier", "arrow", "function"). */
-export declare function getKeyCategory(key: KeyJs): string
+/**
+ * Get displays, primary display id, virtual desktop bounds and cursor position
+ * in one call. Everything comes from a single display enumeration, so the
+ * fields are consistent with each other even across a reconfiguration.
+ */
+export declare function getDisplaySnapshot(): DisplaySnapshotJs
+
+/**
+ * Like `getDisplays`, but returns `[]` instead of throwing when displays
+ * can't be enumerated (e.g. on a headless machine).
+ */
+export declare function getDisplaysOrEmpty(): Array<DisplayInfoJs>
+
+/** Get the display name for an event type (e.g. "Key Pressed"). */
+export declare function getEventTypeDisplayName(event: EventTypeJs): string
+
+/**
+ * Get the currently focused window's title, bounds and owning process.
+ *
+ * This is best-effort: it is currently implemented on Windows only, and returns
+ * `None` on macOS and Linux (including Wayland, which does not expose other
+ * clients' windows), or when the platform refuses the query for lack of permissions.
+ */
+export declare function getFocusedWindow(): FocusedWindowJs | null
+
+/**
+ * Highest refresh rate (Hz) of any connected display, or `null` when no
+ * display reports one.
+ */
+export declare function getHighestRefreshRate(): number | null
+
+/**
+ * List installed keyboard layouts. Currently implemented on Windows only;
+ * elsewhere the list is empty.
+ */
+export declare function getKeyboardLayouts(): Array<KeyboardLayoutJs>
+
+/** Get the category for a key (e.g. "letter", "modifier", "arrow", "function"). */
+export declare function getKeyCategory(key: KeyJs): string
+
+/**
+ * Get the category of a key as a `KeyCategoryJs`, for comparisons that
+ * shouldn't depend on strings.
+ */
+export declare function getKeyCategoryEnum(key: KeyJs): KeyCategoryJs
+
+/**
+ * Get the display name for a key. Meta and Alt use this platform's labels
+ * (⌘ and Option on macOS, Win on Windows, Super on Linux).
+ */
+export declare function getKeyDisplayName(key: KeyJs): string
+
+/**
+ * Get an ASCII-only display name for a key (e.g. "Enter", "Bksp", "Cmd"/"Win"/"Super", "Left"),
+ * for terminals and logs where the symbols from `getKeyDisplayName` don't render.
+ */
+export declare function getKeyDisplayNameAscii(key: KeyJs): string
+
+/**
+ * Get the display name for a key, preferring a label from `overrides`.
+ * See `getAllKeyDisplayInfoLocalized` for the shape of `overrides`.
+ */
+export declare function getKeyDisplayNameLocalized(key: KeyJs, overrides: Record<string, string>): string
+
+/**
+ * Query the OS for the current Caps/Num/Scroll Lock toggle state.
+ *
+ * Implemented on Windows, macOS (which has no Num Lock or Scroll Lock, so
+ * those are always `false`) and Linux under X11, where Num Lock and Scroll
+ * Lock read as off unless the keymap binds them to a modifier. Returns `None`
+ * when no X display can be opened.
+ */
+export declare function getLockState(): LockStateJs | null
+
+/** Get the current mouse cursor position */
+export declare function getMousePosition(): MouseDataJs
+
+/**
+ * Get the cursor position relative to the top-left of the display containing
+ * it, or `null` when it is off every display. With `unscale` the offset is
+ * divided by that display's scale factor.
+ */
+export declare function getMousePositionLocal(unscale?: boolean | undefined | null): LocalPointJs | null
+
+/** Get the primary display */
+export declare function getPrimaryDisplay(): DisplayInfoJs
+
+/**
+ * Like `getPrimaryDisplay`, but returns `null` instead of throwing when there
+ * is no display to report (e.g. on a headless machine).
+ */
+export declare function getPrimaryDisplayOrNull(): DisplayInfoJs | null
+
+/**
+ * Scale factor of the display containing a point, falling back to the
+ * primary display's scale when the point is off every display.
+ */
+export declare function getScaleFactorAtPoint(x: number, y: number): number
+
+/**
+ * All keys that `simulateKey*` can produce on the current OS. Based on a
+ * per-platform allowlist of what the OS injection API can express (Windows
+ * and Linux: every named key; macOS: all but MediaStop, browser and launch
+ * keys, ContextMenu, Insert, PrintScreen, ScrollLock, Pause, NumLock and
+ * F21–F24), not on probing, which would require sending input.
+ */
+export declare function getSimulatableKeys(): Array<KeyJs>
+
+/** Get system settings */
+export declare function getSystemSettings(): SystemSettingsJs
+
+/** Snapshot returned by `InputHook.healthcheck()`. */
+export interface HealthJs {
+  /** Same as `InputHook.isRunning`. */
+  running: boolean
+  /**
+   * Whether the native hook thread is running and delivered something within
+   * `staleAfterMs`.
+   */
+  threadAlive: boolean
+  /**
+   * The hook is installed but its thread has been silent for longer than
+   * `staleAfterMs`.
+   */
+  stale: boolean
+  /**
+   * Milliseconds since the native hook thread last delivered an event (or
+   * since `start()`), or `null` while no hook is running. Injected events
+   * don't count.
+   */
+  lastDispatchAgoMs?: number
+  /**
+   * Milliseconds since the hook thread last dispatched an event, or `null` if
+   * none has arrived since `start()`.
+   */
+  lastEventAgoMs?: number
+  /** Events dropped since `start()` because the JS callback queue rejected them. */
+  droppedCount: number
+  /** Whether the OS event tap is enabled (see `onTapDisabled`). */
+  tapEnabled: boolean
+  /** Events queued for JS callbacks that have not started running yet. */
+  backlog: number
+}
+
+/** Payload for the `InputHook.setHeartbeatMs` callback. */
+export interface HeartbeatEventJs {
+  /** Same time base as event `time`. */
+  time: number
+  /** ms since the hook last saw an event, as in `healthcheck()`. */
+  lastEventAgoMs?: number
+}
+
+/**
+ * Declarative hook setup for `startListenWithConfig`. Every field is optional;
+ * omitted fields leave the corresponding filter off.
+ */
+export interface HookConfigJs {
+  /** Event filter bitmask (see `EVENT_MASK_*`). Defaults to all events. */
+  eventMask?: number
+  /** Forward at most one MouseMoved/MouseDragged event per this many ms. */
+  moveThrottleMs?: number
+  /** Only forward keyboard events for these keys. */
+  keys?: Array<KeyJs>
+  /** Only forward mouse and wheel events inside this rectangle. */
+  region?: RectJs
+  /**
+   * Drop a keyboard event that repeats the previous one (same key and type)
+   * within this many ms.
+   */
+  debounceMs?: number
+  /** Drop repeated KeyPressed events while a key is held down. */
+  suppressAutorepeat?: boolean
+  /**
+   * How events are queued to the callback (see `CallModeJs`). Defaults to
+   * `NonBlocking`.
+   */
+  callMode?: CallModeJs
+}
 
-/** Get the display name for a key. */
-export declare function getKeyDisplayName(key: KeyJs): string
+/** Payload for hotkey callbacks. */
+export interface HotkeyEventJs {
+  /** Id returned by `HotkeyManager.register`. */
+  id: number
+  keys: Array<KeyJs>
+  time: number
+}
 
-/** Get the current mouse cursor position */
-export declare function getMousePosition(): MouseDataJs
+/**
+ * Parse a `+`-separated hotkey string (see `hotkeyToString`) with
+ * `keyFromString`. Errors name the first unknown token.
+ */
+export declare function hotkeyFromString(s: string): Array<KeyJs>
 
-/** Get the primary display */
-export declare function getPrimaryDisplay(): DisplayInfoJs
+/**
+ * Canonical string for a hotkey: modifiers first in a fixed order (Control,
+ * Alt, Shift, Meta), then the other keys as given, joined with `+`, e.g.
+ * `"ControlLeft+ShiftLeft+KeyS"`. `hotkeyFromString` parses it back.
+ */
+export declare function hotkeyToString(keys: Array<KeyJs>): string
 
-/** Get system settings */
-export declare function getSystemSettings(): SystemSettingsJs
+/** Cursor position and held modifiers, from `captureInputState`. */
+export interface InputStateJs {
+  mousePosition: PointJs
+  pressedModifiers: Array<KeyJs>
+}
 
 /** Check whether a subscription pattern is input-related (keyboard or mouse). */
 export declare function isInputPattern(pattern: string): boolean
 
+/** Whether every event type in `sub` is also in `sup`. */
+export declare function isMaskSubset(sub: number, sup: number): boolean
+
 /** Check if a key is a modifier key. */
 export declare function isModifierKey(key: KeyJs): boolean
 
+/**
+ * Total number of named KeyJs variants (0 through 137 inclusive), for
+ * iterating key codes without hardcoding the count.
+ * IMPORTANT: Update this when adding new KeyJs variants, and add matching
+ * arms to key_from_i32, key_name, key_display_name, and key_category;
+ * `verifyKeyTables` fails until they agree.
+ */
+export const KEY_JS_COUNT: number
+
 export interface KeyboardDataJs {
+  /** `Unknown` when monio's key table has no entry for `rawCode`. */
   key: KeyJs
+  /**
+   * The key code exactly as the platform hook reported it, for mapped and
+   * unmapped keys alike: the virtual-key code (`VK_*`) on Windows, the
+   * virtual keycode (`kVK_*`) on macOS, and the X11 keycode (evdev code + 8)
+   * on Linux. macOS and X11 keycodes name physical positions, but Windows
+   * virtual-key codes follow the keyboard layout (`Y` and `Z` swap on a German
+   * layout; only scan codes are layout-independent). Codes differ between
+   * platforms, so store them per platform.
+   *
+   * This identifies keys; it can't re-inject them. Simulation takes `KeyJs`,
+   * so `replayEvents` skips `Unknown` keys and the `simulate*` functions
+   * can't target them.
+   */
   rawCode: number
+  /**
+   * Text the key produced, as composed by the OS (layout, Shift, dead keys).
+   * Set on `KeyTyped` events; `null` when the key produces no text.
+   */
+  char?: string
 }
 
 /** Keyboard event payload for onKeyDown / onKeyUp callbacks. */
 export interface KeyboardEventJs {
   key: KeyJs
+  /** Platform key code, as on `KeyboardDataJs.rawCode`. */
   rawCode: number
   time: number
+  /**
+   * Counts every event the hook sees, before masking and filtering, so a gap
+   * means events this callback did not get: filtered, rate-limited, merged by
+   * wheel coalescing, or dropped by a full callback queue (see `healthcheck`).
+   */
+  seq: number
+  /** Modifiers held when the event occurred (`MODIFIER_*` bits). */
+  modifiers: number
+  /** Release events only: how long the key was held, in ms. */
+  heldMs?: number
+  /**
+   * Release events only: `true` if no press was seen (the hook started
+   * mid-hold), in which case `heldMs` is 0.
+   */
+  synthetic?: boolean
+  /**
+   * Title of the focused window when the event was dispatched. Only set with
+   * `setIncludeWindowContext(true)`, and only where `getFocusedWindow` works.
+   */
+  windowTitle?: string
+  /** Process name of that window, as `windowTitle`. */
+  processName?: string
+  /**
+   * Id of the device the event came from. Best-effort and platform-dependent:
+   * no backend reports one yet, so this is always `null`.
+   */
+  deviceId?: number
+  /** Native fields of the event; only set with `setIncludeRaw(true)`. */
+  raw?: RawEventDataJs
+}
+
+/** An installed keyboard layout. */
+export interface KeyboardLayoutJs {
+  /**
+   * Platform identifier, accepted by `setActiveKeyboardLayout`. On Windows
+   * the layout handle in hex (e.g. `"4090409"`).
+   */
+  id: string
+  /** Human-readable name, e.g. "English (United States)". */
+  name: string
+}
+
+/** Key category; `getKeyCategory` returns the same categories as lowercase strings. */
+export declare enum KeyCategoryJs {
+  Letter = 0,
+  Number = 1,
+  Function = 2,
+  Modifier = 3,
+  Arrow = 4,
+  Navigation = 5,
+  Numpad = 6,
+  Media = 7,
+  Browser = 8,
+  Application = 9,
+  International = 10,
+  Punctuation = 11,
+  Lock = 12,
+  Special = 13,
+  Unknown = 14,
 }
 
 export interface KeyDisplayInfo {
@@ -180,6 +1152,12 @@ export interface KeyDisplayInfo {
   category: string
 }
 
+/**
+ * Parse a `KeyJs` variant name (case-insensitive, e.g. `"controlleft"`).
+ * Returns `null` for unknown names.
+ */
+export declare function keyFromString(name: string): KeyJs | null
+
 export declare enum KeyJs {
   KeyA = 0,
   KeyB = 1,
@@ -321,12 +1299,121 @@ export declare enum KeyJs {
   ContextMenu = 137,
 }
 
+/**
+ * Get the character a key produces under the active keyboard layout.
+ *
+ * The key right of `L` yields `"ö"` on a German layout, for example. Windows
+ * consults the focused window's layout and returns a dead key's standalone
+ * accent; macOS uses the current input source and Linux the X11 keyboard
+ * group, where dead keys return `null`. Returns `null` for keys that produce
+ * no character (modifiers, arrows, function keys, Enter, Tab, ...) and
+ * whenever the layout can't be read (e.g. under Wayland).
+ */
+export declare function keyToChar(key: KeyJs, shift: boolean): string | null
+
+/**
+ * Set 1 scan code for a key (`0xE0xx` for extended keys such as the arrows),
+ * for use with `simulateScancodePress`/`Release`/`Tap`. `null` for keys
+ * without a single scan code (Pause, Unknown).
+ */
+export declare function keyToScancode(key: KeyJs): number | null
+
+/**
+ * The `KeyJs` variant name of a key, e.g. `"ControlLeft"`. Inverse of
+ * `keyFromString`; stable across releases, unlike display names.
+ */
+export declare function keyToString(key: KeyJs): string
+
+/** Cursor position relative to the display it is on. */
+export interface LocalPointJs {
+  displayId: number
+  x: number
+  y: number
+}
+
+/**
+ * Toggle state of the lock keys (whether the indicator is on, not whether the
+ * key is held).
+ */
+export interface LockStateJs {
+  capsLock: boolean
+  numLock: boolean
+  scrollLock: boolean
+}
+
+/** Check whether `mask` includes `event`. */
+export declare function maskHas(mask: number, event: EventTypeJs): boolean
+
+/**
+ * List the event types included in `mask`, in bit order. Bits above
+ * `EVENT_MASK_ALL` have no event type and are ignored.
+ */
+export declare function maskToEventTypes(mask: number): Array<EventTypeJs>
+
+/** Return `mask` with `event` added. */
+export declare function maskWith(mask: number, event: EventTypeJs): number
+
+/** Return `mask` with `event` removed. */
+export declare function maskWithout(mask: number, event: EventTypeJs): number
+
+export const MODIFIER_ALT: number
+
+export const MODIFIER_CTRL: number
+
+export const MODIFIER_META: number
+
+/**
+ * Bits of the `modifiers` field on InputHook event payloads. Left and right
+ * variants of a modifier share a bit.
+ */
+export const MODIFIER_SHIFT: number
+
+/** Payload for onModifierChange callbacks. */
+export interface ModifierChangeEventJs {
+  /** State after the transition. */
+  modifiers: ModifierStateJs
+  /** `"shift"`, `"ctrl"`, `"alt"` or `"meta"`. */
+  changed: string
+  /** `true` if `changed` went down, `false` if it went up. */
+  pressed: boolean
+  time: number
+}
+
+/** Held modifiers, with left and right sides collapsed. */
+export interface ModifierStateJs {
+  shift: boolean
+  ctrl: boolean
+  alt: boolean
+  meta: boolean
+}
+
 /** Mouse button event payload for onMouseDown / onMouseUp / onClick callbacks. */
 export interface MouseButtonEventJs {
   x: number
   y: number
   button: ButtonJs
   time: number
+  /** Sequence number, as on `KeyboardEventJs.seq`. */
+  seq: number
+  /** 1 for a single click, 2 for a double click, 3 for a triple click, etc. */
+  clickCount: number
+  /** Modifiers held when the event occurred (`MODIFIER_*` bits). */
+  modifiers: number
+  /** onMouseUp only: how long the button was held, in ms. */
+  heldMs?: number
+  /**
+   * onMouseUp only: `true` if no press was seen (the hook started mid-hold),
+   * in which case `heldMs` is 0.
+   */
+  synthetic?: boolean
+  /** As on `KeyboardEventJs.windowTitle`. */
+  windowTitle?: string
+  /** As on `KeyboardEventJs.processName`. */
+  processName?: string
+  /** As on `KeyboardEventJs.deviceId`. */
+  deviceId?: number
+  /** As on `KeyboardEventJs.raw`. */
+  raw?: RawEventDataJs
 }
 
 export interface MouseDataJs {
@@ -340,6 +1427,81 @@ export interface MouseMoveEventJs {
   x: number
   y: number
   time: number
+  /** Sequence number, as on `KeyboardEventJs.seq`. */
+  seq: number
+  /** Modifiers held when the event occurred (`MODIFIER_*` bits). */
+  modifiers: number
+  /** As on `KeyboardEventJs.deviceId`. */
+  deviceId?: number
+  /** As on `KeyboardEventJs.raw`. */
+  raw?: RawEventDataJs
+}
+
+/**
+ * Convert a captured event position into the point `simulateMouseMove`
+ * will actually put the cursor on. On Windows the hook's physical pixels are
+ * first converted to this process's logical pixels, which differ on scaled
+ * monitors unless Node runs per-monitor DPI aware. Points off every display
+ * are then clamped as in `simulateMouseMoveClamped`, and the result is snapped
+ * to the device-pixel grid of its display. Moving to the result and reading
+ * `getMousePosition()` gives the same point, which is not guaranteed for the
+ * raw position on scaled displays or across a disconnected monitor.
+ */
+export declare function normalizeCoordinateForSimulation(x: number, y: number): PointJs
+
+/**
+ * Normalize a key for binding comparison: compare `normalizeKey(pressed, opts)`
+ * against `normalizeKey(bound, opts)`. With no options the key is returned as is.
+ */
+export declare function normalizeKey(key: KeyJs, opts?: NormalizeKeyOptionsJs | undefined | null): KeyJs
+
+/** Options for `normalizeKey`. Both folds are off unless set. */
+export interface NormalizeKeyOptionsJs {
+  /**
+   * Map numpad keys to their main-keyboard twin (`Numpad1` → `Num1`,
+   * `NumpadEnter` → `Enter`, `NumpadSubtract` → `Minus`, ...). `NumpadAdd` and
+   * `NumpadMultiply` have no unshifted twin and are left alone.
+   */
+  foldNumpad?: boolean
+  /** Map right-side modifiers to the left-side key (`ShiftRight` → `ShiftLeft`). */
+  foldModifierSides?: boolean
+}
+
+/**
+ * The events `replayEvents` would simulate for a captured stream, with their
+ * original `time`s. Replaying these and capturing the result gives back the
+ * same press, release, move, drag and wheel events (the OS adds `KeyTyped`
+ * and `MouseClicked` events of its own), except for `Unknown` keys, which are
+ * left out.
+ */
+export declare function planReplay(events: Array<EventJs>): Array<EventJs>
+
+export interface PointJs {
+  x: number
+  y: number
+}
+
+/** Delivery priority of an event type under `InputHook.setRateLimit`. */
+export declare enum PriorityJs {
+  /** Shed first: only delivered while more than half the rate budget is left. */
+  Low = 0,
+  /** Delivered while the rate budget lasts (the default for every type). */
+  Normal = 1,
+  /** Always delivered, bypassing the rate limit. */
+  High = 2,
+}
+
+/** Native event fields attached to payloads by `setIncludeRaw(true)`. */
+export interface RawEventDataJs {
+  /** `"windows"`, `"macos"` or `"linux"`. */
+  platform: string
+  /**
+   * The native values monio passes through, by name: `rawCode` (Windows
+   * virtual-key code, macOS keycode or X11 keycode) for keys, `rawDelta` for
+   * the wheel, and the unrounded `x` and `y` for mouse events. monio converts
+   * the rest of the native record on the hook thread and does not keep it.
+   */
+  fields: Record<string, number>
 }
 
 export interface RectJs {
@@ -349,6 +1511,69 @@ export interface RectJs {
   height: number
 }
 
+/**
+ * Release every key and mouse button this process pressed via the simulation
+ * API without releasing. Also runs automatically on `shutdown()` and when the
+ * Node environment exits normally, and on every hook stop with
+ * `setReleaseSimulatedOnStop(true)`.
+ */
+export declare function releaseAllSimulated(): void
+
+/** Order in which `simulateKeyCombo` releases the keys it pressed. */
+export declare enum ReleaseOrderJs {
+  /** Mirror of the press order: final key first, then modifiers innermost-out. */
+  Reverse = 0,
+  /** Same order as pressed: modifiers first, final key last. */
+  Forward = 1,
+  /** Final key first, then modifiers in the order they were pressed. */
+  KeyFirst = 2,
+}
+
+/**
+ * Replay a stream captured from `startListen` (or built by hand) on a
+ * background thread, keeping the gaps between event `time`s divided by
+ * `speed` (default 1). Times are read in the current time base (see
+ * `setTimeBase`), so capture and replay under the same one. The stream is
+ * rebuilt as described by `planReplay` first, and wheel events keep their
+ * captured direction and delta. Resolves and releases like `simulateBinary`.
+ */
+export declare function replayEvents(events: Array<EventJs>, speed?: number | undefined | null, token?: CancelToken | undefined | null): Promise<SimulationStatusJs>
+
+/**
+ * Return to a `captureInputState` snapshot: release modifiers held now but
+ * not then, press those held then but not now, and move the cursor back.
+ * The snapshot is validated first and nothing is changed if it is invalid;
+ * after that every step is attempted and the first failure is thrown
+ * afterwards.
+ */
+export declare function restoreInputState(state: InputStateJs): void
+
+export const RING_HEADER_BYTES: number
+
+/**
+ * Version of the ring buffer layout below; bumped on any incompatible change.
+ *
+ * Layout v1 (host byte order, i.e. little-endian on all supported targets):
+ *
+ * Header, 4 × i32:
+ * - `[0]` records written so far (wrapping); `Atomics.wait` on this index
+ * - `[1]` layout version (`RING_LAYOUT_VERSION`)
+ * - `[2]` capacity in records
+ * - `[3]` record size in bytes (`RING_RECORD_BYTES`)
+ *
+ * Record `n` lives at byte `RING_HEADER_BYTES + (n % capacity) * RING_RECORD_BYTES`:
+ * - `+0`  u32 event type (`eventTypeCode`)
+ * - `+4`  u32 key (`KeyJs` value) for keyboard events, `buttonCode` for mouse
+ *   button events (255 when absent), `ScrollDirectionJs` for wheel events
+ * - `+8`  f64 time (seconds since the Unix epoch, regardless of `setTimeBase`)
+ * - `+16` f32 x, `+20` f32 y (0 for keyboard events)
+ * - `+24` u32 raw code for keyboard events, f32 delta for wheel events
+ * - `+28` u32 modifiers (`MODIFIER_*` bits)
+ */
+export const RING_LAYOUT_VERSION: number
+
+export const RING_RECORD_BYTES: number
+
 export declare enum ScrollDirectionJs {
   Up = 0,
   Down = 1,
@@ -356,26 +1581,285 @@ export declare enum ScrollDirectionJs {
   Right = 3,
 }
 
-/** Press a key */
-export declare function simulateKeyPress(key: KeyJs): void
+/**
+ * Ask the focused window to switch to the layout with `id` (see
+ * `getKeyboardLayouts`). Best-effort: the request is asynchronous and an
+ * application may ignore it. Windows only for now; other platforms return an
+ * error.
+ */
+export declare function setActiveKeyboardLayout(id: string): void
+
+/**
+ * Set the pause (default 15 ms) that move-then-act helpers such as
+ * `simulateScrollAt` and `simulateMouseMoveVerified` wait after moving the
+ * cursor, for helpers not given their own `settleMs`. Raise it where the
+ * target reacts slowly, e.g. over remote desktop.
+ */
+export declare function setDefaultSettleMs(ms: number): void
+
+/**
+ * Set (or clear, with `null`) a callback receiving native-side diagnostics:
+ * hooks installed/removed, hooks disabled by the OS, dropped events (as a
+ * count, at most once a second), and install failures such as missing
+ * permissions.
+ *
+ * `level` is one of `"info"`, `"warn"` or `"error"`. Logging is off by default.
+ * The callback does not keep the process alive.
+ */
+export declare function setLogCallback(callback?: ((level: string, message: string) => void) | null | undefined): void
+
+/**
+ * Also release held simulated input (see `releaseAllSimulated`) whenever a
+ * `startListen` or `InputHook` hook stops: `stop()`, an auto-stop, or a
+ * running hook being garbage-collected. Off by default, since the hook being
+ * stopped need not belong to the automation holding the keys.
+ */
+export declare function setReleaseSimulatedOnStop(enabled: boolean): void
+
+/**
+ * Refuse key presses, clicks and typing while the focused window belongs to a
+ * secure system prompt (UAC, logon/lock screen, credential prompts), including
+ * input posted with `simulateKeyToWindow`/`simulateClickToWindow`. Refused
+ * calls throw an error containing `[SecureContext]`. Moves, scrolls and
+ * releases are never refused, so held input can always be let go. Off by
+ * default.
+ *
+ * Detection uses `getFocusedWindow`, so it only works on Windows; there,
+ * having no foreground window at all (the secure desktop) also counts as a
+ * secure prompt. The foreground window is looked up at most every 100 ms, so
+ * a prompt may take that long to be noticed.
+ */
+export declare function setRequireForegroundApp(enabled: boolean): void
+
+/**
+ * Run the basic `simulate*` calls (mouse move/press/release/click, key
+ * press/release/tap, `simulateKeysPress`/`Release`, `simulateKeyTapIsolated`,
+ * `simulateKeyCombo`, `typeText`, scan code input) on a dedicated thread, in
+ * call order, so a slow injection never blocks the JS thread. While enabled
+ * those calls return as soon as the input is queued; argument validation
+ * still throws immediately, but injection failures are reported by
+ * `awaitSimulationDrained()`. Other simulation functions are not queued, so
+ * await the drain before mixing them in. Disabling lets queued input finish.
+ */
+export declare function setSimulationQueue(enabled: boolean): void
+
+/**
+ * Choose what `time` reports on events from `startListen` and `InputHook`.
+ *
+ * For `PerformanceNow`, pass the current `performance.now()` so the native
+ * clock can be aligned with it: `setTimeBase(TimeBaseJs.PerformanceNow,
+ * performance.now())`. Event `time` is then directly comparable with
+ * `performance.now()` readings on that thread. The ring buffer (see
+ * `attachRingBuffer`) always records Unix seconds.
+ */
+export declare function setTimeBase(base: TimeBaseJs, performanceNow?: number | undefined | null): void
+
+/**
+ * Run a compact binary input script on a background thread, resolving
+ * `Completed` or, if `token` is cancelled, `Cancelled`. Input the script
+ * pressed is released if it is cancelled or fails; otherwise keys and buttons
+ * it leaves down stay held as with `simulateKeyPress`.
+ *
+ * Format version 1: one version byte (`1`), then records of an opcode byte,
+ * its payload, and an unsigned LEB128 varint delay in ms to wait afterwards.
+ * Numbers are little-endian; coordinates and wheel deltas are `f32`, keys are
+ * `u16` `KeyJs` values and buttons are `u8` `buttonCode` values.
+ *
+ * | Opcode | Action        | Payload                                           |
+ * |--------|---------------|---------------------------------------------------|
+ * | `0x01` | move          | x, y                                              |
+ * | `0x02` | mouse press   | button                                            |
+ * | `0x03` | mouse release | button                                            |
+ * | `0x04` | click         | button                                            |
+ * | `0x05` | key press     | key                                               |
+ * | `0x06` | key release   | key                                               |
+ * | `0x07` | key tap       | key                                               |
+ * | `0x08` | wheel         | x, y, direction (`u8` `ScrollDirectionJs`), delta |
+ *
+ * The whole script is validated before anything runs; a malformed one
+ * throws with the offending byte offset.
+ */
+export declare function simulateBinary(buffer: Buffer, token?: CancelToken | undefined | null): Promise<SimulationStatusJs>
+
+/**
+ * Click at `(x, y)` relative to a window's client area by posting button
+ * messages to it, so the window does not need focus. Same platform support and
+ * caveats as `simulateKeyToWindow`; the real cursor does not move.
+ */
+export declare function simulateClickToWindow(windowHandle: number, x: number, y: number, button: ButtonJs): void
+
+/**
+ * Press each key in order (e.g. `[ControlLeft, ShiftLeft, KeyT]`), then release them.
+ *
+ * Every key except the last must be a modifier, and keys must not repeat;
+ * otherwise this fails with `InvalidArg` before anything is pressed. A combo of
+ * only modifiers (e.g. `[ControlLeft, ShiftLeft]`) is allowed and simply taps
+ * them together.
+ *
+ * `release_order` defaults to `Reverse`. If a press fails part-way, the keys that
+ * were already pressed are still released before the error is returned.
+ */
+export declare function simulateKeyCombo(keys: Array<KeyJs>, releaseOrder?: ReleaseOrderJs | undefined | null): void
+
+/**
+ * Hold `key` down for `durationMs`, then release it. Cancelling via the
+ * `CancelToken` releases the key immediately and resolves `Cancelled`.
+ */
+export declare function simulateKeyHold(key: KeyJs, durationMs: number, token?: CancelToken | undefined | null): Promise<SimulationStatusJs>
+
+/** Press a key. `deviceId` as on `simulateMouseMove`. */
+export declare function simulateKeyPress(key: KeyJs, deviceId?: number | undefined | null): void
+
+/** Release a key. `deviceId` as on `simulateMouseMove`. */
+export declare function simulateKeyRelease(key: KeyJs, deviceId?: number | undefined | null): void
+
+/**
+ * Press and hold several keys at once, in array order. Unlike
+ * `simulateKeyCombo` nothing is released until `simulateKeysRelease` (or
+ * `releaseAllSimulated`). If a press fails, keys this call newly pressed are
+ * released again before the error is returned; keys that were already held
+ * stay held.
+ */
+export declare function simulateKeysPress(keys: Array<KeyJs>): void
+
+/**
+ * Release several keys, in reverse array order, so passing the same array
+ * given to `simulateKeysPress` unwinds the chord. Every key is attempted; the
+ * first failure is returned.
+ */
+export declare function simulateKeysRelease(keys: Array<KeyJs>): void
+
+/** Tap a key (press + release). `deviceId` as on `simulateMouseMove`. */
+export declare function simulateKeyTap(key: KeyJs, deviceId?: number | undefined | null): void
+
+/**
+ * Tap a key without the user's physically held modifiers leaking into it:
+ * held modifiers are released, the key is tapped, and they are pressed again.
+ *
+ * Modifier state comes from running hooks (`startListen` or `InputHook`);
+ * with no hook running nothing is known and this behaves like `simulateKeyTap`.
+ * Modifiers held via `simulateKeyPress` are left alone.
+ */
+export declare function simulateKeyTapIsolated(key: KeyJs): void
+
+/**
+ * Tap `key` in a specific window by posting it to the window's message queue,
+ * so the window does not need focus. `windowHandle` comes from
+ * `getFocusedWindow().handle`.
+ *
+ * Windows only; other platforms return an error. Posted input bypasses the
+ * system input queue: modifier state is not faked, and apps that read raw
+ * input or poll key state (many games, some Chromium/Electron and UWP apps)
+ * ignore it entirely.
+ */
+export declare function simulateKeyToWindow(windowHandle: number, key: KeyJs): void
+
+/**
+ * Click a mouse button (press + release). `deviceId` as on
+ * `simulateMouseMove`.
+ */
+export declare function simulateMouseClick(button: ButtonJs, deviceId?: number | undefined | null): void
+
+/**
+ * Move the mouse to absolute coordinates. `deviceId` is accepted for
+ * forward compatibility but throws `[Unsupported]` when set.
+ */
+export declare function simulateMouseMove(x: number, y: number, deviceId?: number | undefined | null): void
+
+/**
+ * Move the mouse, redirecting points that fall outside every current display
+ * (e.g. coordinates from a monitor that has since been disconnected) to the
+ * nearest point on the nearest display.
+ */
+export declare function simulateMouseMoveClamped(x: number, y: number): ClampedMoveJs
+
+/**
+ * Move the mouse to logical (point) coordinates relative to the top-left of
+ * display `displayId`: they are multiplied by that display's `scaleFactor`
+ * and offset by its origin. Returns the global point moved to.
+ */
+export declare function simulateMouseMoveLogical(displayId: number, logicalX: number, logicalY: number): PointJs
+
+/**
+ * Glide the cursor in a straight line from its current position to `(x, y)`
+ * over `durationMs`, moving once per frame at `frameRate` (default: the
+ * highest display refresh rate, see `getHighestRefreshRate`, else 125 Hz).
+ * With a `CancelToken` the cursor stops where it is on cancel and the Promise
+ * resolves `Cancelled`.
+ */
+export declare function simulateMouseMoveSmooth(x: number, y: number, durationMs: number, token?: CancelToken | undefined | null, frameRate?: number | undefined | null): Promise<SimulationStatusJs>
+
+/**
+ * Move the mouse to the center of the display with the given `id` (see
+ * `getDisplays`), wherever it sits in the global layout. Returns the point moved to.
+ */
+export declare function simulateMouseMoveToDisplayCenter(displayId: number): PointJs
+
+/**
+ * Move the cursor to `(x, y)` and read the position back, retrying up to
+ * `retries` times (default 3) while it is more than `tolerancePx` (default 1)
+ * away on either axis. Resolves with whether the cursor ended up in range.
+ * Each attempt waits `settleMs` (default: see `setDefaultSettleMs`) before
+ * reading back.
+ */
+export declare function simulateMouseMoveVerified(x: number, y: number, tolerancePx?: number | undefined | null, retries?: number | undefined | null, settleMs?: number | undefined | null): Promise<boolean>
+
+/** Press a mouse button. `deviceId` as on `simulateMouseMove`. */
+export declare function simulateMousePress(button: ButtonJs, deviceId?: number | undefined | null): void
+
+/** Release a mouse button. `deviceId` as on `simulateMouseMove`. */
+export declare function simulateMouseRelease(button: ButtonJs, deviceId?: number | undefined | null): void
 
-/** Release a key */
-export declare function simulateKeyRelease(key: KeyJs): void
+/**
+ * Pinch by `scale` (>1 zooms in, <1 zooms out) centred on `(x, y)`. Throws
+ * with an `[Unsupported]` prefix where `canSimulateGestures()` is `false`.
+ */
+export declare function simulatePinch(scale: number, x: number, y: number): void
+
+/**
+ * Rotate by `degrees` (clockwise positive) around `(x, y)`. Throws with an
+ * `[Unsupported]` prefix where `canSimulateGestures()` is `false`.
+ */
+export declare function simulateRotate(degrees: number, x: number, y: number): void
+
+/**
+ * Press a key by hardware scan code (see `keyToScancode`), for games that
+ * read scan codes and ignore virtual-key input. Windows only for now; other
+ * platforms return an error.
+ */
+export declare function simulateScancodePress(scancode: number): void
 
-/** Tap a key (press + release) */
-export declare function simulateKeyTap(key: KeyJs): void
+/** Release a key by hardware scan code. See `simulateScancodePress`. */
+export declare function simulateScancodeRelease(scancode: number): void
 
-/** Click a mouse button (press + release) */
-export declare function simulateMouseClick(button: ButtonJs): void
+/** Press and release a key by hardware scan code. See `simulateScancodePress`. */
+export declare function simulateScancodeTap(scancode: number): void
 
-/** Move the mouse to absolute coordinates */
-export declare function simulateMouseMove(x: number, y: number): void
+/**
+ * Move the cursor to `(x, y)`, let it settle for `settleMs` (default: see
+ * `setDefaultSettleMs`), then scroll there. Runs off the JS thread; the
+ * returned Promise resolves once the wheel event is sent.
+ */
+export declare function simulateScrollAt(x: number, y: number, direction: ScrollDirectionJs, delta: number, settleMs?: number | undefined | null): Promise<void>
+
+/** How a cancellable simulation helper finished. */
+export declare enum SimulationStatusJs {
+  Completed = 0,
+  Cancelled = 1,
+}
 
-/** Press a mouse button */
-export declare function simulateMousePress(button: ButtonJs): void
+/** Payload for `InputHook.onSlowConsumer`. */
+export interface SlowConsumerEventJs {
+  /** Events queued for JS callbacks that had not started running yet. */
+  backlog: number
+  time: number
+}
 
-/** Release a mouse button */
-export declare function simulateMouseRelease(button: ButtonJs): void
+/**
+ * Split `text` the way `typeText` does, e.g. `"e\u0301😀"` → `["é", "😀"]`.
+ * Useful for progress reporting or for typing long text in chunks.
+ */
+export declare function splitTextUnits(text: string): Array<string>
 
 /**
  * Start listening for input events with a callback.
@@ -387,9 +1871,21 @@ export declare function simulateMouseRelease(button: ButtonJs): void
  *
  * Use the `EVENT_MASK_*` constants to compose masks. If `None`, all events are forwarded.
  * The mask can be updated at runtime via `HookJs.setEventMask()`.
+ *
+ * Install failures throw with a code prefix: `[PermissionDenied]`, `[Unsupported]`,
+ * `[AlreadyInstalledByAnotherProcess]` or `[Unknown]`. Only for a recognised transient
+ * error, `[TemporaryFailure]`, is the hook returned without running (see
+ * `HookJs.installError`) so it can be `retry()`ed.
  */
 export declare function startListen(callback: (event: EventJs) => void, eventMask?: number | undefined | null): HookJs
 
+/**
+ * Start listening with every filter from `config` applied from the first event,
+ * so there is no window where defaults are in effect. Use `HookJs.config` to
+ * read the effective configuration back as a plain object.
+ */
+export declare function startListenWithConfig(callback: (event: EventJs) => void, config: HookConfigJs): HookJs
+
 export interface SystemSettingsJs {
   keyboardRepeatRate?: number
   keyboardRepeatDelay?: number
@@ -400,6 +1896,70 @@ export interface SystemSettingsJs {
   keyboardLayout?: string
 }
 
+/** Payload for onTapDisabled callbacks. */
+export interface TapDisabledEventJs {
+  time: number
+}
+
+/** What the `time` field of delivered events measures (see `setTimeBase`). */
+export declare enum TimeBaseJs {
+  /** Seconds since the Unix epoch (the default). */
+  UnixEpoch = 0,
+  /**
+   * Seconds on a monotonic clock starting at the `setTimeBase` call, immune to
+   * wall-clock adjustments.
+   */
+  Monotonic = 1,
+  /** Milliseconds on the caller's `performance.now()` clock. */
+  PerformanceNow = 2,
+}
+
+/** One position in `InputHook.getMouseTrail()`. */
+export interface TrailPointJs {
+  x: number
+  y: number
+  time: number
+}
+
+/** Non-throwing outcome of `InputHook.tryStart()` / `tryStop()`. */
+export interface TryResultJs {
+  ok: boolean
+  /**
+   * Install failure code (`"PermissionDenied"`, `"Unsupported"`,
+   * `"AlreadyInstalledByAnotherProcess"`, `"TemporaryFailure"`, `"Unknown"`).
+   */
+  code?: string
+  message?: string
+}
+
+/**
+ * Type `text` as if entered on the keyboard.
+ *
+ * Text is injected one user-perceived character at a time (see
+ * `splitTextUnits`), so surrogate pairs, emoji and combining sequences are
+ * never split. On Windows every unit is sent as Unicode input, independent of
+ * the keyboard layout. Elsewhere each unit is typed with the key that produces
+ * it under the active layout (see `keyToChar`), with Shift if needed, so only
+ * those characters plus `\n` and `\t` can be typed; anything else (AltGr or
+ * Option levels, dead-key compositions) fails with `InvalidArg` after the
+ * preceding text has been typed.
+ */
+export declare function typeText(text: string): void
+
+/**
+ * Lift a block started by `blockUserInput` and wait until real input is
+ * accepted again. Does nothing if input is not blocked.
+ */
+export declare function unblockUserInput(): void
+
+/**
+ * Check that every code in `0..KEY_JS_COUNT` maps to the `KeyJs` variant
+ * with that code, and that the variant has a name `keyFromString` accepts,
+ * a display name and a category (only `Unknown` may be unknown). Also
+ * checks that `KEY_JS_COUNT` itself is not stale. Throws listing every gap.
+ */
+export declare function verifyKeyTables(): void
+
 export interface WheelDataJs {
   x: number
   y: number
@@ -412,6 +1972,20 @@ export interface WheelEventJs {
   x: number
   y: number
   direction: ScrollDirectionJs
+  /** In notches when `setNormalizeWheelDelta(true)`, else as `rawDelta`. */
   delta: number
+  /**
+   * Delta as reported by the platform (Windows: multiples of 120 per notch;
+   * macOS and X11: lines or clicks).
+   */
+  rawDelta: number
   time: number
+  /** Sequence number, as on `KeyboardEventJs.seq`. */
+  seq: number
+  /** Modifiers held when the event occurred (`MODIFIER_*` bits). */
+  modifiers: number
+  /** As on `KeyboardEventJs.deviceId`. */
+  deviceId?: number
+  /** As on `KeyboardEventJs.raw`. */
+  raw?: RawEventDataJs
 }
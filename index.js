@@ -576,35 +576,143 @@ if (!nativeBinding) {
 }
 
 module.exports = nativeBinding
+module.exports.Automation = nativeBinding.Automation
+module.exports.CancelToken = nativeBinding.CancelToken
 module.exports.HookJs = nativeBinding.HookJs
+module.exports.HotkeyManager = nativeBinding.HotkeyManager
 module.exports.InputHook = nativeBinding.InputHook
+module.exports.RelativeMover = nativeBinding.RelativeMover
+module.exports.awaitSimulationDrained = nativeBinding.awaitSimulationDrained
+module.exports.blockUserInput = nativeBinding.blockUserInput
+module.exports.buttonCode = nativeBinding.buttonCode
+module.exports.buttonFromCode = nativeBinding.buttonFromCode
 module.exports.ButtonJs = nativeBinding.ButtonJs
+module.exports.CallModeJs = nativeBinding.CallModeJs
+module.exports.canSimulateGestures = nativeBinding.canSimulateGestures
+module.exports.canSimulateKey = nativeBinding.canSimulateKey
+module.exports.captureInputState = nativeBinding.captureInputState
+module.exports.checkAccessibilityPermission = nativeBinding.checkAccessibilityPermission
+module.exports.checkInputMonitoringPermission = nativeBinding.checkInputMonitoringPermission
 module.exports.computeEventMask = nativeBinding.computeEventMask
+module.exports.describeEvent = nativeBinding.describeEvent
+module.exports.describeMask = nativeBinding.describeMask
+module.exports.diffDisplays = nativeBinding.diffDisplays
+module.exports.diffSystemSettings = nativeBinding.diffSystemSettings
 module.exports.EVENT_MASK_ALL = nativeBinding.EVENT_MASK_ALL
 module.exports.EVENT_MASK_KEYBOARD = nativeBinding.EVENT_MASK_KEYBOARD
 module.exports.EVENT_MASK_MOUSE_ALL = nativeBinding.EVENT_MASK_MOUSE_ALL
 module.exports.EVENT_MASK_MOUSE_BUTTONS = nativeBinding.EVENT_MASK_MOUSE_BUTTONS
 module.exports.EVENT_MASK_MOUSE_MOVEMENT = nativeBinding.EVENT_MASK_MOUSE_MOVEMENT
 module.exports.EVENT_MASK_MOUSE_WHEEL = nativeBinding.EVENT_MASK_MOUSE_WHEEL
+module.exports.eventToKeyboard = nativeBinding.eventToKeyboard
+module.exports.eventToMouseButton = nativeBinding.eventToMouseButton
+module.exports.eventToMouseMove = nativeBinding.eventToMouseMove
+module.exports.eventToWheel = nativeBinding.eventToWheel
+module.exports.eventTypeCode = nativeBinding.eventTypeCode
+module.exports.eventTypeFromCode = nativeBinding.eventTypeFromCode
 module.exports.EventTypeJs = nativeBinding.EventTypeJs
+module.exports.filterAll = nativeBinding.filterAll
+module.exports.filterAny = nativeBinding.filterAny
+module.exports.filterButtons = nativeBinding.filterButtons
+module.exports.filterEventTypes = nativeBinding.filterEventTypes
+module.exports.filterKeys = nativeBinding.filterKeys
+module.exports.filterRegion = nativeBinding.filterRegion
 module.exports.getAllKeyDisplayInfo = nativeBinding.getAllKeyDisplayInfo
+module.exports.getAllKeyDisplayInfoLocalized = nativeBinding.getAllKeyDisplayInfoLocalized
 module.exports.getButtonDisplayName = nativeBinding.getButtonDisplayName
+module.exports.getDefaultSettleMs = nativeBinding.getDefaultSettleMs
 module.exports.getDisplayAtPoint = nativeBinding.getDisplayAtPoint
+module.exports.getDisplayForFocusedWindow = nativeBinding.getDisplayForFocusedWindow
 module.exports.getDisplays = nativeBinding.getDisplays
+module.exports.getDisplaySnapshot = nativeBinding.getDisplaySnapshot
+module.exports.getDisplaysOrEmpty = nativeBinding.getDisplaysOrEmpty
+module.exports.getEventTypeDisplayName = nativeBinding.getEventTypeDisplayName
+module.exports.getFocusedWindow = nativeBinding.getFocusedWindow
+module.exports.getHighestRefreshRate = nativeBinding.getHighestRefreshRate
+module.exports.getKeyboardLayouts = nativeBinding.getKeyboardLayouts
 module.exports.getKeyCategory = nativeBinding.getKeyCategory
+module.exports.getKeyCategoryEnum = nativeBinding.getKeyCategoryEnum
 module.exports.getKeyDisplayName = nativeBinding.getKeyDisplayName
+module.exports.getKeyDisplayNameAscii = nativeBinding.getKeyDisplayNameAscii
+module.exports.getKeyDisplayNameLocalized = nativeBinding.getKeyDisplayNameLocalized
+module.exports.getLockState = nativeBinding.getLockState
 module.exports.getMousePosition = nativeBinding.getMousePosition
+module.exports.getMousePositionLocal = nativeBinding.getMousePositionLocal
 module.exports.getPrimaryDisplay = nativeBinding.getPrimaryDisplay
+module.exports.getPrimaryDisplayOrNull = nativeBinding.getPrimaryDisplayOrNull
+module.exports.getScaleFactorAtPoint = nativeBinding.getScaleFactorAtPoint
+module.exports.getSimulatableKeys = nativeBinding.getSimulatableKeys
 module.exports.getSystemSettings = nativeBinding.getSystemSettings
+module.exports.hotkeyFromString = nativeBinding.hotkeyFromString
+module.exports.hotkeyToString = nativeBinding.hotkeyToString
 module.exports.isInputPattern = nativeBinding.isInputPattern
+module.exports.isMaskSubset = nativeBinding.isMaskSubset
 module.exports.isModifierKey = nativeBinding.isModifierKey
+module.exports.KEY_JS_COUNT = nativeBinding.KEY_JS_COUNT
+module.exports.KeyCategoryJs = nativeBinding.KeyCategoryJs
+module.exports.keyFromString = nativeBinding.keyFromString
 module.exports.KeyJs = nativeBinding.KeyJs
+module.exports.keyToChar = nativeBinding.keyToChar
+module.exports.keyToScancode = nativeBinding.keyToScancode
+module.exports.keyToString = nativeBinding.keyToString
+module.exports.maskHas = nativeBinding.maskHas
+module.exports.maskToEventTypes = nativeBinding.maskToEventTypes
+module.exports.maskWith = nativeBinding.maskWith
+module.exports.maskWithout = nativeBinding.maskWithout
+module.exports.MODIFIER_ALT = nativeBinding.MODIFIER_ALT
+module.exports.MODIFIER_CTRL = nativeBinding.MODIFIER_CTRL
+module.exports.MODIFIER_META = nativeBinding.MODIFIER_META
+module.exports.MODIFIER_SHIFT = nativeBinding.MODIFIER_SHIFT
+module.exports.normalizeCoordinateForSimulation = nativeBinding.normalizeCoordinateForSimulation
+module.exports.normalizeKey = nativeBinding.normalizeKey
+module.exports.planReplay = nativeBinding.planReplay
+module.exports.PriorityJs = nativeBinding.PriorityJs
+module.exports.releaseAllSimulated = nativeBinding.releaseAllSimulated
+module.exports.ReleaseOrderJs = nativeBinding.ReleaseOrderJs
+module.exports.replayEvents = nativeBinding.replayEvents
+module.exports.restoreInputState = nativeBinding.restoreInputState
+module.exports.RING_HEADER_BYTES = nativeBinding.RING_HEADER_BYTES
+module.exports.RING_LAYOUT_VERSION = nativeBinding.RING_LAYOUT_VERSION
+module.exports.RING_RECORD_BYTES = nativeBinding.RING_RECORD_BYTES
 module.exports.ScrollDirectionJs = nativeBinding.ScrollDirectionJs
+module.exports.setActiveKeyboardLayout = nativeBinding.setActiveKeyboardLayout
+module.exports.setDefaultSettleMs = nativeBinding.setDefaultSettleMs
+module.exports.setLogCallback = nativeBinding.setLogCallback
+module.exports.setReleaseSimulatedOnStop = nativeBinding.setReleaseSimulatedOnStop
+module.exports.setRequireForegroundApp = nativeBinding.setRequireForegroundApp
+module.exports.setSimulationQueue = nativeBinding.setSimulationQueue
+module.exports.setTimeBase = nativeBinding.setTimeBase
+module.exports.simulateBinary = nativeBinding.simulateBinary
+module.exports.simulateClickToWindow = nativeBinding.simulateClickToWindow
+module.exports.simulateKeyCombo = nativeBinding.simulateKeyCombo
+module.exports.simulateKeyHold = nativeBinding.simulateKeyHold
 module.exports.simulateKeyPress = nativeBinding.simulateKeyPress
 module.exports.simulateKeyRelease = nativeBinding.simulateKeyRelease
+module.exports.simulateKeysPress = nativeBinding.simulateKeysPress
+module.exports.simulateKeysRelease = nativeBinding.simulateKeysRelease
 module.exports.simulateKeyTap = nativeBinding.simulateKeyTap
+module.exports.simulateKeyTapIsolated = nativeBinding.simulateKeyTapIsolated
+module.exports.simulateKeyToWindow = nativeBinding.simulateKeyToWindow
 module.exports.simulateMouseClick = nativeBinding.simulateMouseClick
 module.exports.simulateMouseMove = nativeBinding.simulateMouseMove
+module.exports.simulateMouseMoveClamped = nativeBinding.simulateMouseMoveClamped
+module.exports.simulateMouseMoveLogical = nativeBinding.simulateMouseMoveLogical
+module.exports.simulateMouseMoveSmooth = nativeBinding.simulateMouseMoveSmooth
+module.exports.simulateMouseMoveToDisplayCenter = nativeBinding.simulateMouseMoveToDisplayCenter
+module.exports.simulateMouseMoveVerified = nativeBinding.simulateMouseMoveVerified
 module.exports.simulateMousePress = nativeBinding.simulateMousePress
 module.exports.simulateMouseRelease = nativeBinding.simulateMouseRelease
+module.exports.simulatePinch = nativeBinding.simulatePinch
+module.exports.simulateRotate = nativeBinding.simulateRotate
+module.exports.simulateScancodePress = nativeBinding.simulateScancodePress
+module.exports.simulateScancodeRelease = nativeBinding.simulateScancodeRelease
+module.exports.simulateScancodeTap = nativeBinding.simulateScancodeTap
+module.exports.simulateScrollAt = nativeBinding.simulateScrollAt
+module.exports.SimulationStatusJs = nativeBinding.SimulationStatusJs
+module.exports.splitTextUnits = nativeBinding.splitTextUnits
 module.exports.startListen = nativeBinding.startListen
+module.exports.startListenWithConfig = nativeBinding.startListenWithConfig
+module.exports.TimeBaseJs = nativeBinding.TimeBaseJs
+module.exports.typeText = nativeBinding.typeText
+module.exports.unblockUserInput = nativeBinding.unblockUserInput
+module.exports.verifyKeyTables = nativeBinding.verifyKeyTables
@@ -5,15 +5,19 @@ use napi::threadsafe_function::{
   ThreadsafeCallContext, ThreadsafeFunction, ThreadsafeFunctionCallMode,
 };
 use napi_derive::napi;
-use std::sync::atomic::{AtomicU32, Ordering};
-use std::sync::{Arc, Mutex};
-use std::time::UNIX_EPOCH;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicU32, AtomicU64, Ordering};
+use std::sync::{mpsc, Arc, Condvar, Mutex, Weak};
+use std::thread::{self, ThreadId};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+mod platform;
 
 // Re-export monio types
 use monio::{
-  display_at_point, displays, key_press, key_release, key_tap, mouse_click, mouse_move,
-  mouse_position, mouse_press, mouse_release, primary_display, system_settings, Button,
-  DisplayInfo, Event, EventType, Hook, Key, Rect, ScrollDirection, SystemSettings,
+  display_at_point, displays, mouse_click, mouse_move, mouse_position, mouse_press, mouse_release,
+  primary_display, simulate, system_settings, Button, DisplayInfo, Event, EventType, Hook, Key,
+  Rect, ScrollDirection, SystemSettings, WheelData,
 };
 
 // ============================================================================
@@ -53,6 +57,24 @@ impl From<EventType> for EventTypeJs {
   }
 }
 
+impl From<EventTypeJs> for EventType {
+  fn from(et: EventTypeJs) -> Self {
+    match et {
+      EventTypeJs::HookEnabled => EventType::HookEnabled,
+      EventTypeJs::HookDisabled => EventType::HookDisabled,
+      EventTypeJs::KeyPressed => EventType::KeyPressed,
+      EventTypeJs::KeyReleased => EventType::KeyReleased,
+      EventTypeJs::KeyTyped => EventType::KeyTyped,
+      EventTypeJs::MousePressed => EventType::MousePressed,
+      EventTypeJs::MouseReleased => EventType::MouseReleased,
+      EventTypeJs::MouseClicked => EventType::MouseClicked,
+      EventTypeJs::MouseMoved => EventType::MouseMoved,
+      EventTypeJs::MouseDragged => EventType::MouseDragged,
+      EventTypeJs::MouseWheel => EventType::MouseWheel,
+    }
+  }
+}
+
 #[napi]
 pub enum ButtonJs {
   Left,
@@ -579,7 +601,27 @@ impl From<KeyJs> for Key {
 // Display Name Helpers (single source of truth for key/button presentation)
 // ============================================================================
 
-fn key_display_name(key: KeyJs) -> &'static str {
+/// Meta and Alt as labelled on this platform's keyboards: ⌘/Option on macOS,
+/// Win/Alt on Windows, Super/Alt elsewhere. `META_LABEL_ASCII` is for
+/// `getKeyDisplayNameAscii`.
+#[cfg(target_os = "macos")]
+const META_LABEL: &str = "\u{2318}"; // ⌘
+#[cfg(target_os = "macos")]
+const META_LABEL_ASCII: &str = "Cmd";
+#[cfg(target_os = "macos")]
+const ALT_LABEL: &str = "Option";
+#[cfg(target_os = "windows")]
+const META_LABEL: &str = "Win";
+#[cfg(target_os = "windows")]
+const META_LABEL_ASCII: &str = "Win";
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const META_LABEL: &str = "Super";
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const META_LABEL_ASCII: &str = "Super";
+#[cfg(not(target_os = "macos"))]
+const ALT_LABEL: &str = "Alt";
+
+fn key_display_name(key: &KeyJs) -> &'static str {
   match key {
     // Letters
     KeyJs::KeyA => "A",
@@ -643,10 +685,10 @@ fn key_display_name(key: KeyJs) -> &'static str {
     KeyJs::ShiftRight => "Shift",
     KeyJs::ControlLeft => "Ctrl",
     KeyJs::ControlRight => "Ctrl",
-    KeyJs::AltLeft => "Alt",
-    KeyJs::AltRight => "Alt",
-    KeyJs::MetaLeft => "\u{2318}",  // ⌘
-    KeyJs::MetaRight => "\u{2318}", // ⌘
+    KeyJs::AltLeft => ALT_LABEL,
+    KeyJs::AltRight => ALT_LABEL,
+    KeyJs::MetaLeft => META_LABEL,
+    KeyJs::MetaRight => META_LABEL,
     KeyJs::CapsLock => "Caps",
     KeyJs::Delete => "Del",
     // Arrows
@@ -739,6 +781,27 @@ fn key_display_name(key: KeyJs) -> &'static str {
   }
 }
 
+/// ASCII stand-ins for the labels in `key_display_name` that use symbols. Every
+/// other key's label is already ASCII and is shared with the pretty form.
+fn key_ascii_override(key: &KeyJs) -> Option<&'static str> {
+  let name = match key {
+    KeyJs::Enter => "Enter",
+    KeyJs::Backspace => "Bksp",
+    KeyJs::MetaLeft | KeyJs::MetaRight => META_LABEL_ASCII,
+    KeyJs::ArrowLeft => "Left",
+    KeyJs::ArrowRight => "Right",
+    KeyJs::ArrowUp => "Up",
+    KeyJs::ArrowDown => "Down",
+    KeyJs::IntlYen => "Yen",
+    _ => return None,
+  };
+  Some(name)
+}
+
+fn key_display_name_ascii(key: KeyJs) -> &'static str {
+  key_ascii_override(&key).unwrap_or_else(|| key_display_name(&key))
+}
+
 fn button_display_name(button: ButtonJs) -> &'static str {
   match button {
     ButtonJs::Left => "MouseL",
@@ -750,32 +813,68 @@ fn button_display_name(button: ButtonJs) -> &'static str {
   }
 }
 
-fn key_category(key: KeyJs) -> &'static str {
+fn event_type_display_name(et: &EventType) -> &'static str {
+  match et {
+    EventType::HookEnabled => "Hook Enabled",
+    EventType::HookDisabled => "Hook Disabled",
+    EventType::KeyPressed => "Key Pressed",
+    EventType::KeyReleased => "Key Released",
+    EventType::KeyTyped => "Key Typed",
+    EventType::MousePressed => "Mouse Pressed",
+    EventType::MouseReleased => "Mouse Released",
+    EventType::MouseClicked => "Mouse Clicked",
+    EventType::MouseMoved => "Mouse Moved",
+    EventType::MouseDragged => "Mouse Dragged",
+    EventType::MouseWheel => "Mouse Wheel",
+  }
+}
+
+/// Key category; `getKeyCategory` returns the same categories as lowercase strings.
+#[napi]
+pub enum KeyCategoryJs {
+  Letter,
+  Number,
+  Function,
+  Modifier,
+  Arrow,
+  Navigation,
+  Numpad,
+  Media,
+  Browser,
+  Application,
+  International,
+  Punctuation,
+  Lock,
+  Special,
+  Unknown,
+}
+
+fn key_category_of(key: KeyJs) -> KeyCategoryJs {
   let k: Key = key.into();
   if k.is_modifier() {
-    return "modifier";
+    return KeyCategoryJs::Modifier;
   }
   if k.is_letter() {
-    return "letter";
+    return KeyCategoryJs::Letter;
   }
   if k.is_number() {
-    return "number";
+    return KeyCategoryJs::Number;
   }
   if k.is_function_key() {
-    return "function";
+    return KeyCategoryJs::Function;
   }
   if k.is_numpad() {
-    return "numpad";
+    return KeyCategoryJs::Numpad;
   }
   if k.is_media() {
-    return "media";
+    return KeyCategoryJs::Media;
   }
   // monio's is_navigation includes arrows, home, end, pageup, pagedown
   if k.is_navigation() {
     // Distinguish arrow keys from other navigation
     return match k {
-      Key::ArrowLeft | Key::ArrowRight | Key::ArrowUp | Key::ArrowDown => "arrow",
-      _ => "navigation",
+      Key::ArrowLeft | Key::ArrowRight | Key::ArrowUp | Key::ArrowDown => KeyCategoryJs::Arrow,
+      _ => KeyCategoryJs::Navigation,
     };
   }
   // Additional categories not covered by monio's built-in methods
@@ -790,17 +889,17 @@ fn key_category(key: KeyJs) -> &'static str {
     | Key::Quote
     | Key::Comma
     | Key::Period
-    | Key::Slash => "punctuation",
-    Key::CapsLock | Key::NumLock | Key::ScrollLock => "lock",
+    | Key::Slash => KeyCategoryJs::Punctuation,
+    Key::CapsLock | Key::NumLock | Key::ScrollLock => KeyCategoryJs::Lock,
     Key::BrowserBack
     | Key::BrowserForward
     | Key::BrowserRefresh
     | Key::BrowserStop
     | Key::BrowserSearch
     | Key::BrowserFavorites
-    | Key::BrowserHome => "browser",
-    Key::LaunchMail | Key::LaunchApp1 | Key::LaunchApp2 => "application",
-    Key::IntlBackslash | Key::IntlYen | Key::IntlRo => "international",
+    | Key::BrowserHome => KeyCategoryJs::Browser,
+    Key::LaunchMail | Key::LaunchApp1 | Key::LaunchApp2 => KeyCategoryJs::Application,
+    Key::IntlBackslash | Key::IntlYen | Key::IntlRo => KeyCategoryJs::International,
     Key::Escape
     | Key::Space
     | Key::Enter
@@ -809,15 +908,100 @@ fn key_category(key: KeyJs) -> &'static str {
     | Key::Delete
     | Key::PrintScreen
     | Key::Pause
-    | Key::ContextMenu => "special",
-    _ => "unknown",
+    | Key::ContextMenu => KeyCategoryJs::Special,
+    _ => KeyCategoryJs::Unknown,
+  }
+}
+
+fn key_category(key: KeyJs) -> &'static str {
+  match key_category_of(key) {
+    KeyCategoryJs::Modifier => "modifier",
+    KeyCategoryJs::Letter => "letter",
+    KeyCategoryJs::Number => "number",
+    KeyCategoryJs::Function => "function",
+    KeyCategoryJs::Numpad => "numpad",
+    KeyCategoryJs::Media => "media",
+    KeyCategoryJs::Arrow => "arrow",
+    KeyCategoryJs::Navigation => "navigation",
+    KeyCategoryJs::Punctuation => "punctuation",
+    KeyCategoryJs::Lock => "lock",
+    KeyCategoryJs::Browser => "browser",
+    KeyCategoryJs::Application => "application",
+    KeyCategoryJs::International => "international",
+    KeyCategoryJs::Special => "special",
+    KeyCategoryJs::Unknown => "unknown",
   }
 }
 
-/// Get the display name for a key.
+/// Get the display name for a key. Meta and Alt use this platform's labels
+/// (⌘ and Option on macOS, Win on Windows, Super on Linux).
 #[napi]
 pub fn get_key_display_name(key: KeyJs) -> String {
-  key_display_name(key).to_string()
+  key_display_name(&key).to_string()
+}
+
+/// Get an ASCII-only display name for a key (e.g. "Enter", "Bksp", "Cmd"/"Win"/"Super", "Left"),
+/// for terminals and logs where the symbols from `getKeyDisplayName` don't render.
+#[napi]
+pub fn get_key_display_name_ascii(key: KeyJs) -> String {
+  key_display_name_ascii(key).to_string()
+}
+
+/// Options for `normalizeKey`. Both folds are off unless set.
+#[napi(object)]
+#[derive(Default)]
+pub struct NormalizeKeyOptionsJs {
+  /// Map numpad keys to their main-keyboard twin (`Numpad1` → `Num1`,
+  /// `NumpadEnter` → `Enter`, `NumpadSubtract` → `Minus`, ...). `NumpadAdd` and
+  /// `NumpadMultiply` have no unshifted twin and are left alone.
+  pub fold_numpad: Option<bool>,
+  /// Map right-side modifiers to the left-side key (`ShiftRight` → `ShiftLeft`).
+  pub fold_modifier_sides: Option<bool>,
+}
+
+/// Fold `key` into a canonical key per `opts`, so bindings compare equal
+/// across numpad/main-keyboard duplicates or modifier sides.
+fn normalize(key: KeyJs, opts: &NormalizeKeyOptionsJs) -> KeyJs {
+  if opts.fold_numpad.unwrap_or(false) {
+    let folded = match key {
+      KeyJs::Numpad0 => Some(KeyJs::Num0),
+      KeyJs::Numpad1 => Some(KeyJs::Num1),
+      KeyJs::Numpad2 => Some(KeyJs::Num2),
+      KeyJs::Numpad3 => Some(KeyJs::Num3),
+      KeyJs::Numpad4 => Some(KeyJs::Num4),
+      KeyJs::Numpad5 => Some(KeyJs::Num5),
+      KeyJs::Numpad6 => Some(KeyJs::Num6),
+      KeyJs::Numpad7 => Some(KeyJs::Num7),
+      KeyJs::Numpad8 => Some(KeyJs::Num8),
+      KeyJs::Numpad9 => Some(KeyJs::Num9),
+      KeyJs::NumpadSubtract => Some(KeyJs::Minus),
+      KeyJs::NumpadDivide => Some(KeyJs::Slash),
+      KeyJs::NumpadDecimal => Some(KeyJs::Period),
+      KeyJs::NumpadEnter => Some(KeyJs::Enter),
+      KeyJs::NumpadEqual => Some(KeyJs::Equal),
+      _ => None,
+    };
+    if let Some(folded) = folded {
+      return folded;
+    }
+  }
+  if opts.fold_modifier_sides.unwrap_or(false) {
+    return match key {
+      KeyJs::ShiftRight => KeyJs::ShiftLeft,
+      KeyJs::ControlRight => KeyJs::ControlLeft,
+      KeyJs::AltRight => KeyJs::AltLeft,
+      KeyJs::MetaRight => KeyJs::MetaLeft,
+      other => other,
+    };
+  }
+  key
+}
+
+/// Normalize a key for binding comparison: compare `normalizeKey(pressed, opts)`
+/// against `normalizeKey(bound, opts)`. With no options the key is returned as is.
+#[napi]
+pub fn normalize_key(key: KeyJs, opts: Option<NormalizeKeyOptionsJs>) -> KeyJs {
+  normalize(key, &opts.unwrap_or_default())
 }
 
 /// Get the display name for a mouse button.
@@ -826,12 +1010,63 @@ pub fn get_button_display_name(button: ButtonJs) -> String {
   button_display_name(button).to_string()
 }
 
+/// Get the display name for an event type (e.g. "Key Pressed").
+#[napi]
+pub fn get_event_type_display_name(event: EventTypeJs) -> String {
+  event_type_display_name(&event.into()).to_string()
+}
+
+/// Describe an event on one line for logs, e.g. `KeyPressed key=A raw=65
+/// t=12345.678` or `MouseMoved x=100 y=200 t=12345.678`. Keys use the ASCII
+/// display names (see `getKeyDisplayNameAscii`) and buttons the names from
+/// `getButtonDisplayName`. The format is meant for humans and may change.
+#[napi]
+pub fn describe_event(event: EventJs) -> String {
+  let mut line = event_type_display_name(&event.event_type.into()).replace(' ', "");
+  if let Some(kb) = event.keyboard {
+    line += &format!(
+      " key={} raw={}",
+      key_display_name_ascii(kb.key),
+      kb.raw_code
+    );
+    if let Some(ch) = kb.char {
+      line += &format!(" char={:?}", ch);
+    }
+  }
+  if let Some(mouse) = event.mouse {
+    line += &format!(" x={} y={}", mouse.x, mouse.y);
+    if let Some(button) = mouse.button {
+      line += &format!(" button={}", button_display_name(button));
+    }
+  }
+  if let Some(wheel) = event.wheel {
+    let direction = match wheel.direction {
+      ScrollDirectionJs::Up => "up",
+      ScrollDirectionJs::Down => "down",
+      ScrollDirectionJs::Left => "left",
+      ScrollDirectionJs::Right => "right",
+    };
+    line += &format!(
+      " x={} y={} dir={} delta={}",
+      wheel.x, wheel.y, direction, wheel.delta
+    );
+  }
+  line + &format!(" t={:.3}", event.time)
+}
+
 /// Get the category for a key (e.g. "letter", "modifier", "arrow", "function").
 #[napi]
 pub fn get_key_category(key: KeyJs) -> String {
   key_category(key).to_string()
 }
 
+/// Get the category of a key as a `KeyCategoryJs`, for comparisons that
+/// shouldn't depend on strings.
+#[napi]
+pub fn get_key_category_enum(key: KeyJs) -> KeyCategoryJs {
+  key_category_of(key)
+}
+
 /// Check if a key is a modifier key.
 #[napi]
 pub fn is_modifier_key(key: KeyJs) -> bool {
@@ -846,10 +1081,158 @@ pub struct KeyDisplayInfo {
   pub category: String,
 }
 
-/// Total number of named KeyJs variants (0 through 137 inclusive).
+/// The `KeyJs` variant name of a key (e.g. `"ControlLeft"`), as used by
+/// `keyToString`/`keyFromString`.
+fn key_name(key: &KeyJs) -> &'static str {
+  match key {
+    KeyJs::KeyA => "KeyA",
+    KeyJs::KeyB => "KeyB",
+    KeyJs::KeyC => "KeyC",
+    KeyJs::KeyD => "KeyD",
+    KeyJs::KeyE => "KeyE",
+    KeyJs::KeyF => "KeyF",
+    KeyJs::KeyG => "KeyG",
+    KeyJs::KeyH => "KeyH",
+    KeyJs::KeyI => "KeyI",
+    KeyJs::KeyJ => "KeyJ",
+    KeyJs::KeyK => "KeyK",
+    KeyJs::KeyL => "KeyL",
+    KeyJs::KeyM => "KeyM",
+    KeyJs::KeyN => "KeyN",
+    KeyJs::KeyO => "KeyO",
+    KeyJs::KeyP => "KeyP",
+    KeyJs::KeyQ => "KeyQ",
+    KeyJs::KeyR => "KeyR",
+    KeyJs::KeyS => "KeyS",
+    KeyJs::KeyT => "KeyT",
+    KeyJs::KeyU => "KeyU",
+    KeyJs::KeyV => "KeyV",
+    KeyJs::KeyW => "KeyW",
+    KeyJs::KeyX => "KeyX",
+    KeyJs::KeyY => "KeyY",
+    KeyJs::KeyZ => "KeyZ",
+    KeyJs::Num0 => "Num0",
+    KeyJs::Num1 => "Num1",
+    KeyJs::Num2 => "Num2",
+    KeyJs::Num3 => "Num3",
+    KeyJs::Num4 => "Num4",
+    KeyJs::Num5 => "Num5",
+    KeyJs::Num6 => "Num6",
+    KeyJs::Num7 => "Num7",
+    KeyJs::Num8 => "Num8",
+    KeyJs::Num9 => "Num9",
+    KeyJs::F1 => "F1",
+    KeyJs::F2 => "F2",
+    KeyJs::F3 => "F3",
+    KeyJs::F4 => "F4",
+    KeyJs::F5 => "F5",
+    KeyJs::F6 => "F6",
+    KeyJs::F7 => "F7",
+    KeyJs::F8 => "F8",
+    KeyJs::F9 => "F9",
+    KeyJs::F10 => "F10",
+    KeyJs::F11 => "F11",
+    KeyJs::F12 => "F12",
+    KeyJs::Escape => "Escape",
+    KeyJs::Space => "Space",
+    KeyJs::Enter => "Enter",
+    KeyJs::Backspace => "Backspace",
+    KeyJs::Tab => "Tab",
+    KeyJs::ShiftLeft => "ShiftLeft",
+    KeyJs::ShiftRight => "ShiftRight",
+    KeyJs::ControlLeft => "ControlLeft",
+    KeyJs::ControlRight => "ControlRight",
+    KeyJs::AltLeft => "AltLeft",
+    KeyJs::AltRight => "AltRight",
+    KeyJs::MetaLeft => "MetaLeft",
+    KeyJs::MetaRight => "MetaRight",
+    KeyJs::CapsLock => "CapsLock",
+    KeyJs::Delete => "Delete",
+    KeyJs::ArrowLeft => "ArrowLeft",
+    KeyJs::ArrowRight => "ArrowRight",
+    KeyJs::ArrowUp => "ArrowUp",
+    KeyJs::ArrowDown => "ArrowDown",
+    KeyJs::Unknown => "Unknown",
+    KeyJs::Insert => "Insert",
+    KeyJs::Home => "Home",
+    KeyJs::End => "End",
+    KeyJs::PageUp => "PageUp",
+    KeyJs::PageDown => "PageDown",
+    KeyJs::NumLock => "NumLock",
+    KeyJs::ScrollLock => "ScrollLock",
+    KeyJs::PrintScreen => "PrintScreen",
+    KeyJs::Pause => "Pause",
+    KeyJs::Grave => "Grave",
+    KeyJs::Minus => "Minus",
+    KeyJs::Equal => "Equal",
+    KeyJs::BracketLeft => "BracketLeft",
+    KeyJs::BracketRight => "BracketRight",
+    KeyJs::Backslash => "Backslash",
+    KeyJs::Semicolon => "Semicolon",
+    KeyJs::Quote => "Quote",
+    KeyJs::Comma => "Comma",
+    KeyJs::Period => "Period",
+    KeyJs::Slash => "Slash",
+    KeyJs::F13 => "F13",
+    KeyJs::F14 => "F14",
+    KeyJs::F15 => "F15",
+    KeyJs::F16 => "F16",
+    KeyJs::F17 => "F17",
+    KeyJs::F18 => "F18",
+    KeyJs::F19 => "F19",
+    KeyJs::F20 => "F20",
+    KeyJs::F21 => "F21",
+    KeyJs::F22 => "F22",
+    KeyJs::F23 => "F23",
+    KeyJs::F24 => "F24",
+    KeyJs::Numpad0 => "Numpad0",
+    KeyJs::Numpad1 => "Numpad1",
+    KeyJs::Numpad2 => "Numpad2",
+    KeyJs::Numpad3 => "Numpad3",
+    KeyJs::Numpad4 => "Numpad4",
+    KeyJs::Numpad5 => "Numpad5",
+    KeyJs::Numpad6 => "Numpad6",
+    KeyJs::Numpad7 => "Numpad7",
+    KeyJs::Numpad8 => "Numpad8",
+    KeyJs::Numpad9 => "Numpad9",
+    KeyJs::NumpadAdd => "NumpadAdd",
+    KeyJs::NumpadSubtract => "NumpadSubtract",
+    KeyJs::NumpadMultiply => "NumpadMultiply",
+    KeyJs::NumpadDivide => "NumpadDivide",
+    KeyJs::NumpadDecimal => "NumpadDecimal",
+    KeyJs::NumpadEnter => "NumpadEnter",
+    KeyJs::NumpadEqual => "NumpadEqual",
+    KeyJs::VolumeUp => "VolumeUp",
+    KeyJs::VolumeDown => "VolumeDown",
+    KeyJs::VolumeMute => "VolumeMute",
+    KeyJs::MediaPlayPause => "MediaPlayPause",
+    KeyJs::MediaStop => "MediaStop",
+    KeyJs::MediaNext => "MediaNext",
+    KeyJs::MediaPrevious => "MediaPrevious",
+    KeyJs::BrowserBack => "BrowserBack",
+    KeyJs::BrowserForward => "BrowserForward",
+    KeyJs::BrowserRefresh => "BrowserRefresh",
+    KeyJs::BrowserStop => "BrowserStop",
+    KeyJs::BrowserSearch => "BrowserSearch",
+    KeyJs::BrowserFavorites => "BrowserFavorites",
+    KeyJs::BrowserHome => "BrowserHome",
+    KeyJs::LaunchMail => "LaunchMail",
+    KeyJs::LaunchApp1 => "LaunchApp1",
+    KeyJs::LaunchApp2 => "LaunchApp2",
+    KeyJs::IntlBackslash => "IntlBackslash",
+    KeyJs::IntlYen => "IntlYen",
+    KeyJs::IntlRo => "IntlRo",
+    KeyJs::ContextMenu => "ContextMenu",
+  }
+}
+
+/// Total number of named KeyJs variants (0 through 137 inclusive), for
+/// iterating key codes without hardcoding the count.
 /// IMPORTANT: Update this when adding new KeyJs variants, and add matching
-/// arms to key_from_i32, key_display_name, and key_category.
-const KEY_JS_COUNT: i32 = 138;
+/// arms to key_from_i32, key_name, key_display_name, and key_category;
+/// `verifyKeyTables` fails until they agree.
+#[napi]
+pub const KEY_JS_COUNT: i32 = 138;
 
 /// Map an integer to a KeyJs variant. Returns None for out-of-range values.
 fn key_from_i32(v: i32) -> Option<KeyJs> {
@@ -996,14 +1379,92 @@ fn key_from_i32(v: i32) -> Option<KeyJs> {
   }
 }
 
+/// Check that every code in `0..KEY_JS_COUNT` maps to the `KeyJs` variant
+/// with that code, and that the variant has a name `keyFromString` accepts,
+/// a display name and a category (only `Unknown` may be unknown). Also
+/// checks that `KEY_JS_COUNT` itself is not stale. Throws listing every gap.
+#[napi]
+pub fn verify_key_tables() -> Result<()> {
+  let mut gaps = Vec::new();
+  for code in 0..KEY_JS_COUNT {
+    let Some(key) = key_from_i32(code) else {
+      gaps.push(format!("{} has no KeyJs variant", code));
+      continue;
+    };
+    // KeyJs is not Copy, so each check re-fetches the variant
+    let name = key_name(&key);
+    let variant = || key_from_i32(code).unwrap();
+    if variant() as i32 != code {
+      gaps.push(format!("{} maps to {} ({})", code, name, variant() as i32));
+    }
+    if key_from_string(name.to_string()).map(|k| k as i32) != Some(code) {
+      gaps.push(format!("{} ({}) does not parse back", code, name));
+    }
+    if key_id(variant().into()) != code as u32 {
+      gaps.push(format!("{} ({}) changes through monio's Key", code, name));
+    }
+    if matches!(key, KeyJs::Unknown) {
+      continue;
+    }
+    let display = key_display_name(&variant());
+    if display.is_empty() || display == "Unknown" {
+      gaps.push(format!("{} ({}) has no display name", code, name));
+    }
+    if key_category(key) == "unknown" {
+      gaps.push(format!("{} ({}) has no category", code, name));
+    }
+  }
+  if key_from_i32(KEY_JS_COUNT).is_some() {
+    gaps.push(format!("KEY_JS_COUNT ({}) is too low", KEY_JS_COUNT));
+  }
+  if gaps.is_empty() {
+    Ok(())
+  } else {
+    Err(Error::new(
+      Status::GenericFailure,
+      format!("Key tables are inconsistent: {}", gaps.join("; ")),
+    ))
+  }
+}
+
 /// Get display info for all known keys.
 #[napi]
 pub fn get_all_key_display_info() -> Vec<KeyDisplayInfo> {
+  all_key_display_info(&HashMap::new())
+}
+
+/// Get display info for all known keys, with caller-supplied labels.
+///
+/// `overrides` maps a key code (as a string, since JS object keys are strings —
+/// e.g. `{ "49": "Leertaste" }` for `KeyJs.Space`) to the label to use instead of
+/// the built-in English one. Keys without an override keep their default label.
+#[napi]
+pub fn get_all_key_display_info_localized(
+  overrides: HashMap<String, String>,
+) -> Vec<KeyDisplayInfo> {
+  all_key_display_info(&overrides)
+}
+
+/// Get the display name for a key, preferring a label from `overrides`.
+/// See `getAllKeyDisplayInfoLocalized` for the shape of `overrides`.
+#[napi]
+pub fn get_key_display_name_localized(key: KeyJs, overrides: HashMap<String, String>) -> String {
+  let name = key_display_name(&key);
+  overrides
+    .get(&(key as u32).to_string())
+    .cloned()
+    .unwrap_or_else(|| name.to_string())
+}
+
+fn all_key_display_info(overrides: &HashMap<String, String>) -> Vec<KeyDisplayInfo> {
   (0..KEY_JS_COUNT)
     .filter_map(|i| {
       // Two key_from_i32 calls because KeyJs is not Copy (napi enum).
       // Both are O(1) match lookups — acceptable for a bulk init function.
-      let display_name = key_display_name(key_from_i32(i)?).to_string();
+      let display_name = match overrides.get(&i.to_string()) {
+        Some(label) => label.clone(),
+        None => key_display_name(&key_from_i32(i)?).to_string(),
+      };
       let category = key_category(key_from_i32(i)?).to_string();
       Some(KeyDisplayInfo {
         key: i as u32,
@@ -1014,14 +1475,349 @@ pub fn get_all_key_display_info() -> Vec<KeyDisplayInfo> {
     .collect()
 }
 
+// ============================================================================
+// Stable Numeric Codes (for persisting buttons and event types)
+// ============================================================================
+
+/// Stable integer for a mouse button, safe to persist. The mapping is fixed:
+/// Left = 0, Right = 1, Middle = 2, Button4 = 3, Button5 = 4, Unknown = 255.
+#[napi]
+pub fn button_code(button: ButtonJs) -> u32 {
+  match button {
+    ButtonJs::Left => 0,
+    ButtonJs::Right => 1,
+    ButtonJs::Middle => 2,
+    ButtonJs::Button4 => 3,
+    ButtonJs::Button5 => 4,
+    ButtonJs::Unknown => 255,
+  }
+}
+
+/// Inverse of `buttonCode`. Returns `null` for codes it never produces.
+#[napi]
+pub fn button_from_code(code: u32) -> Option<ButtonJs> {
+  match code {
+    0 => Some(ButtonJs::Left),
+    1 => Some(ButtonJs::Right),
+    2 => Some(ButtonJs::Middle),
+    3 => Some(ButtonJs::Button4),
+    4 => Some(ButtonJs::Button5),
+    255 => Some(ButtonJs::Unknown),
+    _ => None,
+  }
+}
+
+/// Stable integer for an event type, safe to persist. The mapping is fixed and
+/// matches the bit positions used by event masks: HookEnabled = 0,
+/// HookDisabled = 1, KeyPressed = 2, KeyReleased = 3, KeyTyped = 4,
+/// MousePressed = 5, MouseReleased = 6, MouseClicked = 7, MouseMoved = 8,
+/// MouseDragged = 9, MouseWheel = 10.
+#[napi]
+pub fn event_type_code(event_type: EventTypeJs) -> u32 {
+  event_type_bit(&event_type.into()).trailing_zeros()
+}
+
+/// Inverse of `eventTypeCode`. Returns `null` for codes it never produces.
+#[napi]
+pub fn event_type_from_code(code: u32) -> Option<EventTypeJs> {
+  ALL_EVENT_TYPES.get(code as usize).map(|&et| et.into())
+}
+
+// ============================================================================
+// Key Names (for persisting keys and hotkeys as text)
+// ============================================================================
+
+/// The `KeyJs` variant name of a key, e.g. `"ControlLeft"`. Inverse of
+/// `keyFromString`; stable across releases, unlike display names.
+#[napi]
+pub fn key_to_string(key: KeyJs) -> String {
+  key_name(&key).to_string()
+}
+
+/// Parse a `KeyJs` variant name (case-insensitive, e.g. `"controlleft"`).
+/// Returns `null` for unknown names.
+#[napi]
+pub fn key_from_string(name: String) -> Option<KeyJs> {
+  let name = name.trim();
+  (0..KEY_JS_COUNT)
+    .filter_map(key_from_i32)
+    .find(|key| key_name(key).eq_ignore_ascii_case(name))
+}
+
+/// Position of a modifier in canonical hotkey order (Control, Alt, Shift,
+/// Meta; left before right), or `None` for non-modifiers.
+fn modifier_rank(key: &KeyJs) -> Option<u32> {
+  match key {
+    KeyJs::ControlLeft => Some(0),
+    KeyJs::ControlRight => Some(1),
+    KeyJs::AltLeft => Some(2),
+    KeyJs::AltRight => Some(3),
+    KeyJs::ShiftLeft => Some(4),
+    KeyJs::ShiftRight => Some(5),
+    KeyJs::MetaLeft => Some(6),
+    KeyJs::MetaRight => Some(7),
+    _ => None,
+  }
+}
+
+/// Canonical string for a hotkey: modifiers first in a fixed order (Control,
+/// Alt, Shift, Meta), then the other keys as given, joined with `+`, e.g.
+/// `"ControlLeft+ShiftLeft+KeyS"`. `hotkeyFromString` parses it back.
+#[napi]
+pub fn hotkey_to_string(keys: Vec<KeyJs>) -> String {
+  let mut keys = keys;
+  keys.sort_by_key(|key| modifier_rank(key).unwrap_or(u32::MAX));
+  keys.iter().map(key_name).collect::<Vec<_>>().join("+")
+}
+
+/// Parse a `+`-separated hotkey string (see `hotkeyToString`) with
+/// `keyFromString`. Errors name the first unknown token.
+#[napi]
+pub fn hotkey_from_string(s: String) -> Result<Vec<KeyJs>> {
+  if s.trim().is_empty() {
+    return Err(Error::new(
+      Status::InvalidArg,
+      "Invalid hotkey: empty string",
+    ));
+  }
+  s.split('+')
+    .map(|token| {
+      key_from_string(token.to_string()).ok_or_else(|| {
+        Error::new(
+          Status::InvalidArg,
+          format!("Invalid hotkey: unknown key \"{}\"", token.trim()),
+        )
+      })
+    })
+    .collect()
+}
+
+// ============================================================================
+// Layout-Aware Characters
+// ============================================================================
+
+/// PC/AT (set 1) scan code for the physical position of a key, with `0xE0xx`
+/// for extended (E0-prefixed) keys. KeyJs names describe positions on a US
+/// keyboard, so this is layout-independent. Pause (a multi-byte sequence) has none.
+fn key_scan_code(key: &KeyJs) -> Option<u32> {
+  let code = match key {
+    KeyJs::Escape => 0x01,
+    KeyJs::Num1 => 0x02,
+    KeyJs::Num2 => 0x03,
+    KeyJs::Num3 => 0x04,
+    KeyJs::Num4 => 0x05,
+    KeyJs::Num5 => 0x06,
+    KeyJs::Num6 => 0x07,
+    KeyJs::Num7 => 0x08,
+    KeyJs::Num8 => 0x09,
+    KeyJs::Num9 => 0x0a,
+    KeyJs::Num0 => 0x0b,
+    KeyJs::Minus => 0x0c,
+    KeyJs::Equal => 0x0d,
+    KeyJs::Backspace => 0x0e,
+    KeyJs::Tab => 0x0f,
+    KeyJs::KeyQ => 0x10,
+    KeyJs::KeyW => 0x11,
+    KeyJs::KeyE => 0x12,
+    KeyJs::KeyR => 0x13,
+    KeyJs::KeyT => 0x14,
+    KeyJs::KeyY => 0x15,
+    KeyJs::KeyU => 0x16,
+    KeyJs::KeyI => 0x17,
+    KeyJs::KeyO => 0x18,
+    KeyJs::KeyP => 0x19,
+    KeyJs::BracketLeft => 0x1a,
+    KeyJs::BracketRight => 0x1b,
+    KeyJs::Enter => 0x1c,
+    KeyJs::ControlLeft => 0x1d,
+    KeyJs::KeyA => 0x1e,
+    KeyJs::KeyS => 0x1f,
+    KeyJs::KeyD => 0x20,
+    KeyJs::KeyF => 0x21,
+    KeyJs::KeyG => 0x22,
+    KeyJs::KeyH => 0x23,
+    KeyJs::KeyJ => 0x24,
+    KeyJs::KeyK => 0x25,
+    KeyJs::KeyL => 0x26,
+    KeyJs::Semicolon => 0x27,
+    KeyJs::Quote => 0x28,
+    KeyJs::Grave => 0x29,
+    KeyJs::ShiftLeft => 0x2a,
+    KeyJs::Backslash => 0x2b,
+    KeyJs::KeyZ => 0x2c,
+    KeyJs::KeyX => 0x2d,
+    KeyJs::KeyC => 0x2e,
+    KeyJs::KeyV => 0x2f,
+    KeyJs::KeyB => 0x30,
+    KeyJs::KeyN => 0x31,
+    KeyJs::KeyM => 0x32,
+    KeyJs::Comma => 0x33,
+    KeyJs::Period => 0x34,
+    KeyJs::Slash => 0x35,
+    KeyJs::ShiftRight => 0x36,
+    KeyJs::NumpadMultiply => 0x37,
+    KeyJs::AltLeft => 0x38,
+    KeyJs::Space => 0x39,
+    KeyJs::CapsLock => 0x3a,
+    KeyJs::F1 => 0x3b,
+    KeyJs::F2 => 0x3c,
+    KeyJs::F3 => 0x3d,
+    KeyJs::F4 => 0x3e,
+    KeyJs::F5 => 0x3f,
+    KeyJs::F6 => 0x40,
+    KeyJs::F7 => 0x41,
+    KeyJs::F8 => 0x42,
+    KeyJs::F9 => 0x43,
+    KeyJs::F10 => 0x44,
+    KeyJs::NumLock => 0x45,
+    KeyJs::ScrollLock => 0x46,
+    KeyJs::Numpad7 => 0x47,
+    KeyJs::Numpad8 => 0x48,
+    KeyJs::Numpad9 => 0x49,
+    KeyJs::NumpadSubtract => 0x4a,
+    KeyJs::Numpad4 => 0x4b,
+    KeyJs::Numpad5 => 0x4c,
+    KeyJs::Numpad6 => 0x4d,
+    KeyJs::NumpadAdd => 0x4e,
+    KeyJs::Numpad1 => 0x4f,
+    KeyJs::Numpad2 => 0x50,
+    KeyJs::Numpad3 => 0x51,
+    KeyJs::Numpad0 => 0x52,
+    KeyJs::NumpadDecimal => 0x53,
+    KeyJs::IntlBackslash => 0x56,
+    KeyJs::F11 => 0x57,
+    KeyJs::F12 => 0x58,
+    KeyJs::NumpadEqual => 0x59,
+    KeyJs::F13 => 0x64,
+    KeyJs::F14 => 0x65,
+    KeyJs::F15 => 0x66,
+    KeyJs::F16 => 0x67,
+    KeyJs::F17 => 0x68,
+    KeyJs::F18 => 0x69,
+    KeyJs::F19 => 0x6a,
+    KeyJs::F20 => 0x6b,
+    KeyJs::F21 => 0x6c,
+    KeyJs::F22 => 0x6d,
+    KeyJs::F23 => 0x6e,
+    KeyJs::IntlRo => 0x73,
+    KeyJs::F24 => 0x76,
+    KeyJs::IntlYen => 0x7d,
+    // Extended keys
+    KeyJs::MediaPrevious => 0xe010,
+    KeyJs::MediaNext => 0xe019,
+    KeyJs::NumpadEnter => 0xe01c,
+    KeyJs::ControlRight => 0xe01d,
+    KeyJs::VolumeMute => 0xe020,
+    KeyJs::LaunchApp2 => 0xe021,
+    KeyJs::MediaPlayPause => 0xe022,
+    KeyJs::MediaStop => 0xe024,
+    KeyJs::VolumeDown => 0xe02e,
+    KeyJs::VolumeUp => 0xe030,
+    KeyJs::BrowserHome => 0xe032,
+    KeyJs::NumpadDivide => 0xe035,
+    KeyJs::PrintScreen => 0xe037,
+    KeyJs::AltRight => 0xe038,
+    KeyJs::Home => 0xe047,
+    KeyJs::ArrowUp => 0xe048,
+    KeyJs::PageUp => 0xe049,
+    KeyJs::ArrowLeft => 0xe04b,
+    KeyJs::ArrowRight => 0xe04d,
+    KeyJs::End => 0xe04f,
+    KeyJs::ArrowDown => 0xe050,
+    KeyJs::PageDown => 0xe051,
+    KeyJs::Insert => 0xe052,
+    KeyJs::Delete => 0xe053,
+    KeyJs::MetaLeft => 0xe05b,
+    KeyJs::MetaRight => 0xe05c,
+    KeyJs::ContextMenu => 0xe05d,
+    KeyJs::BrowserSearch => 0xe065,
+    KeyJs::BrowserFavorites => 0xe066,
+    KeyJs::BrowserRefresh => 0xe067,
+    KeyJs::BrowserStop => 0xe068,
+    KeyJs::BrowserForward => 0xe069,
+    KeyJs::BrowserBack => 0xe06a,
+    KeyJs::LaunchApp1 => 0xe06b,
+    KeyJs::LaunchMail => 0xe06c,
+    KeyJs::Pause | KeyJs::Unknown => return None,
+  };
+  Some(code)
+}
+
+/// Get the character a key produces under the active keyboard layout.
+///
+/// The key right of `L` yields `"ö"` on a German layout, for example. Windows
+/// consults the focused window's layout and returns a dead key's standalone
+/// accent; macOS uses the current input source and Linux the X11 keyboard
+/// group, where dead keys return `null`. Returns `null` for keys that produce
+/// no character (modifiers, arrows, function keys, Enter, Tab, ...) and
+/// whenever the layout can't be read (e.g. under Wayland).
+#[napi]
+pub fn key_to_char(key: KeyJs, shift: bool) -> Option<String> {
+  // Extended keys (arrows, media, ...) never produce characters
+  let scan_code = key_scan_code(&key).filter(|&code| code < 0x100)?;
+  platform::scan_code_to_char(scan_code, shift)
+}
+
+/// An installed keyboard layout.
+#[napi(object)]
+pub struct KeyboardLayoutJs {
+  /// Platform identifier, accepted by `setActiveKeyboardLayout`. On Windows
+  /// the layout handle in hex (e.g. `"4090409"`).
+  pub id: String,
+  /// Human-readable name, e.g. "English (United States)".
+  pub name: String,
+}
+
+/// List installed keyboard layouts. Currently implemented on Windows only;
+/// elsewhere the list is empty.
+#[napi]
+pub fn get_keyboard_layouts() -> Vec<KeyboardLayoutJs> {
+  platform::keyboard_layouts()
+    .into_iter()
+    .map(|l| KeyboardLayoutJs {
+      id: l.id,
+      name: l.name,
+    })
+    .collect()
+}
+
+/// Ask the focused window to switch to the layout with `id` (see
+/// `getKeyboardLayouts`). Best-effort: the request is asynchronous and an
+/// application may ignore it. Windows only for now; other platforms return an
+/// error.
+#[napi]
+pub fn set_active_keyboard_layout(id: String) -> Result<()> {
+  platform::set_keyboard_layout(&id).map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to set keyboard layout: {}", e),
+    )
+  })
+}
+
 // ============================================================================
 // Structs
 // ============================================================================
 
 #[napi(object)]
 pub struct KeyboardDataJs {
+  /// `Unknown` when monio's key table has no entry for `rawCode`.
   pub key: KeyJs,
+  /// The key code exactly as the platform hook reported it, for mapped and
+  /// unmapped keys alike: the virtual-key code (`VK_*`) on Windows, the
+  /// virtual keycode (`kVK_*`) on macOS, and the X11 keycode (evdev code + 8)
+  /// on Linux. macOS and X11 keycodes name physical positions, but Windows
+  /// virtual-key codes follow the keyboard layout (`Y` and `Z` swap on a German
+  /// layout; only scan codes are layout-independent). Codes differ between
+  /// platforms, so store them per platform.
+  ///
+  /// This identifies keys; it can't re-inject them. Simulation takes `KeyJs`,
+  /// so `replayEvents` skips `Unknown` keys and the `simulate*` functions
+  /// can't target them.
   pub raw_code: u32,
+  /// Text the key produced, as composed by the OS (layout, Shift, dead keys).
+  /// Set on `KeyTyped` events; `null` when the key produces no text.
+  pub char: Option<String>,
 }
 
 #[napi(object)]
@@ -1050,6 +1846,17 @@ impl From<ScrollDirection> for ScrollDirectionJs {
   }
 }
 
+impl From<ScrollDirectionJs> for ScrollDirection {
+  fn from(dir: ScrollDirectionJs) -> Self {
+    match dir {
+      ScrollDirectionJs::Up => ScrollDirection::Up,
+      ScrollDirectionJs::Down => ScrollDirection::Down,
+      ScrollDirectionJs::Left => ScrollDirection::Left,
+      ScrollDirectionJs::Right => ScrollDirection::Right,
+    }
+  }
+}
+
 #[napi(object)]
 pub struct WheelDataJs {
   pub x: f64,
@@ -1065,15 +1872,19 @@ pub struct EventJs {
   pub keyboard: Option<KeyboardDataJs>,
   pub mouse: Option<MouseDataJs>,
   pub wheel: Option<WheelDataJs>,
+  /// As on `KeyboardEventJs.deviceId`.
+  pub device_id: Option<u32>,
 }
 
 impl From<&Event> for EventJs {
   fn from(event: &Event) -> Self {
-    let time = event
-      .time
-      .duration_since(UNIX_EPOCH)
-      .map(|d| d.as_secs_f64())
-      .unwrap_or(0.0);
+    let time = js_time(
+      event
+        .time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or(0.0),
+    );
 
     EventJs {
       event_type: event.event_type.into(),
@@ -1081,6 +1892,7 @@ impl From<&Event> for EventJs {
       keyboard: event.keyboard.as_ref().map(|kb| KeyboardDataJs {
         key: kb.key.into(),
         raw_code: kb.raw_code,
+        char: kb.char.map(String::from),
       }),
       mouse: event.mouse.as_ref().map(|m| MouseDataJs {
         x: m.x,
@@ -1093,6 +1905,7 @@ impl From<&Event> for EventJs {
         direction: w.direction.into(),
         delta: w.delta,
       }),
+      device_id: None,
     }
   }
 }
@@ -1123,6 +1936,10 @@ pub struct DisplayInfoJs {
   pub scale_factor: f64,
   pub refresh_rate: Option<u32>,
   pub is_primary: bool,
+  /// Position in the stable display order: primary first, then left to right
+  /// (`bounds.x`), then top to bottom (`bounds.y`). `getDisplays` returns
+  /// displays in this order.
+  pub index: u32,
 }
 
 impl From<&DisplayInfo> for DisplayInfoJs {
@@ -1133,10 +1950,53 @@ impl From<&DisplayInfo> for DisplayInfoJs {
       scale_factor: info.scale_factor,
       refresh_rate: info.refresh_rate,
       is_primary: info.is_primary,
+      index: 0,
     }
   }
 }
 
+/// Convert displays into the stable order documented on `DisplayInfoJs.index`.
+fn ordered_displays(infos: &[DisplayInfo]) -> Vec<DisplayInfoJs> {
+  let mut sorted: Vec<&DisplayInfo> = infos.iter().collect();
+  sorted.sort_by(|a, b| {
+    b.is_primary
+      .cmp(&a.is_primary)
+      .then(a.bounds.x.total_cmp(&b.bounds.x))
+      .then(a.bounds.y.total_cmp(&b.bounds.y))
+  });
+  sorted
+    .into_iter()
+    .enumerate()
+    .map(|(index, info)| DisplayInfoJs {
+      index: index as u32,
+      ..info.into()
+    })
+    .collect()
+}
+
+#[napi(object)]
+pub struct PointJs {
+  pub x: f64,
+  pub y: f64,
+}
+
+/// One position in `InputHook.getMouseTrail()`.
+#[napi(object)]
+pub struct TrailPointJs {
+  pub x: f64,
+  pub y: f64,
+  pub time: f64,
+}
+
+#[napi(object)]
+pub struct DisplaySnapshotJs {
+  pub displays: Vec<DisplayInfoJs>,
+  pub primary_id: u32,
+  /// Bounding box of all displays in the global coordinate space.
+  pub virtual_bounds: RectJs,
+  pub cursor: PointJs,
+}
+
 #[napi(object)]
 pub struct SystemSettingsJs {
   pub keyboard_repeat_rate: Option<u32>,
@@ -1163,58 +2023,372 @@ impl From<&SystemSettings> for SystemSettingsJs {
 }
 
 // ============================================================================
-// Hook Management
+// Time Base
 // ============================================================================
 
+/// What the `time` field of delivered events measures (see `setTimeBase`).
 #[napi]
-pub struct HookJs {
-  hook: Arc<Mutex<Option<Hook>>>,
-  mask: Arc<AtomicU32>,
+pub enum TimeBaseJs {
+  /// Seconds since the Unix epoch (the default).
+  UnixEpoch,
+  /// Seconds on a monotonic clock starting at the `setTimeBase` call, immune to
+  /// wall-clock adjustments.
+  Monotonic,
+  /// Milliseconds on the caller's `performance.now()` clock.
+  PerformanceNow,
+}
+
+struct TimeBase {
+  /// A `TimeBaseJs` value.
+  base: u32,
+  /// When `setTimeBase` switched away from `UnixEpoch`.
+  anchor: Option<Instant>,
+  /// `performance.now()` at `anchor`.
+  origin: f64,
+}
+
+static TIME_BASE: Mutex<TimeBase> = Mutex::new(TimeBase {
+  base: TimeBaseJs::UnixEpoch as u32,
+  anchor: None,
+  origin: 0.0,
+});
+
+fn now_secs() -> f64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|d| d.as_secs_f64())
+    .unwrap_or(0.0)
 }
 
+/// Convert an event timestamp (Unix seconds) to the configured time base.
+fn js_time(unix_secs: f64) -> f64 {
+  let time_base = TIME_BASE.lock().unwrap();
+  let Some(anchor) = time_base.anchor else {
+    return unix_secs;
+  };
+  // Events carry wall-clock stamps, so only their age (usually well under a
+  // millisecond) comes from the wall clock; the rest is monotonic.
+  let age = (now_secs() - unix_secs).max(0.0);
+  let secs = anchor.elapsed().as_secs_f64() - age;
+  if time_base.base == TimeBaseJs::PerformanceNow as u32 {
+    time_base.origin + secs * 1000.0
+  } else {
+    secs
+  }
+}
+
+/// Choose what `time` reports on events from `startListen` and `InputHook`.
+///
+/// For `PerformanceNow`, pass the current `performance.now()` so the native
+/// clock can be aligned with it: `setTimeBase(TimeBaseJs.PerformanceNow,
+/// performance.now())`. Event `time` is then directly comparable with
+/// `performance.now()` readings on that thread. The ring buffer (see
+/// `attachRingBuffer`) always records Unix seconds.
 #[napi]
-impl HookJs {
-  #[napi]
-  pub fn stop(&self) -> Result<()> {
-    let mut guard = self.hook.lock().unwrap();
-    if let Some(hook) = guard.take() {
-      hook.stop().map_err(|e| {
+pub fn set_time_base(base: TimeBaseJs, performance_now: Option<f64>) -> Result<()> {
+  let mut time_base = TIME_BASE.lock().unwrap();
+  match base {
+    TimeBaseJs::UnixEpoch => time_base.anchor = None,
+    TimeBaseJs::Monotonic => time_base.anchor = Some(Instant::now()),
+    TimeBaseJs::PerformanceNow => {
+      time_base.origin = performance_now.ok_or_else(|| {
         Error::new(
-          Status::GenericFailure,
-          format!("Failed to stop hook: {}", e),
+          Status::InvalidArg,
+          "setTimeBase(PerformanceNow) needs the current performance.now()",
         )
       })?;
+      time_base.anchor = Some(Instant::now());
     }
-    Ok(())
   }
+  time_base.base = base as u32;
+  Ok(())
+}
 
-  #[napi(getter)]
-  pub fn is_running(&self) -> bool {
-    let guard = self.hook.lock().unwrap();
-    guard.as_ref().is_some_and(|h| h.is_running())
-  }
+// ============================================================================
+// Diagnostics
+// ============================================================================
 
-  /// Update the event filter bitmask at runtime without restarting the hook.
-  /// Each bit corresponds to an EventTypeJs value (bit 0 = HookEnabled, bit 2 = KeyPressed, etc.).
-  /// Set to 0x7FF (2047) for all events, or combine specific bits.
-  #[napi]
-  pub fn set_event_mask(&self, mask: u32) {
-    self.mask.store(mask, Ordering::Relaxed);
-  }
+/// Weak, so a log callback alone doesn't keep the process alive.
+type LogTsFn = ThreadsafeFunction<(String, String), (), Vec<String>, Status, false, true>;
 
-  /// Get the current event filter bitmask.
-  #[napi(getter)]
-  pub fn event_mask(&self) -> u32 {
-    self.mask.load(Ordering::Relaxed)
+static LOG_CALLBACK: Mutex<Option<LogTsFn>> = Mutex::new(None);
+/// Fast-path flag so logging costs a single atomic load when no callback is set.
+static LOG_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Send a diagnostic message to the JS log callback, if one is set.
+/// `message` is only evaluated when logging is enabled.
+fn log_event(level: &str, message: impl FnOnce() -> String) {
+  if !LOG_ENABLED.load(Ordering::Relaxed) {
+    return;
+  }
+  if let Some(ref tsfn) = *LOG_CALLBACK.lock().unwrap() {
+    let _ = tsfn.call(
+      (level.to_string(), message()),
+      ThreadsafeFunctionCallMode::NonBlocking,
+    );
   }
 }
 
-// ============================================================================
-// Event Listening
-// ============================================================================
+/// How event callbacks are queued to the JS thread.
+///
+/// Each callback queue holds up to 1024 events.
+/// `NonBlocking` (the default) drops an event when its queue is full.
+/// `Blocking` waits for room instead, so nothing is dropped, but it stalls the
+/// OS hook thread meanwhile: input lags system-wide while JS is busy, and
+/// macOS disables event taps that stall too long (see `onTapDisabled`).
+#[napi]
+pub enum CallModeJs {
+  NonBlocking,
+  Blocking,
+}
 
-/// Convert an EventType to its bitmask bit.
-fn event_type_bit(et: &EventType) -> u32 {
+/// Capacity of each event callback queue. Without a bound the queue grows
+/// forever and `CallModeJs` has no effect.
+const EVENT_QUEUE_SIZE: usize = 1024;
+
+/// An event callback TSFN with a bounded queue.
+type EventCallback<T> = ThreadsafeFunction<T, (), Vec<T>, Status, false, false, EVENT_QUEUE_SIZE>;
+
+fn call_mode(blocking: bool) -> ThreadsafeFunctionCallMode {
+  if blocking {
+    ThreadsafeFunctionCallMode::Blocking
+  } else {
+    ThreadsafeFunctionCallMode::NonBlocking
+  }
+}
+
+/// Drops `deliver` has not logged yet. A full queue rejects every event until
+/// JS catches up, so drops are logged as a count at most once per
+/// `DROP_LOG_INTERVAL_MS` rather than one message each.
+static UNLOGGED_DROPS: AtomicU64 = AtomicU64::new(0);
+static LAST_DROP_LOG_MS: AtomicU64 = AtomicU64::new(0);
+const DROP_LOG_INTERVAL_MS: u64 = 1000;
+
+/// Queue `data` for a JS callback, logging if the threadsafe function rejects it.
+/// Returns whether the event was queued.
+fn deliver<T: 'static + ToNapiValue>(tsfn: &EventCallback<T>, data: T, blocking: bool) -> bool {
+  let status = tsfn.call(data, call_mode(blocking));
+  if status != Status::Ok {
+    UNLOGGED_DROPS.fetch_add(1, Ordering::Relaxed);
+    let now = now_ms();
+    let last = LAST_DROP_LOG_MS.load(Ordering::Relaxed);
+    if now.saturating_sub(last) >= DROP_LOG_INTERVAL_MS
+      && LAST_DROP_LOG_MS
+        .compare_exchange(last, now, Ordering::Relaxed, Ordering::Relaxed)
+        .is_ok()
+    {
+      let drops = UNLOGGED_DROPS.swap(0, Ordering::Relaxed);
+      log_event("warn", || {
+        format!("Dropped {} event(s): {:?}", drops, status)
+      });
+    }
+  }
+  status == Status::Ok
+}
+
+/// Set (or clear, with `null`) a callback receiving native-side diagnostics:
+/// hooks installed/removed, hooks disabled by the OS, dropped events (as a
+/// count, at most once a second), and install failures such as missing
+/// permissions.
+///
+/// `level` is one of `"info"`, `"warn"` or `"error"`. Logging is off by default.
+/// The callback does not keep the process alive.
+#[napi]
+pub fn set_log_callback(
+  #[napi(ts_arg_type = "((level: string, message: string) => void) | null | undefined")]
+  callback: Option<Function<(), ()>>,
+) -> Result<()> {
+  let tsfn = match callback {
+    Some(callback) => Some(
+      callback
+        .build_threadsafe_function()
+        .weak::<true>()
+        .build_callback(|ctx: ThreadsafeCallContext<(String, String)>| {
+          let (level, message) = ctx.value;
+          Ok(vec![level, message])
+        })?,
+    ),
+    None => None,
+  };
+  let mut guard = LOG_CALLBACK.lock().unwrap();
+  LOG_ENABLED.store(tsfn.is_some(), Ordering::Relaxed);
+  *guard = tsfn;
+  Ok(())
+}
+
+// ============================================================================
+// Hook Management
+// ============================================================================
+
+#[napi]
+pub struct HookJs {
+  hook: Arc<Mutex<Option<Hook>>>,
+  mask: Arc<AtomicU32>,
+  /// Cleared when the OS disables the hook behind our back (HookDisabled).
+  tap_enabled: Arc<AtomicBool>,
+  filter: Arc<ListenFilter>,
+  tsfn: Arc<EventTsFn>,
+  /// Queue events with `CallModeJs.Blocking` (see `HookConfigJs.callMode`).
+  blocking: bool,
+  /// Message of the last failed install, while not running.
+  install_error: Mutex<Option<String>>,
+  /// Modifiers the native hook has seen held (see `track_modifier`).
+  held_modifiers: ModifierTracker,
+}
+
+type EventTsFn = EventCallback<EventJs>;
+
+fn stop_listener(hook: &Mutex<Option<Hook>>, held_modifiers: &AtomicU32) -> Result<()> {
+  let mut guard = hook.lock().unwrap();
+  if let Some(hook) = guard.take() {
+    hook.stop().map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to stop hook: {}", e),
+      )
+    })?;
+    held_modifiers.store(0, Ordering::Relaxed);
+    log_event("info", || "Listener removed".to_string());
+    return release_held_input_on_stop();
+  }
+  Ok(())
+}
+
+impl Drop for HookJs {
+  fn drop(&mut self) {
+    if self.hook.lock().unwrap().is_some() {
+      let _ = release_held_input_on_stop();
+    }
+  }
+}
+
+impl HookJs {
+  /// Install a fresh native hook feeding this handle's callback, unless one is
+  /// already installed.
+  fn install(&self) -> std::result::Result<(), (InstallFailure, Error)> {
+    let mut guard = self.hook.lock().unwrap();
+    if guard.is_some() {
+      return Ok(());
+    }
+    let mask = self.mask.clone();
+    let filter = self.filter.clone();
+    let tap_enabled = self.tap_enabled.clone();
+    let tsfn = self.tsfn.clone();
+    let blocking = self.blocking;
+    let held_modifiers = self.held_modifiers.clone();
+    tap_enabled.store(true, Ordering::Relaxed);
+
+    let hook = Hook::new();
+    let installed = hook.run_async(move |event: &Event| {
+      match event.event_type {
+        EventType::HookEnabled => tap_enabled.store(true, Ordering::Relaxed),
+        EventType::HookDisabled => {
+          tap_enabled.store(false, Ordering::Relaxed);
+          log_event("warn", || "Listener hook disabled".to_string());
+        }
+        _ => {}
+      }
+      track_modifier(&held_modifiers, event);
+      // Filter on the Rust side — skip NAPI boundary for unwanted events
+      let bit = event_type_bit(&event.event_type);
+      if mask.load(Ordering::Relaxed) & bit == 0 || !filter.accepts(event) {
+        return;
+      }
+      let event_js = EventJs::from(event);
+      deliver(&tsfn, event_js, blocking);
+    });
+    if let Err(e) = installed {
+      let (failure, err) = install_error("listener", e);
+      *self.install_error.lock().unwrap() = Some(err.reason.clone());
+      return Err((failure, err));
+    }
+    log_event("info", || "Listener installed".to_string());
+    *self.install_error.lock().unwrap() = None;
+    *guard = Some(hook);
+    Ok(())
+  }
+}
+
+#[napi]
+impl HookJs {
+  /// Try installing the hook again. `startListen` returns a hook that is not
+  /// running (see `installError`) when installation failed with
+  /// `[TemporaryFailure]`; other failures throw immediately since retrying
+  /// won't help. A no-op while the hook is installed.
+  #[napi]
+  pub fn retry(&self) -> Result<()> {
+    self.install().map_err(|(_, e)| e)
+  }
+
+  /// Error message of the last failed install attempt, or `null`.
+  #[napi(getter)]
+  pub fn install_error(&self) -> Option<String> {
+    self.install_error.lock().unwrap().clone()
+  }
+
+  /// Stop listening.
+  #[napi]
+  pub fn stop(&self) -> Result<()> {
+    stop_listener(&self.hook, &self.held_modifiers)
+  }
+
+  /// Tear down in order, resolving once done: wait for input queued by
+  /// `setSimulationQueue` to run, release keys and buttons held via
+  /// simulation (see `releaseAllSimulated`), then stop the listener. Safe to
+  /// call more than once. `startListen` buffers nothing natively and runs no
+  /// background threads, so there is nothing else to flush or join.
+  #[napi]
+  pub fn shutdown(&self) -> AsyncTask<ShutdownTask> {
+    AsyncTask::new(ShutdownTask {
+      hook: self.hook.clone(),
+      held_modifiers: self.held_modifiers.clone(),
+      shared: None,
+    })
+  }
+
+  /// Whether the hook is installed and still receiving events. Becomes `false` if
+  /// the OS disables the hook (e.g. a macOS event tap timing out).
+  #[napi(getter)]
+  pub fn is_running(&self) -> bool {
+    let guard = self.hook.lock().unwrap();
+    guard.as_ref().is_some_and(|h| h.is_running()) && self.tap_enabled.load(Ordering::Relaxed)
+  }
+
+  /// Update the event filter bitmask at runtime without restarting the hook.
+  /// Each bit corresponds to an EventTypeJs value (bit 0 = HookEnabled, bit 2 = KeyPressed, etc.).
+  /// Set to 0x7FF (2047) for all events, or combine specific bits.
+  #[napi]
+  pub fn set_event_mask(&self, mask: u32) {
+    self.mask.store(mask, Ordering::Relaxed);
+  }
+
+  /// Get the current event filter bitmask.
+  #[napi(getter)]
+  pub fn event_mask(&self) -> u32 {
+    self.mask.load(Ordering::Relaxed)
+  }
+
+  /// The configuration this hook is running with, including any mask change
+  /// made via `setEventMask`. Can be passed back to `startListenWithConfig`.
+  #[napi(getter)]
+  pub fn config(&self) -> HookConfigJs {
+    HookConfigJs {
+      call_mode: Some(if self.blocking {
+        CallModeJs::Blocking
+      } else {
+        CallModeJs::NonBlocking
+      }),
+      ..self.filter.to_config(self.mask.load(Ordering::Relaxed))
+    }
+  }
+}
+
+// ============================================================================
+// Event Listening
+// ============================================================================
+
+/// Convert an EventType to its bitmask bit.
+fn event_type_bit(et: &EventType) -> u32 {
   match et {
     EventType::HookEnabled => 1 << 0,
     EventType::HookDisabled => 1 << 1,
@@ -1239,6 +2413,8 @@ fn event_type_bit(et: &EventType) -> u32 {
 /// - `EVENT_MASK_MOUSE_MOVEMENT` (0x300): MouseMoved | MouseDragged
 /// - `EVENT_MASK_MOUSE_WHEEL` (0x400): MouseWheel
 /// - `EVENT_MASK_MOUSE_ALL` (0x7E0): All mouse events
+///
+/// Prefer `maskWith`/`maskWithout`/`maskHas` over shifting bits by hand.
 #[napi]
 pub const EVENT_MASK_ALL: u32 = 0x7FF;
 
@@ -1298,6 +2474,208 @@ pub fn compute_event_mask(patterns: Vec<String>) -> u32 {
   }
 }
 
+/// Every event type, in bit order.
+const ALL_EVENT_TYPES: [EventType; 11] = [
+  EventType::HookEnabled,
+  EventType::HookDisabled,
+  EventType::KeyPressed,
+  EventType::KeyReleased,
+  EventType::KeyTyped,
+  EventType::MousePressed,
+  EventType::MouseReleased,
+  EventType::MouseClicked,
+  EventType::MouseMoved,
+  EventType::MouseDragged,
+  EventType::MouseWheel,
+];
+
+/// Check whether `mask` includes `event`.
+#[napi]
+pub fn mask_has(mask: u32, event: EventTypeJs) -> bool {
+  mask & event_type_bit(&event.into()) != 0
+}
+
+/// Return `mask` with `event` added.
+#[napi]
+pub fn mask_with(mask: u32, event: EventTypeJs) -> u32 {
+  mask | event_type_bit(&event.into())
+}
+
+/// Return `mask` with `event` removed.
+#[napi]
+pub fn mask_without(mask: u32, event: EventTypeJs) -> u32 {
+  mask & !event_type_bit(&event.into())
+}
+
+/// List the event types included in `mask`, in bit order. Bits above
+/// `EVENT_MASK_ALL` have no event type and are ignored.
+#[napi]
+pub fn mask_to_event_types(mask: u32) -> Vec<EventTypeJs> {
+  ALL_EVENT_TYPES
+    .iter()
+    .filter(|et| mask & event_type_bit(et) != 0)
+    .map(|&et| et.into())
+    .collect()
+}
+
+/// Display names (see `getEventTypeDisplayName`) of the event types included
+/// in `mask`, in bit order, e.g. `["Key Pressed", "Mouse Clicked"]` for `0x84`.
+#[napi]
+pub fn describe_mask(mask: u32) -> Vec<String> {
+  ALL_EVENT_TYPES
+    .iter()
+    .filter(|et| mask & event_type_bit(et) != 0)
+    .map(|et| event_type_display_name(et).to_string())
+    .collect()
+}
+
+/// Whether every event type in `sub` is also in `sup`.
+#[napi]
+pub fn is_mask_subset(sub: u32, sup: u32) -> bool {
+  sub & !sup == 0
+}
+
+/// Declarative hook setup for `startListenWithConfig`. Every field is optional;
+/// omitted fields leave the corresponding filter off.
+#[napi(object)]
+#[derive(Default)]
+pub struct HookConfigJs {
+  /// Event filter bitmask (see `EVENT_MASK_*`). Defaults to all events.
+  pub event_mask: Option<u32>,
+  /// Forward at most one MouseMoved/MouseDragged event per this many ms.
+  pub move_throttle_ms: Option<u32>,
+  /// Only forward keyboard events for these keys.
+  pub keys: Option<Vec<KeyJs>>,
+  /// Only forward mouse and wheel events inside this rectangle.
+  pub region: Option<RectJs>,
+  /// Drop a keyboard event that repeats the previous one (same key and type)
+  /// within this many ms.
+  pub debounce_ms: Option<u32>,
+  /// Drop repeated KeyPressed events while a key is held down.
+  pub suppress_autorepeat: Option<bool>,
+  /// How events are queued to the callback (see `CallModeJs`). Defaults to
+  /// `NonBlocking`.
+  pub call_mode: Option<CallModeJs>,
+}
+
+/// Native-side filters from a `HookConfigJs`, applied before crossing into JS.
+struct ListenFilter {
+  move_throttle_ms: Option<u32>,
+  keys: Option<Vec<u32>>,
+  region: Option<Rect>,
+  debounce_ms: Option<u32>,
+  suppress_autorepeat: bool,
+  state: Mutex<ListenFilterState>,
+}
+
+#[derive(Default)]
+struct ListenFilterState {
+  last_move: Option<Instant>,
+  /// Key code, event type bit and time of the last forwarded keyboard event.
+  last_key: Option<(u32, u32, Instant)>,
+  held: Vec<u32>,
+}
+
+impl ListenFilter {
+  fn new(config: HookConfigJs) -> Self {
+    ListenFilter {
+      move_throttle_ms: config.move_throttle_ms,
+      keys: config
+        .keys
+        .map(|keys| keys.into_iter().map(|k| k as u32).collect()),
+      region: config.region.map(|r| Rect {
+        x: r.x,
+        y: r.y,
+        width: r.width,
+        height: r.height,
+      }),
+      debounce_ms: config.debounce_ms,
+      suppress_autorepeat: config.suppress_autorepeat.unwrap_or(false),
+      state: Mutex::new(ListenFilterState::default()),
+    }
+  }
+
+  fn to_config(&self, event_mask: u32) -> HookConfigJs {
+    HookConfigJs {
+      event_mask: Some(event_mask),
+      move_throttle_ms: self.move_throttle_ms,
+      keys: self.keys.as_ref().map(|keys| {
+        keys
+          .iter()
+          .filter_map(|&k| key_from_i32(k as i32))
+          .collect()
+      }),
+      region: self.region.as_ref().map(RectJs::from),
+      debounce_ms: self.debounce_ms,
+      suppress_autorepeat: Some(self.suppress_autorepeat),
+      call_mode: None,
+    }
+  }
+
+  /// Whether `event` should be forwarded. Updates throttle/debounce/held-key
+  /// state as a side effect, so call it once per event.
+  fn accepts(&self, event: &Event) -> bool {
+    if let Some(region) = &self.region {
+      let pos = match (&event.mouse, &event.wheel) {
+        (Some(m), _) => Some((m.x, m.y)),
+        (None, Some(w)) => Some((w.x, w.y)),
+        _ => None,
+      };
+      if let Some((x, y)) = pos {
+        if !rect_contains(region, x, y) {
+          return false;
+        }
+      }
+    }
+
+    let now = Instant::now();
+    let mut state = self.state.lock().unwrap();
+    match event.event_type {
+      EventType::MouseMoved | EventType::MouseDragged => {
+        if let Some(ms) = self.move_throttle_ms {
+          let within = state
+            .last_move
+            .is_some_and(|t| now.duration_since(t) < Duration::from_millis(ms as u64));
+          if within {
+            return false;
+          }
+          state.last_move = Some(now);
+        }
+        true
+      }
+      EventType::KeyPressed | EventType::KeyReleased | EventType::KeyTyped => {
+        let Some(kb) = &event.keyboard else {
+          return true;
+        };
+        let code = key_id(kb.key);
+        if self.keys.as_ref().is_some_and(|keys| !keys.contains(&code)) {
+          return false;
+        }
+        if self.suppress_autorepeat {
+          match event.event_type {
+            EventType::KeyPressed if state.held.contains(&code) => return false,
+            EventType::KeyPressed => state.held.push(code),
+            EventType::KeyReleased => state.held.retain(|&k| k != code),
+            _ => {}
+          }
+        }
+        let bit = event_type_bit(&event.event_type);
+        if let Some(ms) = self.debounce_ms {
+          let repeat = state.last_key.is_some_and(|(k, b, t)| {
+            k == code && b == bit && now.duration_since(t) < Duration::from_millis(ms as u64)
+          });
+          if repeat {
+            return false;
+          }
+        }
+        state.last_key = Some((code, bit, now));
+        true
+      }
+      _ => true,
+    }
+  }
+}
+
 /// Start listening for input events with a callback.
 /// Returns a HookJs instance that can be used to stop the listener.
 ///
@@ -1307,632 +2685,6535 @@ pub fn compute_event_mask(patterns: Vec<String>) -> u32 {
 ///
 /// Use the `EVENT_MASK_*` constants to compose masks. If `None`, all events are forwarded.
 /// The mask can be updated at runtime via `HookJs.setEventMask()`.
+///
+/// Install failures throw with a code prefix: `[PermissionDenied]`, `[Unsupported]`,
+/// `[AlreadyInstalledByAnotherProcess]` or `[Unknown]`. Only for a recognised transient
+/// error, `[TemporaryFailure]`, is the hook returned without running (see
+/// `HookJs.installError`) so it can be `retry()`ed.
 #[napi(ts_return_type = "HookJs")]
 pub fn start_listen(
   #[napi(ts_arg_type = "(event: EventJs) => void")] callback: Function<(), ()>,
   event_mask: Option<u32>,
 ) -> Result<HookJs> {
+  listen(
+    callback,
+    HookConfigJs {
+      event_mask,
+      ..Default::default()
+    },
+  )
+}
+
+/// Start listening with every filter from `config` applied from the first event,
+/// so there is no window where defaults are in effect. Use `HookJs.config` to
+/// read the effective configuration back as a plain object.
+#[napi(ts_return_type = "HookJs")]
+pub fn start_listen_with_config(
+  #[napi(ts_arg_type = "(event: EventJs) => void")] callback: Function<(), ()>,
+  config: HookConfigJs,
+) -> Result<HookJs> {
+  listen(callback, config)
+}
+
+fn listen(callback: Function<(), ()>, config: HookConfigJs) -> Result<HookJs> {
+  let blocking = matches!(config.call_mode, Some(CallModeJs::Blocking));
   let tsfn = callback
     .build_threadsafe_function()
+    .max_queue_size::<EVENT_QUEUE_SIZE>()
     .build_callback(|ctx: ThreadsafeCallContext<EventJs>| Ok(vec![ctx.value]))?;
 
-  let mask = Arc::new(AtomicU32::new(event_mask.unwrap_or(EVENT_MASK_ALL)));
-  let mask_clone = mask.clone();
+  let slot = Arc::new(Mutex::new(None));
+  let hook = HookJs {
+    held_modifiers: register_modifier_tracker(&slot),
+    hook: slot,
+    mask: Arc::new(AtomicU32::new(config.event_mask.unwrap_or(EVENT_MASK_ALL))),
+    tap_enabled: Arc::new(AtomicBool::new(true)),
+    filter: Arc::new(ListenFilter::new(config)),
+    tsfn: Arc::new(tsfn),
+    blocking,
+    install_error: Mutex::new(None),
+  };
+  match hook.install() {
+    Ok(()) => Ok(hook),
+    // Hand back the idle hook so the caller can `retry()`
+    Err((InstallFailure::TemporaryFailure, _)) => Ok(hook),
+    Err((_, e)) => Err(e),
+  }
+}
 
-  let hook = Hook::new();
-  hook
-    .run_async(move |event: &Event| {
-      // Filter on the Rust side — skip NAPI boundary for unwanted events
-      let bit = event_type_bit(&event.event_type);
-      if mask_clone.load(Ordering::Relaxed) & bit == 0 {
-        return;
-      }
-      let event_js = EventJs::from(event);
-      let _ = tsfn.call(event_js, ThreadsafeFunctionCallMode::NonBlocking);
-    })
-    .map_err(|e| {
-      Error::new(
-        Status::GenericFailure,
-        format!("Failed to start listener: {}", e),
-      )
-    })?;
+/// Why a native hook failed to install. The code is prefixed to the error
+/// message, e.g. `"[PermissionDenied] Failed to start listener: ..."`.
+#[derive(Clone, Copy)]
+enum InstallFailure {
+  /// Missing OS permission (macOS Accessibility/Input Monitoring, ...).
+  PermissionDenied,
+  /// The platform or session can't host a hook (e.g. Wayland); don't retry.
+  Unsupported,
+  /// Another process holds an exclusive hook or grab.
+  AlreadyInstalledByAnotherProcess,
+  /// A transient condition (timeout, busy, interrupted); retrying may help.
+  TemporaryFailure,
+  /// Anything not recognised above; thrown, since retrying may not help.
+  Unknown,
+}
 
-  Ok(HookJs {
-    hook: Arc::new(Mutex::new(Some(hook))),
-    mask,
-  })
+impl InstallFailure {
+  /// monio reports install failures as text only, so classify by message.
+  fn classify(message: &str) -> Self {
+    let message = message.to_lowercase();
+    let has = |words: &[&str]| words.iter().any(|w| message.contains(w));
+    if has(&[
+      "permission",
+      "accessibility",
+      "not trusted",
+      "denied",
+      "privilege",
+    ]) {
+      InstallFailure::PermissionDenied
+    } else if has(&["unsupported", "not supported", "not implemented", "wayland"]) {
+      InstallFailure::Unsupported
+    } else if has(&["already", "in use", "grab"]) {
+      InstallFailure::AlreadyInstalledByAnotherProcess
+    } else if has(&[
+      "timeout",
+      "timed out",
+      "temporar",
+      "try again",
+      "busy",
+      "interrupted",
+    ]) {
+      InstallFailure::TemporaryFailure
+    } else {
+      InstallFailure::Unknown
+    }
+  }
+
+  fn code(self) -> &'static str {
+    match self {
+      InstallFailure::PermissionDenied => "PermissionDenied",
+      InstallFailure::Unsupported => "Unsupported",
+      InstallFailure::AlreadyInstalledByAnotherProcess => "AlreadyInstalledByAnotherProcess",
+      InstallFailure::TemporaryFailure => "TemporaryFailure",
+      InstallFailure::Unknown => "Unknown",
+    }
+  }
+}
+
+/// Classify a `run_async` failure and build the error surfaced to JS.
+fn install_error(what: &str, e: impl std::fmt::Display) -> (InstallFailure, Error) {
+  let message = e.to_string();
+  let failure = InstallFailure::classify(&message);
+  let message = format!("[{}] Failed to start {}: {}", failure.code(), what, message);
+  log_event("error", || message.clone());
+  (failure, Error::new(Status::GenericFailure, message))
+}
+
+/// Non-throwing outcome of `InputHook.tryStart()` / `tryStop()`.
+#[napi(object)]
+pub struct TryResultJs {
+  pub ok: bool,
+  /// Install failure code (`"PermissionDenied"`, `"Unsupported"`,
+  /// `"AlreadyInstalledByAnotherProcess"`, `"TemporaryFailure"`, `"Unknown"`).
+  pub code: Option<String>,
+  pub message: Option<String>,
+}
+
+impl From<Result<()>> for TryResultJs {
+  fn from(result: Result<()>) -> Self {
+    match result {
+      Ok(()) => TryResultJs {
+        ok: true,
+        code: None,
+        message: None,
+      },
+      Err(e) => {
+        // Only install_error prefixes messages with a bracketed code
+        let code = e
+          .reason
+          .strip_prefix('[')
+          .and_then(|rest| rest.split_once(']'))
+          .map(|(code, _)| code.to_string());
+        TryResultJs {
+          ok: false,
+          code,
+          message: Some(e.reason),
+        }
+      }
+    }
+  }
 }
 
 // ============================================================================
-// EventEmitter-style InputHook (per-event-type callbacks, Rust-side dispatch)
+// Declarative Event Filters (evaluated natively, see InputHook.setFilter)
 // ============================================================================
 
-/// Keyboard event payload for onKeyDown / onKeyUp callbacks.
+/// A filter evaluated in Rust before an event is dispatched to JS.
+///
+/// Grammar: a spec matches an event when **every** field that is set matches
+/// (fields combine with AND; unset fields are ignored, so `{}` matches all):
+///
+/// - `eventTypes`: the event's type is one of these
+/// - `keys`: a keyboard event whose key is one of these (non-keyboard events fail)
+/// - `buttons`: a mouse event whose button is one of these (events without a
+///   button, such as moves, fail)
+/// - `region`: a mouse or wheel event inside this rectangle (other events fail)
+/// - `all`: every sub-spec matches (AND)
+/// - `any`: at least one sub-spec matches (OR); an empty list matches nothing
+///
+/// Build specs as plain objects or with the `filter*` helpers, e.g.
+/// `filterAny([filterKeys([KeyJs.Escape]), filterButtons([ButtonJs.Right])])`.
 #[napi(object)]
-pub struct KeyboardEventJs {
-  pub key: KeyJs,
-  pub raw_code: u32,
-  pub time: f64,
+#[derive(Default)]
+pub struct FilterSpecJs {
+  pub event_types: Option<Vec<EventTypeJs>>,
+  pub keys: Option<Vec<KeyJs>>,
+  pub buttons: Option<Vec<ButtonJs>>,
+  pub region: Option<RectJs>,
+  pub all: Option<Vec<FilterSpecJs>>,
+  pub any: Option<Vec<FilterSpecJs>>,
 }
 
-/// Mouse button event payload for onMouseDown / onMouseUp / onClick callbacks.
-#[napi(object)]
-pub struct MouseButtonEventJs {
-  pub x: f64,
-  pub y: f64,
-  pub button: ButtonJs,
-  pub time: f64,
+/// Spec matching events of the given types.
+#[napi]
+pub fn filter_event_types(event_types: Vec<EventTypeJs>) -> FilterSpecJs {
+  FilterSpecJs {
+    event_types: Some(event_types),
+    ..Default::default()
+  }
 }
 
-/// Mouse move event payload for onMouseMove callbacks.
-#[napi(object)]
-pub struct MouseMoveEventJs {
-  pub x: f64,
-  pub y: f64,
-  pub time: f64,
+/// Spec matching keyboard events for the given keys.
+#[napi]
+pub fn filter_keys(keys: Vec<KeyJs>) -> FilterSpecJs {
+  FilterSpecJs {
+    keys: Some(keys),
+    ..Default::default()
+  }
 }
 
-/// Wheel event payload for onWheel callbacks.
-#[napi(object)]
-pub struct WheelEventJs {
-  pub x: f64,
-  pub y: f64,
-  pub direction: ScrollDirectionJs,
-  pub delta: f64,
-  pub time: f64,
+/// Spec matching mouse events for the given buttons.
+#[napi]
+pub fn filter_buttons(buttons: Vec<ButtonJs>) -> FilterSpecJs {
+  FilterSpecJs {
+    buttons: Some(buttons),
+    ..Default::default()
+  }
 }
 
-// Type aliases for the per-event threadsafe functions.
-// Each TSFN carries its own typed payload, avoiding the generic EventJs.
-// build_callback() produces: ThreadsafeFunction<T, (), Vec<T>, Status, false>
-type KeyboardTsFn = ThreadsafeFunction<KeyboardEventJs, (), Vec<KeyboardEventJs>, Status, false>;
-type MouseButtonTsFn =
-  ThreadsafeFunction<MouseButtonEventJs, (), Vec<MouseButtonEventJs>, Status, false>;
-type MouseMoveTsFn = ThreadsafeFunction<MouseMoveEventJs, (), Vec<MouseMoveEventJs>, Status, false>;
-type WheelTsFn = ThreadsafeFunction<WheelEventJs, (), Vec<WheelEventJs>, Status, false>;
+/// Spec matching mouse and wheel events inside a rectangle.
+#[napi]
+pub fn filter_region(region: RectJs) -> FilterSpecJs {
+  FilterSpecJs {
+    region: Some(region),
+    ..Default::default()
+  }
+}
 
-/// Internal storage for per-event-type callbacks.
-struct InputHookCallbacks {
-  key_down: Option<KeyboardTsFn>,
-  key_up: Option<KeyboardTsFn>,
-  mouse_down: Option<MouseButtonTsFn>,
-  mouse_up: Option<MouseButtonTsFn>,
-  mouse_click: Option<MouseButtonTsFn>,
-  mouse_move: Option<MouseMoveTsFn>,
-  mouse_wheel: Option<WheelTsFn>,
-}
-
-// SAFETY: All fields are Option<ThreadsafeFunction<...>>, which is designed for
-// cross-thread use. If a non-Send/Sync field is ever added to this struct,
-// these impls must be revisited — the compiler will NOT catch the violation.
-unsafe impl Send for InputHookCallbacks {}
-unsafe impl Sync for InputHookCallbacks {}
+/// Spec matching when every one of `specs` matches.
+#[napi]
+pub fn filter_all(specs: Vec<FilterSpecJs>) -> FilterSpecJs {
+  FilterSpecJs {
+    all: Some(specs),
+    ..Default::default()
+  }
+}
 
-impl InputHookCallbacks {
-  fn new() -> Self {
-    Self {
-      key_down: None,
-      key_up: None,
-      mouse_down: None,
-      mouse_up: None,
-      mouse_click: None,
-      mouse_move: None,
-      mouse_wheel: None,
+/// Spec matching when at least one of `specs` matches.
+#[napi]
+pub fn filter_any(specs: Vec<FilterSpecJs>) -> FilterSpecJs {
+  FilterSpecJs {
+    any: Some(specs),
+    ..Default::default()
+  }
+}
+
+/// `FilterSpecJs` compiled to plain codes so matching never touches JS values.
+#[derive(Clone)]
+struct EventFilter {
+  /// Bitmask over `event_type_bit`.
+  event_types: Option<u32>,
+  keys: Option<Vec<u32>>,
+  buttons: Option<Vec<u32>>,
+  region: Option<Rect>,
+  all: Vec<EventFilter>,
+  any: Option<Vec<EventFilter>>,
+}
+
+impl From<FilterSpecJs> for EventFilter {
+  fn from(spec: FilterSpecJs) -> Self {
+    EventFilter {
+      event_types: spec.event_types.map(|types| {
+        types
+          .into_iter()
+          .fold(0, |mask, et| mask | event_type_bit(&et.into()))
+      }),
+      keys: spec
+        .keys
+        .map(|keys| keys.into_iter().map(|k| k as u32).collect()),
+      buttons: spec
+        .buttons
+        .map(|buttons| buttons.into_iter().map(|b| b as u32).collect()),
+      region: spec.region.map(|r| Rect {
+        x: r.x,
+        y: r.y,
+        width: r.width,
+        height: r.height,
+      }),
+      all: spec
+        .all
+        .unwrap_or_default()
+        .into_iter()
+        .map(EventFilter::from)
+        .collect(),
+      any: spec
+        .any
+        .map(|specs| specs.into_iter().map(EventFilter::from).collect()),
     }
   }
+}
 
-  /// Compute the event mask from which callbacks are registered.
+impl EventFilter {
+  fn matches(&self, event: &InputEvent) -> bool {
+    if let Some(mask) = self.event_types {
+      if mask & event_type_bit(&event.event_type) == 0 {
+        return false;
+      }
+    }
+    if let Some(keys) = &self.keys {
+      if !event
+        .keyboard
+        .is_some_and(|kb| keys.contains(&key_id(kb.key)))
+      {
+        return false;
+      }
+    }
+    if let Some(buttons) = &self.buttons {
+      let button = event.mouse.and_then(|m| m.button);
+      if !button.is_some_and(|b| buttons.contains(&button_id(b))) {
+        return false;
+      }
+    }
+    if let Some(region) = &self.region {
+      let pos = match (event.mouse, event.wheel) {
+        (Some(m), _) => Some((m.x, m.y)),
+        (None, Some(w)) => Some((w.x, w.y)),
+        _ => None,
+      };
+      if !pos.is_some_and(|(x, y)| rect_contains(region, x, y)) {
+        return false;
+      }
+    }
+    if !self.all.iter().all(|f| f.matches(event)) {
+      return false;
+    }
+    match &self.any {
+      Some(any) => any.iter().any(|f| f.matches(event)),
+      None => true,
+    }
+  }
+}
+
+// ============================================================================
+// EventEmitter-style InputHook (per-event-type callbacks, Rust-side dispatch)
+// ============================================================================
+
+/// Bits of the `modifiers` field on InputHook event payloads. Left and right
+/// variants of a modifier share a bit.
+#[napi]
+pub const MODIFIER_SHIFT: u32 = 1 << 0;
+#[napi]
+pub const MODIFIER_CTRL: u32 = 1 << 1;
+#[napi]
+pub const MODIFIER_ALT: u32 = 1 << 2;
+#[napi]
+pub const MODIFIER_META: u32 = 1 << 3;
+
+/// Tracking bit for a modifier key: the public `MODIFIER_*` bit for left keys,
+/// shifted up by 4 for right keys so releasing one side keeps the other held.
+fn modifier_key_bit(key: Key) -> u32 {
+  match KeyJs::from(key) {
+    KeyJs::ShiftLeft => MODIFIER_SHIFT,
+    KeyJs::ControlLeft => MODIFIER_CTRL,
+    KeyJs::AltLeft => MODIFIER_ALT,
+    KeyJs::MetaLeft => MODIFIER_META,
+    KeyJs::ShiftRight => MODIFIER_SHIFT << 4,
+    KeyJs::ControlRight => MODIFIER_CTRL << 4,
+    KeyJs::AltRight => MODIFIER_ALT << 4,
+    KeyJs::MetaRight => MODIFIER_META << 4,
+    _ => 0,
+  }
+}
+
+/// Held modifiers, with left and right sides collapsed.
+#[napi(object)]
+pub struct ModifierStateJs {
+  pub shift: bool,
+  pub ctrl: bool,
+  pub alt: bool,
+  pub meta: bool,
+}
+
+impl From<u32> for ModifierStateJs {
+  fn from(bits: u32) -> Self {
+    Self {
+      shift: bits & MODIFIER_SHIFT != 0,
+      ctrl: bits & MODIFIER_CTRL != 0,
+      alt: bits & MODIFIER_ALT != 0,
+      meta: bits & MODIFIER_META != 0,
+    }
+  }
+}
+
+/// Payload for onModifierChange callbacks.
+#[napi(object)]
+pub struct ModifierChangeEventJs {
+  /// State after the transition.
+  pub modifiers: ModifierStateJs,
+  /// `"shift"`, `"ctrl"`, `"alt"` or `"meta"`.
+  pub changed: String,
+  /// `true` if `changed` went down, `false` if it went up.
+  pub pressed: bool,
+  pub time: f64,
+}
+
+/// Keyboard event payload for onKeyDown / onKeyUp callbacks.
+#[napi(object)]
+pub struct KeyboardEventJs {
+  pub key: KeyJs,
+  /// Platform key code, as on `KeyboardDataJs.rawCode`.
+  pub raw_code: u32,
+  pub time: f64,
+  /// Counts every event the hook sees, before masking and filtering, so a gap
+  /// means events this callback did not get: filtered, rate-limited, merged by
+  /// wheel coalescing, or dropped by a full callback queue (see `healthcheck`).
+  pub seq: f64,
+  /// Modifiers held when the event occurred (`MODIFIER_*` bits).
+  pub modifiers: u32,
+  /// Release events only: how long the key was held, in ms.
+  pub held_ms: Option<f64>,
+  /// Release events only: `true` if no press was seen (the hook started
+  /// mid-hold), in which case `heldMs` is 0.
+  pub synthetic: Option<bool>,
+  /// Title of the focused window when the event was dispatched. Only set with
+  /// `setIncludeWindowContext(true)`, and only where `getFocusedWindow` works.
+  pub window_title: Option<String>,
+  /// Process name of that window, as `windowTitle`.
+  pub process_name: Option<String>,
+  /// Id of the device the event came from. Best-effort and platform-dependent:
+  /// no backend reports one yet, so this is always `null`.
+  pub device_id: Option<u32>,
+  /// Native fields of the event; only set with `setIncludeRaw(true)`.
+  pub raw: Option<RawEventDataJs>,
+}
+
+/// Mouse button event payload for onMouseDown / onMouseUp / onClick callbacks.
+#[napi(object)]
+pub struct MouseButtonEventJs {
+  pub x: f64,
+  pub y: f64,
+  pub button: ButtonJs,
+  pub time: f64,
+  /// Sequence number, as on `KeyboardEventJs.seq`.
+  pub seq: f64,
+  /// 1 for a single click, 2 for a double click, 3 for a triple click, etc.
+  pub click_count: u32,
+  /// Modifiers held when the event occurred (`MODIFIER_*` bits).
+  pub modifiers: u32,
+  /// onMouseUp only: how long the button was held, in ms.
+  pub held_ms: Option<f64>,
+  /// onMouseUp only: `true` if no press was seen (the hook started mid-hold),
+  /// in which case `heldMs` is 0.
+  pub synthetic: Option<bool>,
+  /// As on `KeyboardEventJs.windowTitle`.
+  pub window_title: Option<String>,
+  /// As on `KeyboardEventJs.processName`.
+  pub process_name: Option<String>,
+  /// As on `KeyboardEventJs.deviceId`.
+  pub device_id: Option<u32>,
+  /// As on `KeyboardEventJs.raw`.
+  pub raw: Option<RawEventDataJs>,
+}
+
+/// Mouse move event payload for onMouseMove callbacks.
+#[napi(object)]
+pub struct MouseMoveEventJs {
+  pub x: f64,
+  pub y: f64,
+  pub time: f64,
+  /// Sequence number, as on `KeyboardEventJs.seq`.
+  pub seq: f64,
+  /// Modifiers held when the event occurred (`MODIFIER_*` bits).
+  pub modifiers: u32,
+  /// As on `KeyboardEventJs.deviceId`.
+  pub device_id: Option<u32>,
+  /// As on `KeyboardEventJs.raw`.
+  pub raw: Option<RawEventDataJs>,
+}
+
+/// Wheel event payload for onWheel callbacks.
+#[napi(object)]
+pub struct WheelEventJs {
+  pub x: f64,
+  pub y: f64,
+  pub direction: ScrollDirectionJs,
+  /// In notches when `setNormalizeWheelDelta(true)`, else as `rawDelta`.
+  pub delta: f64,
+  /// Delta as reported by the platform (Windows: multiples of 120 per notch;
+  /// macOS and X11: lines or clicks).
+  pub raw_delta: f64,
+  pub time: f64,
+  /// Sequence number, as on `KeyboardEventJs.seq`.
+  pub seq: f64,
+  /// Modifiers held when the event occurred (`MODIFIER_*` bits).
+  pub modifiers: u32,
+  /// As on `KeyboardEventJs.deviceId`.
+  pub device_id: Option<u32>,
+  /// As on `KeyboardEventJs.raw`.
+  pub raw: Option<RawEventDataJs>,
+}
+
+/// Native event fields attached to payloads by `setIncludeRaw(true)`.
+#[napi(object)]
+#[derive(Clone)]
+pub struct RawEventDataJs {
+  /// `"windows"`, `"macos"` or `"linux"`.
+  pub platform: String,
+  /// The native values monio passes through, by name: `rawCode` (Windows
+  /// virtual-key code, macOS keycode or X11 keycode) for keys, `rawDelta` for
+  /// the wheel, and the unrounded `x` and `y` for mouse events. monio converts
+  /// the rest of the native record on the hook thread and does not keep it.
+  pub fields: HashMap<String, f64>,
+}
+
+/// Payload for onTapDisabled callbacks.
+#[napi(object)]
+pub struct TapDisabledEventJs {
+  pub time: f64,
+}
+
+/// Payload for onAutoStop callbacks.
+#[napi(object)]
+pub struct AutoStopEventJs {
+  /// `"duration"` or `"events"`: which limit stopped the hook.
+  pub reason: String,
+  /// Events dispatched during the session.
+  pub events: u32,
+  pub time: f64,
+}
+
+/// Payload for the `InputHook.setHeartbeatMs` callback.
+#[napi(object)]
+pub struct HeartbeatEventJs {
+  /// Same time base as event `time`.
+  pub time: f64,
+  /// ms since the hook last saw an event, as in `healthcheck()`.
+  pub last_event_ago_ms: Option<f64>,
+}
+
+/// Per-event-type counts returned by `InputHook.getEventCounts()`.
+#[napi(object)]
+pub struct EventCountsJs {
+  pub hook_enabled: f64,
+  pub hook_disabled: f64,
+  pub key_pressed: f64,
+  pub key_released: f64,
+  pub key_typed: f64,
+  pub mouse_pressed: f64,
+  pub mouse_released: f64,
+  pub mouse_clicked: f64,
+  pub mouse_moved: f64,
+  pub mouse_dragged: f64,
+  pub mouse_wheel: f64,
+}
+
+/// Default `staleAfterMs` for `InputHook.healthcheck()`.
+const DEFAULT_STALE_AFTER_MS: u32 = 30_000;
+
+/// Snapshot returned by `InputHook.healthcheck()`.
+#[napi(object)]
+pub struct HealthJs {
+  /// Same as `InputHook.isRunning`.
+  pub running: bool,
+  /// Whether the native hook thread is running and delivered something within
+  /// `staleAfterMs`.
+  pub thread_alive: bool,
+  /// The hook is installed but its thread has been silent for longer than
+  /// `staleAfterMs`.
+  pub stale: bool,
+  /// Milliseconds since the native hook thread last delivered an event (or
+  /// since `start()`), or `null` while no hook is running. Injected events
+  /// don't count.
+  pub last_dispatch_ago_ms: Option<f64>,
+  /// Milliseconds since the hook thread last dispatched an event, or `null` if
+  /// none has arrived since `start()`.
+  pub last_event_ago_ms: Option<f64>,
+  /// Events dropped since `start()` because the JS callback queue rejected them.
+  pub dropped_count: u32,
+  /// Whether the OS event tap is enabled (see `onTapDisabled`).
+  pub tap_enabled: bool,
+  /// Events queued for JS callbacks that have not started running yet.
+  pub backlog: u32,
+}
+
+/// Payload for `InputHook.onSlowConsumer`.
+#[napi(object)]
+pub struct SlowConsumerEventJs {
+  /// Events queued for JS callbacks that had not started running yet.
+  pub backlog: u32,
+  pub time: f64,
+}
+
+// Type aliases for the per-event threadsafe functions.
+// Each TSFN carries its own typed payload, avoiding the generic EventJs.
+type KeyboardTsFn = EventCallback<KeyboardEventJs>;
+type MouseButtonTsFn = EventCallback<MouseButtonEventJs>;
+type MouseMoveTsFn = EventCallback<MouseMoveEventJs>;
+type WheelTsFn = EventCallback<WheelEventJs>;
+type TapDisabledTsFn = EventCallback<TapDisabledEventJs>;
+type AutoStopTsFn = EventCallback<AutoStopEventJs>;
+type ModifierChangeTsFn = EventCallback<ModifierChangeEventJs>;
+type HeartbeatTsFn = EventCallback<HeartbeatEventJs>;
+type HotkeyTsFn = EventCallback<HotkeyEventJs>;
+type SlowConsumerTsFn = EventCallback<SlowConsumerEventJs>;
+
+/// Build an `InputHook` callback TSFN that decrements `backlog` as each queued
+/// event reaches JS, so `backlog` tracks how far the JS thread lags behind.
+fn tracked_callback<T: 'static + ToNapiValue>(
+  backlog: &Arc<AtomicU32>,
+  callback: Function<(), ()>,
+) -> Result<EventCallback<T>> {
+  let backlog = Arc::clone(backlog);
+  callback
+    .build_threadsafe_function()
+    .max_queue_size::<EVENT_QUEUE_SIZE>()
+    .build_callback(move |ctx: ThreadsafeCallContext<T>| {
+      let _ = backlog.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| {
+        Some(n.saturating_sub(1))
+      });
+      Ok(vec![ctx.value])
+    })
+}
+
+/// A `Copy` snapshot of the event fields `InputHook` dispatch needs.
+///
+/// Decoupled from monio's `Event` so the same dispatch code can run on events
+/// from the native hook and on events injected from JS for testing.
+#[derive(Clone, Copy)]
+struct InputEvent {
+  event_type: EventType,
+  time: f64,
+  keyboard: Option<KeyInput>,
+  mouse: Option<MouseInput>,
+  wheel: Option<WheelInput>,
+}
+
+#[derive(Clone, Copy)]
+struct KeyInput {
+  key: Key,
+  raw_code: u32,
+}
+
+#[derive(Clone, Copy)]
+struct MouseInput {
+  x: f64,
+  y: f64,
+  button: Option<Button>,
+}
+
+#[derive(Clone, Copy)]
+struct WheelInput {
+  x: f64,
+  y: f64,
+  direction: ScrollDirection,
+  delta: f64,
+  /// `delta` before normalization (see `setNormalizeWheelDelta`).
+  raw_delta: f64,
+}
+
+impl From<&Event> for InputEvent {
+  fn from(event: &Event) -> Self {
+    InputEvent {
+      event_type: event.event_type,
+      time: event
+        .time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or(0.0),
+      keyboard: event.keyboard.as_ref().map(|kb| KeyInput {
+        key: kb.key,
+        raw_code: kb.raw_code,
+      }),
+      mouse: event.mouse.as_ref().map(|m| MouseInput {
+        x: m.x,
+        y: m.y,
+        button: m.button,
+      }),
+      wheel: event.wheel.as_ref().map(|w| WheelInput {
+        x: w.x,
+        y: w.y,
+        direction: w.direction,
+        delta: w.delta,
+        raw_delta: w.delta,
+      }),
+    }
+  }
+}
+
+impl From<EventJs> for InputEvent {
+  fn from(event: EventJs) -> Self {
+    InputEvent {
+      event_type: event.event_type.into(),
+      time: event.time,
+      keyboard: event.keyboard.map(|kb| KeyInput {
+        key: kb.key.into(),
+        raw_code: kb.raw_code,
+      }),
+      mouse: event.mouse.map(|m| MouseInput {
+        x: m.x,
+        y: m.y,
+        button: m.button.map(|b| b.into()),
+      }),
+      wheel: event.wheel.map(|w| WheelInput {
+        x: w.x,
+        y: w.y,
+        direction: w.direction.into(),
+        delta: w.delta,
+        raw_delta: w.delta,
+      }),
+    }
+  }
+}
+
+// ============================================================================
+// Generic to Typed Payloads (startListen events in InputHook shapes)
+// ============================================================================
+//
+// `EventJs` carries no modifier state, sequence number or click/hold
+// tracking, so `modifiers` and `seq` are 0, `clickCount` is 1 and the
+// release-only fields are unset.
+
+/// Reshape a `KeyPressed`/`KeyReleased` event as an `onKeyDown`/`onKeyUp`
+/// payload; `null` for other events.
+#[napi]
+pub fn event_to_keyboard(event: EventJs) -> Option<KeyboardEventJs> {
+  let event = InputEvent::from(event);
+  let kb = event.keyboard?;
+  matches!(
+    event.event_type,
+    EventType::KeyPressed | EventType::KeyReleased
+  )
+  .then(|| KeyboardEventJs {
+    key: kb.key.into(),
+    raw_code: kb.raw_code,
+    time: event.time,
+    seq: 0.0,
+    modifiers: 0,
+    held_ms: None,
+    synthetic: None,
+    window_title: None,
+    process_name: None,
+    device_id: None,
+    raw: None,
+  })
+}
+
+/// Reshape a mouse press, release or click as an `onMouseDown`/`onMouseUp`/
+/// `onClick` payload (a missing button is reported as Left, as the hook
+/// does); `null` for other events.
+#[napi]
+pub fn event_to_mouse_button(event: EventJs) -> Option<MouseButtonEventJs> {
+  let event = InputEvent::from(event);
+  let m = event.mouse?;
+  matches!(
+    event.event_type,
+    EventType::MousePressed | EventType::MouseReleased | EventType::MouseClicked
+  )
+  .then(|| MouseButtonEventJs {
+    x: m.x,
+    y: m.y,
+    button: m.button.unwrap_or(Button::Left).into(),
+    time: event.time,
+    seq: 0.0,
+    click_count: 1,
+    modifiers: 0,
+    held_ms: None,
+    synthetic: None,
+    window_title: None,
+    process_name: None,
+    device_id: None,
+    raw: None,
+  })
+}
+
+/// Reshape a move or drag as an `onMouseMove` payload; `null` for other events.
+#[napi]
+pub fn event_to_mouse_move(event: EventJs) -> Option<MouseMoveEventJs> {
+  let event = InputEvent::from(event);
+  let m = event.mouse?;
+  matches!(
+    event.event_type,
+    EventType::MouseMoved | EventType::MouseDragged
+  )
+  .then(|| MouseMoveEventJs {
+    x: m.x,
+    y: m.y,
+    time: event.time,
+    seq: 0.0,
+    modifiers: 0,
+    device_id: None,
+    raw: None,
+  })
+}
+
+/// Reshape a wheel event as an `onWheel` payload; `null` for other events.
+#[napi]
+pub fn event_to_wheel(event: EventJs) -> Option<WheelEventJs> {
+  let event = InputEvent::from(event);
+  let w = event.wheel?;
+  matches!(event.event_type, EventType::MouseWheel).then(|| WheelEventJs {
+    x: w.x,
+    y: w.y,
+    direction: w.direction.into(),
+    delta: w.delta,
+    raw_delta: w.raw_delta,
+    time: event.time,
+    seq: 0.0,
+    modifiers: 0,
+    device_id: None,
+    raw: None,
+  })
+}
+
+/// Listeners for one event type, in registration order, with their ids.
+type Listeners<F> = Vec<(u32, F)>;
+
+/// Internal storage for per-event-type callbacks.
+struct InputHookCallbacks {
+  key_down: Listeners<KeyboardTsFn>,
+  key_up: Listeners<KeyboardTsFn>,
+  mouse_down: Listeners<MouseButtonTsFn>,
+  mouse_up: Listeners<MouseButtonTsFn>,
+  mouse_click: Listeners<MouseButtonTsFn>,
+  mouse_move: Listeners<MouseMoveTsFn>,
+  mouse_wheel: Listeners<WheelTsFn>,
+  tap_disabled: Listeners<TapDisabledTsFn>,
+  auto_stop: Listeners<AutoStopTsFn>,
+  modifier_change: Listeners<ModifierChangeTsFn>,
+  hotkeys: Listeners<Hotkey>,
+  heartbeat: Listeners<HeartbeatTsFn>,
+}
+
+// SAFETY: All fields hold ThreadsafeFunction<...>s, which is designed for
+// cross-thread use, alongside plain Send + Sync data. If a non-Send/Sync field
+// is ever added to this struct, these impls must be revisited — the compiler
+// will NOT catch the violation.
+unsafe impl Send for InputHookCallbacks {}
+unsafe impl Sync for InputHookCallbacks {}
+
+impl InputHookCallbacks {
+  fn new() -> Self {
+    Self {
+      key_down: Vec::new(),
+      key_up: Vec::new(),
+      mouse_down: Vec::new(),
+      mouse_up: Vec::new(),
+      mouse_click: Vec::new(),
+      mouse_move: Vec::new(),
+      mouse_wheel: Vec::new(),
+      tap_disabled: Vec::new(),
+      auto_stop: Vec::new(),
+      modifier_change: Vec::new(),
+      hotkeys: Vec::new(),
+      heartbeat: Vec::new(),
+    }
+  }
+
+  /// Compute the event mask from which callbacks are registered.
   fn compute_mask(&self) -> u32 {
     let mut mask = 0u32;
-    if self.key_down.is_some() {
+    if !self.key_down.is_empty() {
       mask |= 1 << 2;
     } // KeyPressed
-    if self.key_up.is_some() {
+    if !self.key_up.is_empty() {
       mask |= 1 << 3;
     } // KeyReleased
-    if self.mouse_down.is_some() {
+    if !self.mouse_down.is_empty() {
       mask |= 1 << 5;
     } // MousePressed
-    if self.mouse_up.is_some() {
+    if !self.mouse_up.is_empty() {
       mask |= 1 << 6;
     } // MouseReleased
-    if self.mouse_click.is_some() {
+    if !self.mouse_click.is_empty() {
       mask |= 1 << 7;
     } // MouseClicked
-    if self.mouse_move.is_some() {
+    if !self.mouse_move.is_empty() {
       mask |= (1 << 8) | (1 << 9);
     } // MouseMoved | MouseDragged
-    if self.mouse_wheel.is_some() {
+    if !self.mouse_wheel.is_empty() {
       mask |= 1 << 10;
     } // MouseWheel
     mask
   }
+
+  /// Remove the listener registered under `id`, wherever it is.
+  fn remove(&mut self, id: u32) -> bool {
+    fn remove_from<F>(listeners: &mut Listeners<F>, id: u32) -> bool {
+      let before = listeners.len();
+      listeners.retain(|(i, _)| *i != id);
+      listeners.len() != before
+    }
+    remove_from(&mut self.key_down, id)
+      || remove_from(&mut self.key_up, id)
+      || remove_from(&mut self.mouse_down, id)
+      || remove_from(&mut self.mouse_up, id)
+      || remove_from(&mut self.mouse_click, id)
+      || remove_from(&mut self.mouse_move, id)
+      || remove_from(&mut self.mouse_wheel, id)
+      || remove_from(&mut self.tap_disabled, id)
+      || remove_from(&mut self.auto_stop, id)
+      || remove_from(&mut self.modifier_change, id)
+      || remove_from(&mut self.hotkeys, id)
+      || remove_from(&mut self.heartbeat, id)
+  }
+}
+
+/// Default distance, in pixels, successive presses may drift and still count as a multi-click.
+const DEFAULT_CLICK_TOLERANCE_PX: f64 = 4.0;
+
+/// Fallback double-click interval when the OS doesn't report one.
+const DEFAULT_DOUBLE_CLICK_MS: u32 = 500;
+
+/// Stable per-button id used as a key in dispatch-side bookkeeping.
+fn button_id(button: Button) -> u32 {
+  ButtonJs::from(button) as u32
+}
+
+/// Stable per-key id, for the same purpose as `button_id`.
+fn key_id(key: Key) -> u32 {
+  KeyJs::from(key) as u32
+}
+
+/// Derives click counts (single/double/triple...) from consecutive presses.
+struct ClickTracker {
+  tolerance: f64,
+  interval_ms: f64,
+  last: Option<LastPress>,
+}
+
+#[derive(Clone, Copy)]
+struct LastPress {
+  button: u32,
+  x: f64,
+  y: f64,
+  time: f64,
+  count: u32,
+}
+
+impl ClickTracker {
+  fn new() -> Self {
+    Self {
+      tolerance: DEFAULT_CLICK_TOLERANCE_PX,
+      interval_ms: DEFAULT_DOUBLE_CLICK_MS as f64,
+      last: None,
+    }
+  }
+
+  /// Re-read the system double-click interval.
+  fn refresh_interval(&mut self) {
+    let ms = system_settings()
+      .ok()
+      .and_then(|s| s.double_click_time)
+      .unwrap_or(DEFAULT_DOUBLE_CLICK_MS);
+    self.interval_ms = ms as f64;
+  }
+
+  /// Record a press and return its click count.
+  fn press(&mut self, button: u32, x: f64, y: f64, time: f64) -> u32 {
+    let count = match self.last {
+      Some(last)
+        if last.button == button
+          && (time - last.time) * 1000.0 <= self.interval_ms
+          && (x - last.x).abs() <= self.tolerance
+          && (y - last.y).abs() <= self.tolerance =>
+      {
+        last.count + 1
+      }
+      _ => 1,
+    };
+    self.last = Some(LastPress {
+      button,
+      x,
+      y,
+      time,
+      count,
+    });
+    count
+  }
+
+  /// Click count of the latest press, for the matching release/click events.
+  fn count_for(&self, button: u32) -> u32 {
+    self
+      .last
+      .filter(|last| last.button == button)
+      .map_or(1, |last| last.count)
+  }
+}
+
+/// Version of the ring buffer layout below; bumped on any incompatible change.
+///
+/// Layout v1 (host byte order, i.e. little-endian on all supported targets):
+///
+/// Header, 4 × i32:
+/// - `[0]` records written so far (wrapping); `Atomics.wait` on this index
+/// - `[1]` layout version (`RING_LAYOUT_VERSION`)
+/// - `[2]` capacity in records
+/// - `[3]` record size in bytes (`RING_RECORD_BYTES`)
+///
+/// Record `n` lives at byte `RING_HEADER_BYTES + (n % capacity) * RING_RECORD_BYTES`:
+/// - `+0`  u32 event type (`eventTypeCode`)
+/// - `+4`  u32 key (`KeyJs` value) for keyboard events, `buttonCode` for mouse
+///   button events (255 when absent), `ScrollDirectionJs` for wheel events
+/// - `+8`  f64 time (seconds since the Unix epoch, regardless of `setTimeBase`)
+/// - `+16` f32 x, `+20` f32 y (0 for keyboard events)
+/// - `+24` u32 raw code for keyboard events, f32 delta for wheel events
+/// - `+28` u32 modifiers (`MODIFIER_*` bits)
+#[napi]
+pub const RING_LAYOUT_VERSION: u32 = 1;
+#[napi]
+pub const RING_HEADER_BYTES: u32 = 16;
+#[napi]
+pub const RING_RECORD_BYTES: u32 = 32;
+
+type RingNotifyTsFn = ThreadsafeFunction<u32, (), Vec<u32>, Status, false>;
+
+/// Caller-provided ring buffer that `InputHook` writes event records into.
+///
+/// Only the raw pointer crosses to the hook thread; the `Int32Array` itself
+/// stays in `InputHook::ring_buffer` on the JS thread.
+struct EventRing {
+  ptr: *mut i32,
+  capacity: u32,
+  written: u32,
+  /// Coalesced "new data" callback; the flag is set while a call is queued.
+  notify: Option<(RingNotifyTsFn, Arc<AtomicBool>)>,
+}
+
+// SAFETY: `ptr` points into the SharedArrayBuffer kept alive by
+// `InputHook::ring_buffer`, which clears `InputHookShared::ring` before
+// releasing the buffer. All writes go through that mutex, and JS only reads
+// records below the published write count.
+unsafe impl Send for EventRing {}
+
+impl EventRing {
+  fn push(&mut self, event: &InputEvent, modifiers: u32) {
+    let (code, x, y, extra) = match (event.keyboard, event.mouse, event.wheel) {
+      (Some(kb), _, _) => (key_id(kb.key), 0.0, 0.0, kb.raw_code),
+      (_, Some(m), _) => (m.button.map_or(255, |b| button_code(b.into())), m.x, m.y, 0),
+      (_, _, Some(w)) => (
+        ScrollDirectionJs::from(w.direction) as u32,
+        w.x,
+        w.y,
+        (w.delta as f32).to_bits(),
+      ),
+      _ => (0, 0.0, 0.0, 0),
+    };
+    let offset = RING_HEADER_BYTES as usize
+      + (self.written % self.capacity) as usize * RING_RECORD_BYTES as usize;
+    // SAFETY: `attach_ring_buffer` checked that `capacity` records fit after
+    // the header; unaligned writes make no assumption about the byte offset.
+    unsafe {
+      let record = (self.ptr as *mut u8).add(offset);
+      let type_code = event_type_bit(&event.event_type).trailing_zeros();
+      std::ptr::write_unaligned(record as *mut u32, type_code);
+      std::ptr::write_unaligned(record.add(4) as *mut u32, code);
+      std::ptr::write_unaligned(record.add(8) as *mut f64, event.time);
+      std::ptr::write_unaligned(record.add(16) as *mut f32, x as f32);
+      std::ptr::write_unaligned(record.add(20) as *mut f32, y as f32);
+      std::ptr::write_unaligned(record.add(24) as *mut u32, extra);
+      std::ptr::write_unaligned(record.add(28) as *mut u32, modifiers);
+    }
+    self.written = self.written.wrapping_add(1);
+    // SAFETY: the header slot is an aligned i32 inside the buffer; Release
+    // publishes the record before JS can observe the new count.
+    let count = unsafe { AtomicI32::from_ptr(self.ptr) };
+    count.store(self.written as i32, Ordering::Release);
+
+    if let Some((tsfn, pending)) = &self.notify {
+      if !pending.swap(true, Ordering::AcqRel)
+        && tsfn.call(self.written, ThreadsafeFunctionCallMode::NonBlocking) != Status::Ok
+      {
+        pending.store(false, Ordering::Release);
+      }
+    }
+  }
+}
+
+/// Delivery priority of an event type under `InputHook.setRateLimit`.
+#[napi]
+pub enum PriorityJs {
+  /// Shed first: only delivered while more than half the rate budget is left.
+  Low,
+  /// Delivered while the rate budget lasts (the default for every type).
+  Normal,
+  /// Always delivered, bypassing the rate limit.
+  High,
+}
+
+/// Token bucket behind `InputHook.setRateLimit` and `setRateLimitFor`,
+/// refilled continuously and holding at most one second's worth of events.
+struct RateLimiter {
+  /// Events per second (0 = unlimited).
+  rate: u32,
+  tokens: f64,
+  last: Instant,
+}
+
+impl RateLimiter {
+  fn new() -> Self {
+    RateLimiter {
+      rate: 0,
+      tokens: 0.0,
+      last: Instant::now(),
+    }
+  }
+
+  /// Change the rate (0 = unlimited) and start with a full bucket.
+  fn reset(&mut self, rate: u32) {
+    self.rate = rate;
+    self.tokens = rate as f64;
+    self.last = Instant::now();
+  }
+
+  /// Whether an event with `priority` (a `PriorityJs` value) may be delivered.
+  /// Only checks: `take` uses up the token once every limiter has agreed.
+  fn allows(&mut self, priority: u32) -> bool {
+    if self.rate == 0 || priority == PriorityJs::High as u32 {
+      return true;
+    }
+    let rate = self.rate as f64;
+    let now = Instant::now();
+    self.tokens = (self.tokens + now.duration_since(self.last).as_secs_f64() * rate).min(rate);
+    self.last = now;
+    let floor = if priority == PriorityJs::Low as u32 {
+      rate / 2.0
+    } else {
+      0.0
+    };
+    self.tokens - 1.0 >= floor
+  }
+
+  /// Use up the token for an event `allows` let through.
+  fn take(&mut self, priority: u32) {
+    if self.rate != 0 && priority != PriorityJs::High as u32 {
+      self.tokens -= 1.0;
+    }
+  }
+}
+
+/// Wall-clock milliseconds since the Unix epoch.
+fn now_ms() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|d| d.as_millis() as u64)
+    .unwrap_or(0)
+}
+
+/// How long (event seconds) a held non-modifier key may go without a press or
+/// autorepeat before its release is assumed missed (see `held_presses`).
+const STALE_PRESS_SECS: f64 = 10.0;
+
+/// How long a focused-window lookup is reused by dispatch before asking the OS again.
+const FOCUS_CACHE_MS: u64 = 100;
+
+/// A focused-window lookup and when it was made.
+struct FocusCache {
+  at: Option<Instant>,
+  window: Option<platform::FocusedWindow>,
+}
+
+/// The focused window as dispatch sees it, shared by all hooks.
+static DISPATCH_FOCUS: Mutex<FocusCache> = Mutex::new(FocusCache {
+  at: None,
+  window: None,
+});
+/// Set while a background refresh of `DISPATCH_FOCUS` is running.
+static FOCUS_REFRESHING: AtomicBool = AtomicBool::new(false);
+
+/// Look up the focused window now and cache it for dispatch.
+fn refresh_focused_window() {
+  let window = platform::focused_window();
+  *DISPATCH_FOCUS.lock().unwrap() = FocusCache {
+    at: Some(Instant::now()),
+    window,
+  };
+}
+
+/// Read the focused window as cached for dispatch. A lookup older than
+/// `FOCUS_CACHE_MS` is refreshed on a background thread and used meanwhile,
+/// so the hook thread never waits on the OS (which can block on a hung
+/// window).
+fn with_focused_window<T>(f: impl FnOnce(Option<&platform::FocusedWindow>) -> T) -> T {
+  let cache = DISPATCH_FOCUS.lock().unwrap();
+  let stale = cache
+    .at
+    .is_none_or(|at| at.elapsed() >= Duration::from_millis(FOCUS_CACHE_MS));
+  if stale && !FOCUS_REFRESHING.swap(true, Ordering::Relaxed) {
+    thread::spawn(|| {
+      refresh_focused_window();
+      FOCUS_REFRESHING.store(false, Ordering::Relaxed);
+    });
+  }
+  f(cache.window.as_ref())
+}
+
+/// Raw wheel delta per physical detent: Windows reports multiples of
+/// `WHEEL_DELTA` (120), macOS and X11 report lines or button clicks.
+#[cfg(target_os = "windows")]
+const WHEEL_DELTA_PER_NOTCH: f64 = 120.0;
+#[cfg(not(target_os = "windows"))]
+const WHEEL_DELTA_PER_NOTCH: f64 = 1.0;
+
+/// Bound on a coalesced gesture's `delta` and `rawDelta` (either sign), far
+/// beyond any real gesture, so the sums stay finite and sane.
+const MAX_WHEEL_DELTA: f64 = 1e6;
+
+/// A wheel gesture being accumulated while coalescing is on.
+#[derive(Clone, Copy)]
+struct PendingWheel {
+  x: f64,
+  y: f64,
+  direction: ScrollDirection,
+  delta: f64,
+  raw_delta: f64,
+  /// Timestamp of the first event in the gesture.
+  time: f64,
+  /// Sequence number of the last event in the gesture.
+  seq: f64,
+  modifiers: u32,
+  last: Instant,
+}
+
+impl From<PendingWheel> for WheelEventJs {
+  fn from(p: PendingWheel) -> Self {
+    WheelEventJs {
+      x: p.x,
+      y: p.y,
+      direction: p.direction.into(),
+      delta: p.delta,
+      raw_delta: p.raw_delta,
+      time: p.time,
+      seq: p.seq,
+      modifiers: p.modifiers,
+      device_id: None,
+      raw: None,
+    }
+  }
+}
+
+/// State shared between an `InputHook` handle and its native hook thread.
+struct InputHookShared {
+  callbacks: Mutex<InputHookCallbacks>,
+  /// Id handed to the next `on*` listener (and `simulateKeyTapConfirmed` watch).
+  next_listener_id: AtomicU32,
+  mask: AtomicU32,
+  /// Cleared on HookDisabled, so `isRunning` can't report a dead tap as live.
+  tap_enabled: AtomicBool,
+  /// Set while `stop()` runs, so our own HookDisabled isn't reported as a failure.
+  stopping: AtomicBool,
+  /// Only deliver input while this process owns the focused window (0 = off).
+  source_pid: AtomicU32,
+  /// Attach the focused window to key and button payloads (see `setIncludeWindowContext`).
+  include_window_context: AtomicBool,
+  /// Attach native event fields to payloads (see `setIncludeRaw`).
+  include_raw: AtomicBool,
+  /// Set once HookEnabled is observed after `start()`; `startAsync` waits on it.
+  ready: Mutex<bool>,
+  ready_cv: Condvar,
+  /// Pending `simulateKeyTapConfirmed` calls: watch id -> (key id, seen).
+  key_watch: Mutex<HashMap<u32, (u32, bool)>>,
+  key_watch_cv: Condvar,
+  clicks: Mutex<ClickTracker>,
+  /// Merge same-direction wheel events closer than this many ms (0 = off).
+  wheel_gap_ms: AtomicU32,
+  wheel_pending: Mutex<Option<PendingWheel>>,
+  /// Whether the thread flushing idle wheel gestures is running.
+  wheel_flusher: AtomicBool,
+  wheel_flusher_thread: Mutex<Option<thread::JoinHandle<()>>>,
+  /// Held modifiers as `modifier_key_bit` bits, tracked from key events.
+  modifiers: AtomicU32,
+  /// As `modifiers`, but only from the native hook, never injected events
+  /// (see `track_modifier`).
+  held_modifiers: ModifierTracker,
+  /// Set via `setFilter`; events it rejects are not dispatched.
+  filter: Mutex<Option<EventFilter>>,
+  /// Heartbeat: wall-clock ms of the last event dispatched (0 = none yet).
+  last_event_ms: AtomicU64,
+  /// Wall-clock ms the native hook thread last ran our callback, or when the
+  /// hook started. Only the hook thread updates it, unlike `last_event_ms`
+  /// which injected events also touch.
+  last_dispatch_ms: AtomicU64,
+  /// Events the JS callback queue rejected.
+  dropped: AtomicU32,
+  /// Auto-stop limits applied on `start()` (0 = off).
+  auto_stop_ms: AtomicU32,
+  auto_stop_events: AtomicU32,
+  /// Heartbeat interval (0 = off), and a counter bumped whenever it is set so
+  /// the previous heartbeat thread exits.
+  heartbeat_ms: AtomicU32,
+  heartbeat_gen: AtomicU32,
+  heartbeat_thread: Mutex<Option<thread::JoinHandle<()>>>,
+  /// Raised by `shutdown()` to end the heartbeat and wheel flusher threads.
+  thread_stop: ThreadStop,
+  /// Events seen this session, counted toward `auto_stop_events`.
+  event_count: AtomicU32,
+  /// Report right-side modifiers as their left-side key (see `setFoldModifierSides`).
+  fold_modifier_sides: AtomicBool,
+  /// Round mouse coordinates in payloads (see `setIntegerCoordinates`).
+  integer_coordinates: AtomicBool,
+  /// Report wheel `delta` in notches (see `setNormalizeWheelDelta`).
+  normalize_wheel: AtomicBool,
+  /// Queue callbacks with `CallModeJs.Blocking` (see `setCallMode`).
+  blocking: AtomicBool,
+  /// Skip moves to the last forwarded position (see `setDedupMoves`).
+  dedup_moves: AtomicBool,
+  /// Last position delivered to `onMouseMove`, as `f64` bits (NaN = none).
+  last_move: [AtomicU64; 2],
+  /// `PriorityJs` per event type, indexed by `event_type_bit` position.
+  priorities: [AtomicU32; 11],
+  limiter: Mutex<RateLimiter>,
+  /// Per-type limits (see `setRateLimitFor`), indexed like `priorities`.
+  type_limiters: Mutex<[RateLimiter; 11]>,
+  /// Events queued to JS callbacks but not yet picked up by the JS thread.
+  /// Shared with the callbacks themselves (see `tracked_callback`).
+  backlog: Arc<AtomicU32>,
+  /// Backlog at which `slow_consumer` fires (0 = off).
+  slow_threshold: AtomicU32,
+  /// Cleared once `slow_consumer` fires, re-armed when the backlog halves.
+  slow_armed: AtomicBool,
+  slow_consumer: Mutex<Option<SlowConsumerTsFn>>,
+  /// Set while a ring buffer is attached, so dispatch skips the lock otherwise.
+  ring_attached: AtomicBool,
+  ring: Mutex<Option<EventRing>>,
+  /// Last `trail_capacity` mouse positions as (x, y, Unix seconds), oldest first.
+  trail_capacity: AtomicU32,
+  trail: Mutex<VecDeque<(f64, f64, f64)>>,
+  /// Held keys/buttons as `(press_id, press time, last seen)` in event
+  /// seconds; see `held_presses` for how missed releases are pruned.
+  press_times: Mutex<Vec<(u32, f64, f64)>>,
+  /// Window for dropping duplicate key presses (0 = off, see `setDedupKeysWindowMs`).
+  dedup_keys_ms: AtomicU32,
+  /// Last accepted press time (event seconds) per key id, for `dedup_keys_ms`.
+  last_key_presses: Mutex<Vec<(u32, f64)>>,
+  /// Key id of each dropped duplicate press whose release is still to come.
+  dropped_key_presses: Mutex<Vec<u32>>,
+  /// Events seen per type, indexed by `event_type_bit` position.
+  counts: [AtomicU64; 11],
+  /// Next event sequence number; never reset, so it increases across sessions.
+  seq: AtomicU64,
+  /// Bumped on every `start()` and auto-stop, so a stale auto-stop timer can't
+  /// stop a later session and the two limits can't both fire.
+  session: AtomicU32,
+}
+
+impl InputHookShared {
+  fn new() -> Self {
+    Self {
+      callbacks: Mutex::new(InputHookCallbacks::new()),
+      next_listener_id: AtomicU32::new(1),
+      mask: AtomicU32::new(0),
+      tap_enabled: AtomicBool::new(false),
+      stopping: AtomicBool::new(false),
+      source_pid: AtomicU32::new(0),
+      include_window_context: AtomicBool::new(false),
+      include_raw: AtomicBool::new(false),
+      ready: Mutex::new(false),
+      ready_cv: Condvar::new(),
+      key_watch: Mutex::new(HashMap::new()),
+      key_watch_cv: Condvar::new(),
+      clicks: Mutex::new(ClickTracker::new()),
+      wheel_gap_ms: AtomicU32::new(0),
+      wheel_pending: Mutex::new(None),
+      wheel_flusher: AtomicBool::new(false),
+      wheel_flusher_thread: Mutex::new(None),
+      modifiers: AtomicU32::new(0),
+      held_modifiers: Arc::new(AtomicU32::new(0)),
+      filter: Mutex::new(None),
+      last_event_ms: AtomicU64::new(0),
+      last_dispatch_ms: AtomicU64::new(0),
+      dropped: AtomicU32::new(0),
+      auto_stop_ms: AtomicU32::new(0),
+      auto_stop_events: AtomicU32::new(0),
+      heartbeat_ms: AtomicU32::new(0),
+      heartbeat_gen: AtomicU32::new(0),
+      heartbeat_thread: Mutex::new(None),
+      thread_stop: Default::default(),
+      event_count: AtomicU32::new(0),
+      session: AtomicU32::new(0),
+      counts: Default::default(),
+      seq: AtomicU64::new(0),
+      press_times: Mutex::new(Vec::new()),
+      dedup_keys_ms: AtomicU32::new(0),
+      last_key_presses: Mutex::new(Vec::new()),
+      dropped_key_presses: Mutex::new(Vec::new()),
+      fold_modifier_sides: AtomicBool::new(false),
+      integer_coordinates: AtomicBool::new(false),
+      normalize_wheel: AtomicBool::new(false),
+      blocking: AtomicBool::new(false),
+      dedup_moves: AtomicBool::new(false),
+      last_move: std::array::from_fn(|_| AtomicU64::new(f64::NAN.to_bits())),
+      priorities: std::array::from_fn(|_| AtomicU32::new(PriorityJs::Normal as u32)),
+      limiter: Mutex::new(RateLimiter::new()),
+      type_limiters: Mutex::new(std::array::from_fn(|_| RateLimiter::new())),
+      backlog: Arc::new(AtomicU32::new(0)),
+      slow_threshold: AtomicU32::new(0),
+      slow_armed: AtomicBool::new(true),
+      slow_consumer: Mutex::new(None),
+      ring_attached: AtomicBool::new(false),
+      ring: Mutex::new(None),
+      trail_capacity: AtomicU32::new(0),
+      trail: Mutex::new(VecDeque::new()),
+    }
+  }
+
+  /// Process id of the focused window.
+  fn focused_pid(&self) -> Option<u32> {
+    with_focused_window(|w| w.and_then(|w| w.process_id))
+  }
+
+  /// `(windowTitle, processName)` for key and button payloads; both `None`
+  /// unless `include_window_context` is set.
+  fn window_context(&self) -> (Option<String>, Option<String>) {
+    if !self.include_window_context.load(Ordering::Relaxed) {
+      return (None, None);
+    }
+    with_focused_window(|w| {
+      w.map_or((None, None), |w| {
+        (Some(w.title.clone()), w.process_name.clone())
+      })
+    })
+  }
+
+  /// The `raw` payload field for `event`, as the hook reported it (before
+  /// side folding, coordinate rounding and wheel normalization); `None`
+  /// unless `include_raw` is set.
+  fn raw_data(&self, event: &InputEvent) -> Option<RawEventDataJs> {
+    if !self.include_raw.load(Ordering::Relaxed) {
+      return None;
+    }
+    let mut fields = HashMap::new();
+    if let Some(kb) = event.keyboard {
+      fields.insert("rawCode".to_string(), kb.raw_code as f64);
+    }
+    if let Some(m) = event.mouse {
+      fields.insert("x".to_string(), m.x);
+      fields.insert("y".to_string(), m.y);
+    }
+    if let Some(w) = event.wheel {
+      fields.insert("x".to_string(), w.x);
+      fields.insert("y".to_string(), w.y);
+      fields.insert("rawDelta".to_string(), w.raw_delta);
+    }
+    Some(RawEventDataJs {
+      platform: std::env::consts::OS.to_string(),
+      fields,
+    })
+  }
+
+  /// `deliver`, counting events the JS queue rejected for `healthcheck` and
+  /// tracking the backlog for `onSlowConsumer`.
+  fn deliver<T: 'static + ToNapiValue>(&self, tsfn: &EventCallback<T>, data: T) {
+    if !deliver(tsfn, data, self.blocking.load(Ordering::Relaxed)) {
+      self.dropped.fetch_add(1, Ordering::Relaxed);
+      return;
+    }
+    let backlog = self.backlog.fetch_add(1, Ordering::Relaxed) + 1;
+    let threshold = self.slow_threshold.load(Ordering::Relaxed);
+    if threshold == 0 {
+      return;
+    }
+    if backlog < threshold {
+      if backlog <= threshold / 2 {
+        self.slow_armed.store(true, Ordering::Relaxed);
+      }
+      return;
+    }
+    if self.slow_armed.swap(false, Ordering::Relaxed) {
+      log_event("warn", || {
+        format!("InputHook consumer is slow: {} events queued", backlog)
+      });
+      if let Some(ref tsfn) = *self.slow_consumer.lock().unwrap() {
+        let data = SlowConsumerEventJs {
+          backlog,
+          time: js_time(now_secs()),
+        };
+        deliver(tsfn, data, false);
+      }
+    }
+  }
+
+  /// `deliver` to every listener, building a payload for each (napi payloads
+  /// are not `Clone`).
+  fn emit<T: 'static + ToNapiValue>(
+    &self,
+    listeners: &Listeners<EventCallback<T>>,
+    make: impl Fn() -> T,
+  ) {
+    for (_, tsfn) in listeners {
+      self.deliver(tsfn, make());
+    }
+  }
+
+  /// End the heartbeat and wheel flusher threads and wait for them to exit.
+  /// They can be started again afterwards.
+  fn join_threads(&self) {
+    let (raised, cv) = &*self.thread_stop;
+    *raised.lock().unwrap() = true;
+    cv.notify_all();
+    let handles = [
+      self.heartbeat_thread.lock().unwrap().take(),
+      self.wheel_flusher_thread.lock().unwrap().take(),
+    ];
+    for handle in handles.into_iter().flatten() {
+      let _ = handle.join();
+    }
+    *raised.lock().unwrap() = false;
+  }
+
+  /// Deliver the pending wheel gesture if it has been idle for `min_idle`.
+  fn flush_wheel(&self, min_idle: Duration) {
+    let pending = {
+      let mut pending = self.wheel_pending.lock().unwrap();
+      match *pending {
+        Some(p) if p.last.elapsed() >= min_idle => pending.take(),
+        _ => None,
+      }
+    };
+    if let Some(p) = pending {
+      let cbs = self.callbacks.lock().unwrap();
+      self.emit(&cbs.mouse_wheel, || WheelEventJs::from(p));
+    }
+  }
+
+  /// Fold a wheel event into the pending gesture. Returns the previous gesture
+  /// when this event ends it (direction changed or the gap elapsed).
+  fn coalesce_wheel(
+    &self,
+    w: WheelInput,
+    time: f64,
+    seq: f64,
+    modifiers: u32,
+    gap: Duration,
+  ) -> Option<PendingWheel> {
+    let mut pending = self.wheel_pending.lock().unwrap();
+    if let Some(p) = pending.as_mut() {
+      let same_direction =
+        ScrollDirectionJs::from(p.direction) as u32 == ScrollDirectionJs::from(w.direction) as u32;
+      if same_direction && p.last.elapsed() < gap {
+        p.x = w.x;
+        p.y = w.y;
+        p.delta = (p.delta + w.delta).clamp(-MAX_WHEEL_DELTA, MAX_WHEEL_DELTA);
+        p.raw_delta = (p.raw_delta + w.raw_delta).clamp(-MAX_WHEEL_DELTA, MAX_WHEEL_DELTA);
+        p.seq = seq;
+        p.modifiers = modifiers;
+        p.last = Instant::now();
+        return None;
+      }
+    }
+    pending.replace(PendingWheel {
+      x: w.x,
+      y: w.y,
+      direction: w.direction,
+      delta: w.delta,
+      raw_delta: w.raw_delta,
+      time,
+      seq,
+      modifiers,
+      last: Instant::now(),
+    })
+  }
+
+  /// Id for `press_times`: key ids as-is, button ids offset past every key id.
+  fn press_id(event: &InputEvent) -> Option<u32> {
+    match (event.keyboard, event.mouse) {
+      (Some(kb), _) => Some(key_id(kb.key)),
+      (None, Some(m)) => Some(0x1_0000 | button_id(m.button.unwrap_or(Button::Left))),
+      _ => None,
+    }
+  }
+
+  /// `press_times`, without non-modifier keys not seen for
+  /// `STALE_PRESS_SECS` before `time`. A held key autorepeats far more often
+  /// than that, so such an entry is a release the hook missed (e.g. one
+  /// swallowed by a secure prompt); left in place it would turn the key's
+  /// next press into an autorepeat and block every hotkey chord. Buttons and
+  /// modifiers don't autorepeat everywhere, so they are kept.
+  fn held_presses(&self, time: f64) -> std::sync::MutexGuard<'_, Vec<(u32, f64, f64)>> {
+    let mut times = self.press_times.lock().unwrap();
+    times.retain(|&(id, _, last_seen)| {
+      let modifier =
+        id >= 0x1_0000 || key_from_i32(id as i32).is_some_and(|k| Key::from(k).is_modifier());
+      modifier || time - last_seen < STALE_PRESS_SECS
+    });
+    times
+  }
+
+  /// Record press times and, for releases, return `(heldMs, synthetic)`.
+  fn track_press(&self, event: &InputEvent) -> Option<(f64, bool)> {
+    let id = Self::press_id(event)?;
+    let mut times = self.held_presses(event.time);
+    match event.event_type {
+      // Auto-repeat presses keep the original press time
+      EventType::KeyPressed | EventType::MousePressed => {
+        match times.iter_mut().find(|(i, _, _)| *i == id) {
+          Some((_, _, last_seen)) => *last_seen = event.time,
+          None => times.push((id, event.time, event.time)),
+        }
+        None
+      }
+      EventType::KeyReleased | EventType::MouseReleased => {
+        match times.iter().position(|&(i, _, _)| i == id) {
+          Some(pos) => {
+            let (_, pressed, _) = times.swap_remove(pos);
+            Some((((event.time - pressed) * 1000.0).max(0.0), false))
+          }
+          None => Some((0.0, true)),
+        }
+      }
+      _ => None,
+    }
+  }
+
+  /// Whether a press of key `id` at `time` (event seconds) comes within
+  /// `dedup_keys_ms` of the last accepted press of the same key. Accepted
+  /// presses are recorded; dropped ones are not, so a stream of duplicates
+  /// can't extend the window; `is_duplicate_release` swallows their releases.
+  /// A press of a key that is still held is autorepeat and always passes, or
+  /// its single release would be swallowed and the key left stuck down.
+  fn is_duplicate_press(&self, id: u32, time: f64) -> bool {
+    let ms = self.dedup_keys_ms.load(Ordering::Relaxed);
+    if ms == 0 || self.held_presses(time).iter().any(|&(i, _, _)| i == id) {
+      return false;
+    }
+    let window = ms as f64 / 1000.0;
+    let mut last = self.last_key_presses.lock().unwrap();
+    match last.iter_mut().find(|(i, _)| *i == id) {
+      Some((_, t)) if (time - *t).abs() < window => {
+        // At most one release is swallowed per key, however many presses drop
+        let mut dropped = self.dropped_key_presses.lock().unwrap();
+        if !dropped.contains(&id) {
+          dropped.push(id);
+        }
+        true
+      }
+      Some((_, t)) => {
+        *t = time;
+        false
+      }
+      None => {
+        last.push((id, time));
+        false
+      }
+    }
+  }
+
+  /// Whether a release of key `id` pairs with a dropped duplicate press, so
+  /// the key isn't reported released while the accepted press is still held.
+  fn is_duplicate_release(&self, id: u32) -> bool {
+    let mut dropped = self.dropped_key_presses.lock().unwrap();
+    match dropped.iter().position(|&i| i == id) {
+      Some(index) => {
+        dropped.swap_remove(index);
+        true
+      }
+      None => false,
+    }
+  }
+
+  /// Fire the hotkeys whose chord is exactly what is held now that `key` went
+  /// down, with `key` as the trigger. Runs before `track_press`, so a key
+  /// already in `press_times` is autorepeat and never triggers.
+  fn match_hotkeys(&self, key: Key, time: f64) {
+    let id = key_id(key);
+    let chord = {
+      let times = self.held_presses(time);
+      if times.iter().any(|&(i, _, _)| i == id) {
+        return;
+      }
+      // Ids below 0x1_0000 are keys (see `press_id`)
+      hotkey_chord(
+        times
+          .iter()
+          .map(|&(i, _, _)| i)
+          .filter(|&i| i < 0x1_0000)
+          .chain([id]),
+      )
+    };
+    let trigger = hotkey_key_id(id);
+    let now = Instant::now();
+    let mut cbs = self.callbacks.lock().unwrap();
+    for (hotkey_id, hotkey) in cbs.hotkeys.iter_mut() {
+      if hotkey.trigger != trigger || hotkey.chord != chord {
+        continue;
+      }
+      if hotkey
+        .last_fired
+        .is_some_and(|last| now.duration_since(last) < hotkey.cooldown)
+      {
+        continue;
+      }
+      hotkey.last_fired = Some(now);
+      self.deliver(
+        &hotkey.callback,
+        HotkeyEventJs {
+          id: *hotkey_id,
+          keys: hotkey.keys.iter().map(|&k| k.into()).collect(),
+          time: js_time(time),
+        },
+      );
+    }
+  }
+
+  /// Store the mask derived from the currently registered callbacks.
+  fn update_mask(&self, cbs: &InputHookCallbacks) {
+    self.mask.store(cbs.compute_mask(), Ordering::Relaxed);
+  }
+
+  /// Route one event to its typed callbacks. This is the whole dispatch path for
+  /// both the native hook and `injectEventForTest`.
+  fn dispatch(&self, event: &InputEvent) {
+    self.last_event_ms.store(now_ms(), Ordering::Relaxed);
+    let seq = self.seq.fetch_add(1, Ordering::Relaxed) as f64;
+    let index = event_type_bit(&event.event_type).trailing_zeros() as usize;
+    self.counts[index].fetch_add(1, Ordering::Relaxed);
+
+    // Hook lifecycle is tracked regardless of the mask
+    match event.event_type {
+      EventType::HookEnabled => {
+        self.tap_enabled.store(true, Ordering::Relaxed);
+        *self.ready.lock().unwrap() = true;
+        self.ready_cv.notify_all();
+      }
+      EventType::HookDisabled => {
+        self.tap_enabled.store(false, Ordering::Relaxed);
+        self.modifiers.store(0, Ordering::Relaxed);
+        if !self.stopping.load(Ordering::Relaxed) {
+          log_event("warn", || "InputHook disabled by the OS".to_string());
+          let cbs = self.callbacks.lock().unwrap();
+          let time = js_time(event.time);
+          self.emit(&cbs.tap_disabled, || TapDisabledEventJs { time });
+        }
+      }
+      _ => {}
+    }
+
+    // Before any key tracking, so a dropped duplicate leaves no trace
+    match (event.event_type, event.keyboard) {
+      (EventType::KeyPressed, Some(kb)) if self.is_duplicate_press(key_id(kb.key), event.time) => {
+        return
+      }
+      (EventType::KeyReleased, Some(kb)) if self.is_duplicate_release(key_id(kb.key)) => return,
+      _ => {}
+    }
+
+    if let Some(kb) = event.keyboard {
+      if matches!(event.event_type, EventType::KeyPressed) {
+        let mut watch = self.key_watch.lock().unwrap();
+        if !watch.is_empty() {
+          let id = key_id(kb.key);
+          for (key, seen) in watch.values_mut() {
+            *seen |= *key == id;
+          }
+          self.key_watch_cv.notify_all();
+        }
+      }
+      let bit = modifier_key_bit(kb.key);
+      let (before, after) = match event.event_type {
+        EventType::KeyPressed => {
+          let before = self.modifiers.fetch_or(bit, Ordering::Relaxed);
+          (before, before | bit)
+        }
+        EventType::KeyReleased => {
+          let before = self.modifiers.fetch_and(!bit, Ordering::Relaxed);
+          (before, before & !bit)
+        }
+        _ => (0, 0),
+      };
+      // Collapse sides so autorepeat and the second side are not transitions
+      let (before, after) = ((before | before >> 4) & 0xF, (after | after >> 4) & 0xF);
+      if before != after {
+        let cbs = self.callbacks.lock().unwrap();
+        let changed = match before ^ after {
+          MODIFIER_SHIFT => "shift",
+          MODIFIER_CTRL => "ctrl",
+          MODIFIER_ALT => "alt",
+          _ => "meta",
+        };
+        self.emit(&cbs.modifier_change, || ModifierChangeEventJs {
+          modifiers: after.into(),
+          changed: changed.to_string(),
+          pressed: after & (before ^ after) != 0,
+          time: js_time(event.time),
+        });
+      }
+    }
+
+    // Pair presses with releases even when presses are masked out
+    if let (EventType::KeyPressed, Some(kb)) = (event.event_type, event.keyboard) {
+      self.match_hotkeys(kb.key, event.time);
+    }
+    let hold = self.track_press(event);
+
+    // Count clicks even when presses are masked out, so onClick alone gets counts
+    if let (EventType::MousePressed, Some(m)) = (event.event_type, event.mouse) {
+      let button = button_id(m.button.unwrap_or(Button::Left));
+      let mut clicks = self.clicks.lock().unwrap();
+      clicks.press(button, m.x, m.y, event.time);
+    }
+
+    // Adjust after modifier, press and click tracking, which need raw values
+    let raw = self.raw_data(event);
+    let mut event = *event;
+    if self.fold_modifier_sides.load(Ordering::Relaxed) {
+      if let Some(kb) = event.keyboard.as_mut() {
+        let opts = NormalizeKeyOptionsJs {
+          fold_modifier_sides: Some(true),
+          ..Default::default()
+        };
+        kb.key = normalize(kb.key.into(), &opts).into();
+      }
+    }
+    if self.integer_coordinates.load(Ordering::Relaxed) {
+      if let Some(m) = event.mouse.as_mut() {
+        m.x = m.x.round_ties_even();
+        m.y = m.y.round_ties_even();
+      }
+      if let Some(w) = event.wheel.as_mut() {
+        w.x = w.x.round_ties_even();
+        w.y = w.y.round_ties_even();
+      }
+    }
+    if self.normalize_wheel.load(Ordering::Relaxed) {
+      if let Some(w) = event.wheel.as_mut() {
+        w.delta = w.raw_delta / WHEEL_DELTA_PER_NOTCH;
+      }
+    }
+    let event = &event;
+
+    // A malformed native delta would poison coalesced gestures and consumers
+    if event
+      .wheel
+      .is_some_and(|w| !w.delta.is_finite() || !w.raw_delta.is_finite())
+    {
+      log_event("warn", || {
+        "Ignored wheel event with non-finite delta".to_string()
+      });
+      return;
+    }
+
+    // Recorded regardless of the mask, so no move listener is needed
+    if let (EventType::MouseMoved | EventType::MouseDragged, Some(m)) =
+      (event.event_type, event.mouse)
+    {
+      let capacity = self.trail_capacity.load(Ordering::Relaxed) as usize;
+      if capacity > 0 {
+        let mut trail = self.trail.lock().unwrap();
+        if trail.len() >= capacity {
+          trail.pop_front();
+        }
+        trail.push_back((m.x, m.y, event.time));
+      }
+    }
+
+    // Check the mask BEFORE acquiring the lock
+    let bit = event_type_bit(&event.event_type);
+    let wanted = self.mask.load(Ordering::Relaxed) & bit != 0;
+    let to_ring = self.ring_attached.load(Ordering::Relaxed)
+      && !matches!(
+        event.event_type,
+        EventType::HookEnabled | EventType::HookDisabled
+      );
+    if !wanted && !to_ring {
+      return;
+    }
+
+    // Events pass when the focused process can't be determined (see setSourcePidFilter)
+    let source_pid = self.source_pid.load(Ordering::Relaxed);
+    if source_pid != 0 && self.focused_pid().is_some_and(|pid| pid != source_pid) {
+      return;
+    }
+
+    if let Some(filter) = &*self.filter.lock().unwrap() {
+      if !filter.matches(event) {
+        return;
+      }
+    }
+
+    let time = js_time(event.time);
+    let held = self.modifiers.load(Ordering::Relaxed);
+    let modifiers = (held | held >> 4) & 0xF;
+
+    if to_ring {
+      if let Some(ring) = self.ring.lock().unwrap().as_mut() {
+        ring.push(event, modifiers);
+      }
+    }
+    if !wanted {
+      return;
+    }
+
+    // Charged only for events about to be delivered, after every drop check,
+    // so deduplicated moves and merged wheel ticks don't use up the budget
+    // Both buckets are checked before either is charged, so an event the
+    // shared limit rejects doesn't also use up its type's budget
+    let admit = || {
+      let priority = self.priorities[index].load(Ordering::Relaxed);
+      let normal = PriorityJs::Normal as u32;
+      let mut type_limiters = self.type_limiters.lock().unwrap();
+      let mut limiter = self.limiter.lock().unwrap();
+      if !type_limiters[index].allows(normal) || !limiter.allows(priority) {
+        return false;
+      }
+      type_limiters[index].take(normal);
+      limiter.take(priority);
+      true
+    };
+
+    let cbs = self.callbacks.lock().unwrap();
+
+    match event.event_type {
+      EventType::KeyPressed => {
+        if let (Some(kb), true) = (event.keyboard, admit()) {
+          let (window_title, process_name) = self.window_context();
+          self.emit(&cbs.key_down, || KeyboardEventJs {
+            key: kb.key.into(),
+            raw_code: kb.raw_code,
+            time,
+            seq,
+            modifiers,
+            held_ms: None,
+            synthetic: None,
+            window_title: window_title.clone(),
+            process_name: process_name.clone(),
+            device_id: None,
+            raw: raw.clone(),
+          });
+        }
+      }
+      EventType::KeyReleased => {
+        if let (Some(kb), true) = (event.keyboard, admit()) {
+          let (window_title, process_name) = self.window_context();
+          self.emit(&cbs.key_up, || KeyboardEventJs {
+            key: kb.key.into(),
+            raw_code: kb.raw_code,
+            time,
+            seq,
+            modifiers,
+            held_ms: hold.map(|(ms, _)| ms),
+            synthetic: hold.map(|(_, synthetic)| synthetic),
+            window_title: window_title.clone(),
+            process_name: process_name.clone(),
+            device_id: None,
+            raw: raw.clone(),
+          });
+        }
+      }
+      EventType::MousePressed | EventType::MouseReleased | EventType::MouseClicked => {
+        let listeners = match event.event_type {
+          EventType::MousePressed => &cbs.mouse_down,
+          EventType::MouseReleased => &cbs.mouse_up,
+          _ => &cbs.mouse_click,
+        };
+        if let (false, Some(m)) = (listeners.is_empty(), event.mouse) {
+          if !admit() {
+            return;
+          }
+          let button = m.button.unwrap_or(Button::Left);
+          let click_count = self.clicks.lock().unwrap().count_for(button_id(button));
+          let (window_title, process_name) = self.window_context();
+          self.emit(listeners, || MouseButtonEventJs {
+            x: m.x,
+            y: m.y,
+            button: button.into(),
+            time,
+            seq,
+            click_count,
+            modifiers,
+            held_ms: hold.map(|(ms, _)| ms),
+            synthetic: hold.map(|(_, synthetic)| synthetic),
+            window_title: window_title.clone(),
+            process_name: process_name.clone(),
+            device_id: None,
+            raw: raw.clone(),
+          });
+        }
+      }
+      EventType::MouseMoved | EventType::MouseDragged => {
+        if let Some(m) = event.mouse {
+          if self.dedup_moves.load(Ordering::Relaxed) {
+            let [last_x, last_y] = &self.last_move;
+            let last = (
+              f64::from_bits(last_x.swap(m.x.to_bits(), Ordering::Relaxed)),
+              f64::from_bits(last_y.swap(m.y.to_bits(), Ordering::Relaxed)),
+            );
+            if last == (m.x, m.y) {
+              return;
+            }
+          }
+          if !admit() {
+            return;
+          }
+          self.emit(&cbs.mouse_move, || MouseMoveEventJs {
+            x: m.x,
+            y: m.y,
+            time,
+            seq,
+            modifiers,
+            device_id: None,
+            raw: raw.clone(),
+          });
+        }
+      }
+      EventType::MouseWheel => {
+        if let (false, Some(w)) = (cbs.mouse_wheel.is_empty(), event.wheel) {
+          let gap = self.wheel_gap_ms.load(Ordering::Relaxed);
+          if gap > 0 {
+            let gap = Duration::from_millis(gap as u64);
+            let done = self.coalesce_wheel(w, time, seq, modifiers, gap);
+            if let (Some(done), true) = (done, admit()) {
+              self.emit(&cbs.mouse_wheel, || WheelEventJs {
+                raw: raw.clone(),
+                ..WheelEventJs::from(done)
+              });
+            }
+            return;
+          }
+          if !admit() {
+            return;
+          }
+          self.emit(&cbs.mouse_wheel, || WheelEventJs {
+            x: w.x,
+            y: w.y,
+            direction: w.direction.into(),
+            delta: w.delta,
+            raw_delta: w.raw_delta,
+            time,
+            seq,
+            modifiers,
+            device_id: None,
+            raw: raw.clone(),
+          });
+        }
+      }
+      _ => {} // HookEnabled, HookDisabled, KeyTyped — ignored
+    }
+  }
+}
+
+/// EventEmitter-style input hook with per-event-type callbacks.
+///
+/// Unlike `startListen()` which sends all events through a single callback,
+/// `InputHook` dispatches events to typed callbacks registered via `onKeyDown()`,
+/// `onMouseMove()`, etc. Only registered event types cross the NAPI boundary —
+/// the event mask is computed automatically from which callbacks are set.
+///
+/// ```js
+/// const hook = new InputHook();
+/// hook.onKeyDown((data) => console.log("key:", data.key, data.rawCode));
+/// hook.onMouseMove((data) => console.log("mouse:", data.x, data.y));
+/// hook.start();
+/// // ... later:
+/// hook.stop();
+/// ```
+#[napi]
+pub struct InputHook {
+  hook: Arc<Mutex<Option<Hook>>>,
+  shared: Arc<InputHookShared>,
+  /// The JS buffer behind `shared.ring`, owned here so it is only ever
+  /// dropped on the JS thread.
+  ring_buffer: Option<Int32Array>,
+}
+
+impl Default for InputHook {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Drop for InputHook {
+  fn drop(&mut self) {
+    // A running hook keeps `shared` alive past finalization; stop it writing
+    // before `ring_buffer` goes.
+    if self.ring_buffer.is_some() {
+      self.detach_ring_buffer();
+    }
+    // Nothing can stop it any more, so release as `stop()` would have
+    if self.hook.lock().unwrap().is_some() {
+      let _ = release_held_input_on_stop();
+    }
+  }
+}
+
+#[napi]
+impl InputHook {
+  #[napi(constructor)]
+  pub fn new() -> Self {
+    let hook = Arc::new(Mutex::new(None));
+    let mut shared = InputHookShared::new();
+    shared.held_modifiers = register_modifier_tracker(&hook);
+    Self {
+      hook,
+      shared: Arc::new(shared),
+      ring_buffer: None,
+    }
+  }
+
+  // ─── Per-event-type callback registration ──────────────────────────
+  //
+  // Each `on*` call adds a listener (earlier ones keep firing) and returns an
+  // id for `removeListener`; the matching `off*` removes all of them.
+
+  #[napi]
+  pub fn on_key_down(
+    &self,
+    #[napi(ts_arg_type = "(data: KeyboardEventJs) => void")] callback: Function<(), ()>,
+  ) -> Result<u32> {
+    let tsfn = tracked_callback::<KeyboardEventJs>(&self.shared.backlog, callback)?;
+    let id = self.shared.next_listener_id.fetch_add(1, Ordering::Relaxed);
+    let mut cbs = self.shared.callbacks.lock().unwrap();
+    cbs.key_down.push((id, tsfn));
+    self.shared.update_mask(&cbs);
+    Ok(id)
+  }
+
+  #[napi]
+  pub fn on_key_up(
+    &self,
+    #[napi(ts_arg_type = "(data: KeyboardEventJs) => void")] callback: Function<(), ()>,
+  ) -> Result<u32> {
+    let tsfn = tracked_callback::<KeyboardEventJs>(&self.shared.backlog, callback)?;
+    let id = self.shared.next_listener_id.fetch_add(1, Ordering::Relaxed);
+    let mut cbs = self.shared.callbacks.lock().unwrap();
+    cbs.key_up.push((id, tsfn));
+    self.shared.update_mask(&cbs);
+    Ok(id)
+  }
+
+  #[napi]
+  pub fn on_mouse_down(
+    &self,
+    #[napi(ts_arg_type = "(data: MouseButtonEventJs) => void")] callback: Function<(), ()>,
+  ) -> Result<u32> {
+    let tsfn = tracked_callback::<MouseButtonEventJs>(&self.shared.backlog, callback)?;
+    let id = self.shared.next_listener_id.fetch_add(1, Ordering::Relaxed);
+    let mut cbs = self.shared.callbacks.lock().unwrap();
+    cbs.mouse_down.push((id, tsfn));
+    self.shared.update_mask(&cbs);
+    Ok(id)
+  }
+
+  #[napi]
+  pub fn on_mouse_up(
+    &self,
+    #[napi(ts_arg_type = "(data: MouseButtonEventJs) => void")] callback: Function<(), ()>,
+  ) -> Result<u32> {
+    let tsfn = tracked_callback::<MouseButtonEventJs>(&self.shared.backlog, callback)?;
+    let id = self.shared.next_listener_id.fetch_add(1, Ordering::Relaxed);
+    let mut cbs = self.shared.callbacks.lock().unwrap();
+    cbs.mouse_up.push((id, tsfn));
+    self.shared.update_mask(&cbs);
+    Ok(id)
+  }
+
+  #[napi]
+  pub fn on_click(
+    &self,
+    #[napi(ts_arg_type = "(data: MouseButtonEventJs) => void")] callback: Function<(), ()>,
+  ) -> Result<u32> {
+    let tsfn = tracked_callback::<MouseButtonEventJs>(&self.shared.backlog, callback)?;
+    let id = self.shared.next_listener_id.fetch_add(1, Ordering::Relaxed);
+    let mut cbs = self.shared.callbacks.lock().unwrap();
+    cbs.mouse_click.push((id, tsfn));
+    self.shared.update_mask(&cbs);
+    Ok(id)
+  }
+
+  #[napi]
+  pub fn on_mouse_move(
+    &self,
+    #[napi(ts_arg_type = "(data: MouseMoveEventJs) => void")] callback: Function<(), ()>,
+  ) -> Result<u32> {
+    let tsfn = tracked_callback::<MouseMoveEventJs>(&self.shared.backlog, callback)?;
+    let id = self.shared.next_listener_id.fetch_add(1, Ordering::Relaxed);
+    let mut cbs = self.shared.callbacks.lock().unwrap();
+    cbs.mouse_move.push((id, tsfn));
+    self.shared.update_mask(&cbs);
+    Ok(id)
+  }
+
+  #[napi]
+  pub fn on_wheel(
+    &self,
+    #[napi(ts_arg_type = "(data: WheelEventJs) => void")] callback: Function<(), ()>,
+  ) -> Result<u32> {
+    let tsfn = tracked_callback::<WheelEventJs>(&self.shared.backlog, callback)?;
+    let id = self.shared.next_listener_id.fetch_add(1, Ordering::Relaxed);
+    let mut cbs = self.shared.callbacks.lock().unwrap();
+    cbs.mouse_wheel.push((id, tsfn));
+    self.shared.update_mask(&cbs);
+    Ok(id)
+  }
+
+  // ─── Callback removal ──────────────────────────────────────────────
+
+  #[napi]
+  pub fn off_key_down(&self) {
+    let mut cbs = self.shared.callbacks.lock().unwrap();
+    cbs.key_down.clear();
+    self.shared.update_mask(&cbs);
+  }
+
+  #[napi]
+  pub fn off_key_up(&self) {
+    let mut cbs = self.shared.callbacks.lock().unwrap();
+    cbs.key_up.clear();
+    self.shared.update_mask(&cbs);
+  }
+
+  #[napi]
+  pub fn off_mouse_down(&self) {
+    let mut cbs = self.shared.callbacks.lock().unwrap();
+    cbs.mouse_down.clear();
+    self.shared.update_mask(&cbs);
+  }
+
+  #[napi]
+  pub fn off_mouse_up(&self) {
+    let mut cbs = self.shared.callbacks.lock().unwrap();
+    cbs.mouse_up.clear();
+    self.shared.update_mask(&cbs);
+  }
+
+  #[napi]
+  pub fn off_click(&self) {
+    let mut cbs = self.shared.callbacks.lock().unwrap();
+    cbs.mouse_click.clear();
+    self.shared.update_mask(&cbs);
+  }
+
+  #[napi]
+  pub fn off_mouse_move(&self) {
+    let mut cbs = self.shared.callbacks.lock().unwrap();
+    cbs.mouse_move.clear();
+    self.shared.update_mask(&cbs);
+  }
+
+  #[napi]
+  pub fn off_wheel(&self) {
+    let mut cbs = self.shared.callbacks.lock().unwrap();
+    cbs.mouse_wheel.clear();
+    self.shared.update_mask(&cbs);
+  }
+
+  /// Register a callback fired when the OS disables the hook while it should be
+  /// running (on macOS: the event tap timed out or permission was revoked).
+  /// Afterwards `isRunning` reports `false`; call `stop()` then `start()` to reinstall.
+  #[napi]
+  pub fn on_tap_disabled(
+    &self,
+    #[napi(ts_arg_type = "(data: TapDisabledEventJs) => void")] callback: Function<(), ()>,
+  ) -> Result<u32> {
+    let tsfn = tracked_callback::<TapDisabledEventJs>(&self.shared.backlog, callback)?;
+    let id = self.shared.next_listener_id.fetch_add(1, Ordering::Relaxed);
+    let mut cbs = self.shared.callbacks.lock().unwrap();
+    cbs.tap_disabled.push((id, tsfn));
+    Ok(id)
+  }
+
+  #[napi]
+  pub fn off_tap_disabled(&self) {
+    let mut cbs = self.shared.callbacks.lock().unwrap();
+    cbs.tap_disabled.clear();
+  }
+
+  /// Register a callback fired after the hook stops itself because a limit set
+  /// with `setAutoStopAfterMs` or `setAutoStopAfterEvents` was reached.
+  #[napi]
+  pub fn on_auto_stop(
+    &self,
+    #[napi(ts_arg_type = "(data: AutoStopEventJs) => void")] callback: Function<(), ()>,
+  ) -> Result<u32> {
+    let tsfn = tracked_callback::<AutoStopEventJs>(&self.shared.backlog, callback)?;
+    let id = self.shared.next_listener_id.fetch_add(1, Ordering::Relaxed);
+    let mut cbs = self.shared.callbacks.lock().unwrap();
+    cbs.auto_stop.push((id, tsfn));
+    Ok(id)
+  }
+
+  #[napi]
+  pub fn off_auto_stop(&self) {
+    let mut cbs = self.shared.callbacks.lock().unwrap();
+    cbs.auto_stop.clear();
+  }
+
+  /// Register a callback fired when the held-modifier state changes, with left
+  /// and right sides collapsed: pressing ShiftRight while ShiftLeft is down,
+  /// or autorepeat of a held modifier, does not fire. Works without any key
+  /// listener registered.
+  #[napi]
+  pub fn on_modifier_change(
+    &self,
+    #[napi(ts_arg_type = "(data: ModifierChangeEventJs) => void")] callback: Function<(), ()>,
+  ) -> Result<u32> {
+    let tsfn = tracked_callback::<ModifierChangeEventJs>(&self.shared.backlog, callback)?;
+    let id = self.shared.next_listener_id.fetch_add(1, Ordering::Relaxed);
+    let mut cbs = self.shared.callbacks.lock().unwrap();
+    cbs.modifier_change.push((id, tsfn));
+    Ok(id)
+  }
+
+  #[napi]
+  pub fn off_modifier_change(&self) {
+    let mut cbs = self.shared.callbacks.lock().unwrap();
+    cbs.modifier_change.clear();
+  }
+
+  /// Register a callback fired when more than `setSlowConsumerThreshold`
+  /// events are queued for JS callbacks that have not started running, i.e.
+  /// a handler is blocking the JS thread. It fires once per episode and
+  /// re-arms when the backlog halves. Since it is queued on the same stalled
+  /// thread it arrives late; `setLogCallback` gets the same warning.
+  #[napi]
+  pub fn on_slow_consumer(
+    &self,
+    #[napi(ts_arg_type = "(data: SlowConsumerEventJs) => void")] callback: Function<(), ()>,
+  ) -> Result<()> {
+    let tsfn = callback
+      .build_threadsafe_function()
+      .max_queue_size::<EVENT_QUEUE_SIZE>()
+      .build_callback(|ctx: ThreadsafeCallContext<SlowConsumerEventJs>| Ok(vec![ctx.value]))?;
+    *self.shared.slow_consumer.lock().unwrap() = Some(tsfn);
+    Ok(())
+  }
+
+  #[napi]
+  pub fn off_slow_consumer(&self) {
+    *self.shared.slow_consumer.lock().unwrap() = None;
+  }
+
+  /// Set the backlog that counts as a slow consumer (see `onSlowConsumer`);
+  /// `null` or 0 turns the watchdog off (the default).
+  #[napi]
+  pub fn set_slow_consumer_threshold(&self, events: Option<u32>) {
+    self
+      .shared
+      .slow_threshold
+      .store(events.unwrap_or(0), Ordering::Relaxed);
+    self.shared.slow_armed.store(true, Ordering::Relaxed);
+  }
+
+  /// Remove the single listener with the id an `on*` call returned. Returns
+  /// whether it was found.
+  #[napi]
+  pub fn remove_listener(&self, id: u32) -> bool {
+    let mut cbs = self.shared.callbacks.lock().unwrap();
+    let removed = cbs.remove(id);
+    self.shared.update_mask(&cbs);
+    removed
+  }
+
+  #[napi]
+  pub fn remove_all_listeners(&self) {
+    let mut cbs = self.shared.callbacks.lock().unwrap();
+    *cbs = InputHookCallbacks::new();
+    self.shared.mask.store(0, Ordering::Relaxed);
+  }
+
+  // ─── Lifecycle ─────────────────────────────────────────────────────
+
+  #[napi]
+  pub fn start(&self) -> Result<()> {
+    let mut hook_guard = self.hook.lock().unwrap();
+    if hook_guard.is_some() {
+      return Err(Error::new(
+        Status::GenericFailure,
+        "Hook is already running",
+      ));
+    }
+
+    self.shared.tap_enabled.store(true, Ordering::Relaxed);
+    self.shared.stopping.store(false, Ordering::Relaxed);
+    self.shared.last_event_ms.store(0, Ordering::Relaxed);
+    self
+      .shared
+      .last_dispatch_ms
+      .store(now_ms(), Ordering::Relaxed);
+    self.shared.dropped.store(0, Ordering::Relaxed);
+    self.shared.modifiers.store(0, Ordering::Relaxed);
+    self.shared.held_modifiers.store(0, Ordering::Relaxed);
+    *self.shared.ready.lock().unwrap() = false;
+    self.shared.clicks.lock().unwrap().refresh_interval();
+    self.shared.event_count.store(0, Ordering::Relaxed);
+    self.reset_counts();
+    self.shared.press_times.lock().unwrap().clear();
+    self.shared.last_key_presses.lock().unwrap().clear();
+    self.shared.dropped_key_presses.lock().unwrap().clear();
+    for last in &self.shared.last_move {
+      last.store(f64::NAN.to_bits(), Ordering::Relaxed);
+    }
+    let session = self
+      .shared
+      .session
+      .fetch_add(1, Ordering::Relaxed)
+      .wrapping_add(1);
+    let shared = self.shared.clone();
+    let hook_slot = self.hook.clone();
+    let max_events = self.shared.auto_stop_events.load(Ordering::Relaxed);
+
+    let hook = Hook::new();
+    hook
+      .run_async(move |event: &Event| {
+        // Late events from a stopped session (e.g. its HookDisabled) must not
+        // clobber the state of the one that replaced it
+        if shared.session.load(Ordering::Relaxed) != session {
+          return;
+        }
+        shared.last_dispatch_ms.store(now_ms(), Ordering::Relaxed);
+        track_modifier(&shared.held_modifiers, event);
+        shared.dispatch(&InputEvent::from(event));
+        if max_events > 0
+          && !matches!(
+            event.event_type,
+            EventType::HookEnabled | EventType::HookDisabled
+          )
+          && shared.event_count.fetch_add(1, Ordering::Relaxed) + 1 == max_events
+        {
+          auto_stop_input_hook(hook_slot.clone(), shared.clone(), session, "events");
+        }
+      })
+      .map_err(|e| install_error("hook", e).1)?;
+    log_event("info", || "InputHook installed".to_string());
+
+    *hook_guard = Some(hook);
+    spawn_heartbeat(&self.hook, &self.shared, session);
+    // `shutdown()` ends the flusher while keeping the coalescing setting
+    if self.shared.wheel_gap_ms.load(Ordering::Relaxed) > 0 {
+      spawn_wheel_flusher(&self.shared);
+    }
+
+    let max_ms = self.shared.auto_stop_ms.load(Ordering::Relaxed);
+    if max_ms > 0 {
+      let hook_slot = self.hook.clone();
+      let shared = self.shared.clone();
+      thread::spawn(move || {
+        thread::sleep(Duration::from_millis(max_ms as u64));
+        auto_stop_input_hook(hook_slot, shared, session, "duration");
+      });
+    }
+    Ok(())
+  }
+
+  /// Start the hook and resolve once the native hook reports it is active
+  /// (the HookEnabled event), so no input is missed after `await`.
+  ///
+  /// Rejects if the hook fails to install or is not ready within `timeout_ms`
+  /// (default 5000); on a timeout the hook is stopped first, unless it was
+  /// stopped or restarted meanwhile. The synchronous `start()` remains
+  /// available.
+  #[napi]
+  pub fn start_async(&self, timeout_ms: Option<u32>) -> Result<AsyncTask<HookReadyTask>> {
+    self.start()?;
+    Ok(AsyncTask::new(HookReadyTask {
+      hook: self.hook.clone(),
+      session: self.shared.session.load(Ordering::Relaxed),
+      shared: self.shared.clone(),
+      timeout: Duration::from_millis(timeout_ms.unwrap_or(5000) as u64),
+    }))
+  }
+
+  /// Stop the hook.
+  #[napi]
+  pub fn stop(&self) -> Result<()> {
+    // Invalidate pending auto-stops so they don't report a stop we made
+    self.shared.session.fetch_add(1, Ordering::Relaxed);
+    stop_input_hook(&self.hook, &self.shared)
+  }
+
+  /// Tear everything down in order, resolving once quiesced: wait for input
+  /// queued by `setSimulationQueue` to run, release keys and buttons held via
+  /// simulation (see `releaseAllSimulated`), stop the native hook (flushing a
+  /// coalesced wheel gesture and cancelling auto-stop timers), end and join
+  /// the heartbeat and wheel coalescing threads, and wait for already-queued
+  /// callbacks to reach JS (up to one second). Settings, including
+  /// `setWheelCoalesceGap` and `setHeartbeatMs`, are kept, and their threads
+  /// come back on the next `start()`. Safe to call more than once.
+  #[napi]
+  pub fn shutdown(&self) -> AsyncTask<ShutdownTask> {
+    AsyncTask::new(ShutdownTask {
+      hook: self.hook.clone(),
+      held_modifiers: self.shared.held_modifiers.clone(),
+      shared: Some(self.shared.clone()),
+    })
+  }
+
+  /// Tap `key` and resolve `true` once this running hook sees it pressed, or
+  /// `false` if it doesn't within `timeoutMs` (default 500) — e.g. because the
+  /// OS blocked the injection. Needs no `onKeyDown` listener. Any press of the
+  /// same key counts, since platforms don't mark injected events reliably.
+  /// Throws if the hook is not running.
+  #[napi]
+  pub fn simulate_key_tap_confirmed(
+    &self,
+    key: KeyJs,
+    timeout_ms: Option<u32>,
+  ) -> Result<AsyncTask<KeyTapConfirmTask>> {
+    if !self.is_running() {
+      return Err(Error::new(Status::GenericFailure, "Hook is not running"));
+    }
+    Ok(AsyncTask::new(KeyTapConfirmTask {
+      shared: self.shared.clone(),
+      key: key.into(),
+      timeout: Duration::from_millis(timeout_ms.unwrap_or(500) as u64),
+    }))
+  }
+
+  /// `start()` that reports failure in the returned object instead of throwing.
+  #[napi]
+  pub fn try_start(&self) -> TryResultJs {
+    self.start().into()
+  }
+
+  /// `stop()` that reports failure in the returned object instead of throwing.
+  #[napi]
+  pub fn try_stop(&self) -> TryResultJs {
+    self.stop().into()
+  }
+
+  /// Whether the hook is installed and still receiving events. Becomes `false` if
+  /// the OS disables the hook (see `onTapDisabled`).
+  #[napi(getter)]
+  pub fn is_running(&self) -> bool {
+    let guard = self.hook.lock().unwrap();
+    guard.as_ref().is_some_and(|h| h.is_running())
+      && self.shared.tap_enabled.load(Ordering::Relaxed)
+  }
+
+  #[napi(getter)]
+  pub fn event_mask(&self) -> u32 {
+    self.shared.mask.load(Ordering::Relaxed)
+  }
+
+  /// Number of events of each type seen since `start()` (or `resetCounts()`),
+  /// counted before any mask or filter so they reflect the actual input.
+  #[napi]
+  pub fn get_event_counts(&self) -> EventCountsJs {
+    let c = |et: EventType| {
+      self.shared.counts[event_type_bit(&et).trailing_zeros() as usize].load(Ordering::Relaxed)
+        as f64
+    };
+    EventCountsJs {
+      hook_enabled: c(EventType::HookEnabled),
+      hook_disabled: c(EventType::HookDisabled),
+      key_pressed: c(EventType::KeyPressed),
+      key_released: c(EventType::KeyReleased),
+      key_typed: c(EventType::KeyTyped),
+      mouse_pressed: c(EventType::MousePressed),
+      mouse_released: c(EventType::MouseReleased),
+      mouse_clicked: c(EventType::MouseClicked),
+      mouse_moved: c(EventType::MouseMoved),
+      mouse_dragged: c(EventType::MouseDragged),
+      mouse_wheel: c(EventType::MouseWheel),
+    }
+  }
+
+  /// Zero the counters reported by `getEventCounts()`.
+  #[napi]
+  pub fn reset_counts(&self) {
+    for count in &self.shared.counts {
+      count.store(0, Ordering::Relaxed);
+    }
+  }
+
+  /// Aggregate hook state for monitoring.
+  ///
+  /// `threadAlive` is judged from the native hook thread's own activity: it is
+  /// `false` once the thread has exited or has not delivered anything for
+  /// `staleAfterMs` (default 30000). The hook thread has no idle tick, so on a
+  /// quiet system it goes stale too; pick a threshold that input should always
+  /// beat, or treat `stale` as a prompt to check rather than proof of failure.
+  #[napi]
+  pub fn healthcheck(&self, stale_after_ms: Option<u32>) -> HealthJs {
+    let installed = self
+      .hook
+      .lock()
+      .unwrap()
+      .as_ref()
+      .is_some_and(|h| h.is_running());
+    let tap_enabled = self.shared.tap_enabled.load(Ordering::Relaxed);
+    let last = self.shared.last_event_ms.load(Ordering::Relaxed);
+    let dispatch_ago = installed.then(|| {
+      now_ms().saturating_sub(self.shared.last_dispatch_ms.load(Ordering::Relaxed)) as f64
+    });
+    let stale =
+      dispatch_ago.is_some_and(|ago| ago > stale_after_ms.unwrap_or(DEFAULT_STALE_AFTER_MS) as f64);
+    HealthJs {
+      running: installed && tap_enabled,
+      thread_alive: installed && !stale,
+      stale,
+      last_dispatch_ago_ms: dispatch_ago,
+      last_event_ago_ms: (last != 0).then(|| now_ms().saturating_sub(last) as f64),
+      dropped_count: self.shared.dropped.load(Ordering::Relaxed),
+      tap_enabled,
+      backlog: self.shared.backlog.load(Ordering::Relaxed),
+    }
+  }
+
+  /// Report `ShiftRight`, `ControlRight`, `AltRight` and `MetaRight` as their
+  /// left-side key in every callback (and the ring buffer), for consumers that
+  /// don't care which side was used. Off by default. Modifier state and
+  /// `heldMs` still track each side separately.
+  #[napi]
+  pub fn set_fold_modifier_sides(&self, fold: bool) {
+    self
+      .shared
+      .fold_modifier_sides
+      .store(fold, Ordering::Relaxed);
+  }
+
+  /// Round `x`/`y` in mouse and wheel payloads (and the ring buffer) to whole
+  /// pixels, rounding halves to even so there is no drift in either direction.
+  /// Off by default, keeping sub-pixel positions.
+  #[napi]
+  pub fn set_integer_coordinates(&self, enabled: bool) {
+    self
+      .shared
+      .integer_coordinates
+      .store(enabled, Ordering::Relaxed);
+  }
+
+  /// Report wheel `delta` in notches on every platform, where 1.0 is one detent
+  /// of a regular mouse wheel; `rawDelta` keeps the platform value. High
+  /// resolution wheels and trackpads yield fractional notches. Off by default.
+  #[napi]
+  pub fn set_normalize_wheel_delta(&self, enabled: bool) {
+    self
+      .shared
+      .normalize_wheel
+      .store(enabled, Ordering::Relaxed);
+  }
+
+  /// Choose how event callbacks are queued to JS (see `CallModeJs`). Defaults
+  /// to `NonBlocking`.
+  #[napi]
+  pub fn set_call_mode(&self, mode: CallModeJs) {
+    self
+      .shared
+      .blocking
+      .store(matches!(mode, CallModeJs::Blocking), Ordering::Relaxed);
+  }
+
+  /// Drop moves (and drags) to the position `onMouseMove` last reported, such
+  /// as the no-op moves some platforms send on focus changes. Positions are
+  /// compared after `setIntegerCoordinates` rounding. Off by default.
+  #[napi]
+  pub fn set_dedup_moves(&self, enabled: bool) {
+    self.shared.dedup_moves.store(enabled, Ordering::Relaxed);
+  }
+
+  /// Attach the focused window's title and process name (`windowTitle`,
+  /// `processName`) to key and mouse button payloads, for attributing input
+  /// to applications. The window is looked up off the hook thread at most
+  /// every 100 ms, so it may lag a focus change slightly. Off by default
+  /// because of that lookup; like `getFocusedWindow`, it only yields values
+  /// on Windows.
+  #[napi]
+  pub fn set_include_window_context(&self, enabled: bool) {
+    if enabled {
+      refresh_focused_window();
+    }
+    self
+      .shared
+      .include_window_context
+      .store(enabled, Ordering::Relaxed);
+  }
+
+  /// Attach the native fields monio passes through (`raw`: the platform plus
+  /// `rawCode`, `rawDelta` and the coordinates before any adjustment) to key,
+  /// button, move and wheel payloads. Off by default because it builds an
+  /// extra object per event.
+  #[napi]
+  pub fn set_include_raw(&self, enabled: bool) {
+    self.shared.include_raw.store(enabled, Ordering::Relaxed);
+  }
+
+  /// Drop a KeyPressed for a key that was released and pressed again less
+  /// than `ms` after its last accepted press, such as the same key arriving
+  /// from two keyboards in quick succession. Events don't say which device
+  /// sent them, so this is purely time-based. Presses of a key that is still
+  /// held are autorepeat and always delivered. Unlike `startListen`'s
+  /// `debounceMs` it is tracked per key, so interleaved presses of different
+  /// keys don't reset it. Dropped presses reach no callback, hotkey or
+  /// modifier tracking, and swallow the next release of their key (one
+  /// release per key, however many presses were dropped). 0 (the default)
+  /// turns it off.
+  #[napi]
+  pub fn set_dedup_keys_window_ms(&self, ms: u32) {
+    self.shared.dedup_keys_ms.store(ms, Ordering::Relaxed);
+    self.shared.last_key_presses.lock().unwrap().clear();
+    self.shared.dropped_key_presses.lock().unwrap().clear();
+  }
+
+  /// Set how far (in pixels, per axis) successive presses may drift and still
+  /// count toward `clickCount`. Defaults to 4. The time window is the system
+  /// double-click interval, re-read on `start()`.
+  #[napi]
+  pub fn set_click_tolerance(&self, px: f64) {
+    self.shared.clicks.lock().unwrap().tolerance = px.max(0.0);
+  }
+
+  /// Create a new, stopped `InputHook` with this hook's settings: filter,
+  /// source-pid filter, click tolerance, wheel coalescing, auto-stop limits,
+  /// rate limits and priorities, slow-consumer threshold, modifier-side folding,
+  /// integer coordinates, wheel normalization, key dedup and window context.
+  /// Callbacks (and so the event mask, which follows them), the ring buffer
+  /// and counters are not copied.
+  #[napi]
+  pub fn clone_config(&self) -> InputHook {
+    let clone = InputHook::new();
+    self.copy_config_to(&clone);
+    clone
+  }
+
+  /// Put every setting `cloneConfig()` copies back to its default, keeping
+  /// callbacks (and so the event mask), the ring buffer and the running state.
+  /// The counterpart of `removeAllListeners()`, which keeps the settings.
+  #[napi]
+  pub fn reset_settings(&self) {
+    InputHook::new().copy_config_to(self);
+  }
+
+  /// Only deliver events while the focused window belongs to process `pid`;
+  /// pass `null` to deliver everything again.
+  ///
+  /// No platform reports the originating process per event, so this checks the
+  /// focused window's owner, looked up off the hook thread at most every
+  /// ~100ms (so a focus change may take that long to apply). That only works
+  /// on Windows (see `getFocusedWindow`); elsewhere setting a pid throws
+  /// `[Unsupported]`.
+  #[napi]
+  pub fn set_source_pid_filter(&self, pid: Option<u32>) -> Result<()> {
+    if pid.is_some() && !cfg!(target_os = "windows") {
+      return Err(Error::new(
+        Status::GenericFailure,
+        "[Unsupported] setSourcePidFilter is only available on Windows",
+      ));
+    }
+    if pid.is_some() {
+      refresh_focused_window();
+    }
+    self
+      .shared
+      .source_pid
+      .store(pid.unwrap_or(0), Ordering::Relaxed);
+    Ok(())
+  }
+
+  /// Stop automatically `ms` after `start()`; `null` or 0 disables the limit
+  /// (the default). Takes effect on the next `start()`.
+  #[napi]
+  pub fn set_auto_stop_after_ms(&self, ms: Option<u32>) {
+    self
+      .shared
+      .auto_stop_ms
+      .store(ms.unwrap_or(0), Ordering::Relaxed);
+  }
+
+  /// Stop automatically once `count` input events have been seen since
+  /// `start()`, whether or not a callback was registered for them; `null` or 0
+  /// disables the limit (the default). Takes effect on the next `start()`.
+  #[napi]
+  pub fn set_auto_stop_after_events(&self, count: Option<u32>) {
+    self
+      .shared
+      .auto_stop_events
+      .store(count.unwrap_or(0), Ordering::Relaxed);
+  }
+
+  /// Write events into `buffer` — an `Int32Array` over a `SharedArrayBuffer` —
+  /// instead of (or as well as) calling per-event callbacks, for capture rates
+  /// where callback overhead dominates. See `RING_LAYOUT_VERSION` for the
+  /// record layout; the buffer's header is initialised here.
+  ///
+  /// Every input event that passes `setSourcePidFilter` and `setFilter` is
+  /// written, regardless of which callbacks are registered. JS tracks its own
+  /// read position and has been overrun if the write count gets more than the
+  /// capacity ahead of it.
+  ///
+  /// Native code can't `Atomics.notify` a JS waiter, so a worker blocked in
+  /// `Atomics.wait(view, 0, seen, timeout)` should use a short timeout. For
+  /// event-loop consumers, `onData` (if given) is called with the write count
+  /// once per batch: further writes don't queue another call until it has run.
+  #[napi]
+  pub fn attach_ring_buffer(
+    &mut self,
+    mut buffer: Int32Array,
+    #[napi(ts_arg_type = "((written: number) => void) | undefined | null")] on_data: Option<
+      Function<(), ()>,
+    >,
+  ) -> Result<()> {
+    let header = (RING_HEADER_BYTES / 4) as usize;
+    let record = (RING_RECORD_BYTES / 4) as usize;
+    if buffer.len() < header + record {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!(
+          "Ring buffer must hold at least {} bytes",
+          RING_HEADER_BYTES + RING_RECORD_BYTES
+        ),
+      ));
+    }
+    let capacity = ((buffer.len() - header) / record) as u32;
+    buffer[0] = 0;
+    buffer[1] = RING_LAYOUT_VERSION as i32;
+    buffer[2] = capacity as i32;
+    buffer[3] = RING_RECORD_BYTES as i32;
+
+    let notify = match on_data {
+      Some(callback) => {
+        let pending = Arc::new(AtomicBool::new(false));
+        let pending_js = pending.clone();
+        let tsfn = callback.build_threadsafe_function().build_callback(
+          move |ctx: ThreadsafeCallContext<u32>| {
+            pending_js.store(false, Ordering::Release);
+            Ok(vec![ctx.value])
+          },
+        )?;
+        Some((tsfn, pending))
+      }
+      None => None,
+    };
+
+    *self.shared.ring.lock().unwrap() = Some(EventRing {
+      ptr: buffer.as_mut_ptr(),
+      capacity,
+      written: 0,
+      notify,
+    });
+    self.shared.ring_attached.store(true, Ordering::Relaxed);
+    // Any previous buffer is released only after the hook stopped using it.
+    self.ring_buffer = Some(buffer);
+    Ok(())
+  }
+
+  /// Stop writing to the ring buffer set with `attachRingBuffer`.
+  #[napi]
+  pub fn detach_ring_buffer(&mut self) {
+    self.shared.ring_attached.store(false, Ordering::Relaxed);
+    *self.shared.ring.lock().unwrap() = None;
+    self.ring_buffer = None;
+  }
+
+  /// Keep the last `n` mouse positions (moves and drags) for `getMouseTrail`,
+  /// whether or not any mouse listener is registered; 0 turns it off (the
+  /// default) and clears the trail. Shrinking drops the oldest positions.
+  #[napi]
+  pub fn set_trail_capacity(&self, n: u32) {
+    self.shared.trail_capacity.store(n, Ordering::Relaxed);
+    let mut trail = self.shared.trail.lock().unwrap();
+    let excess = trail.len().saturating_sub(n as usize);
+    trail.drain(..excess);
+  }
+
+  /// Recent mouse positions, oldest first (see `setTrailCapacity`).
+  #[napi]
+  pub fn get_mouse_trail(&self) -> Vec<TrailPointJs> {
+    let trail = self.shared.trail.lock().unwrap();
+    trail
+      .iter()
+      .map(|&(x, y, time)| TrailPointJs {
+        x,
+        y,
+        time: js_time(time),
+      })
+      .collect()
+  }
+
+  /// Limit callback delivery to `eventsPerSecond` (short bursts up to one
+  /// second's worth are allowed); `null` or 0 removes the limit (the default).
+  /// Which events are shed is controlled by `setEventPriority`.
+  #[napi]
+  pub fn set_rate_limit(&self, events_per_second: Option<u32>) {
+    self
+      .shared
+      .limiter
+      .lock()
+      .unwrap()
+      .reset(events_per_second.unwrap_or(0));
+  }
+
+  /// Limit one event type to `eventsPerSecond`, e.g. mouse moves to 60/s while
+  /// key presses stay unlimited; `null` or 0 removes that type's limit (the
+  /// default). Each type has its own bucket, checked along with
+  /// `setRateLimit`'s shared one (regardless of `setEventPriority`): an event
+  /// is charged to both or, if either says no, to neither.
+  #[napi]
+  pub fn set_rate_limit_for(&self, event: EventTypeJs, events_per_second: Option<u32>) {
+    let index = event_type_bit(&event.into()).trailing_zeros() as usize;
+    self.shared.type_limiters.lock().unwrap()[index].reset(events_per_second.unwrap_or(0));
+  }
+
+  /// Set how an event type fares under `setRateLimit`: `High` always gets
+  /// through (e.g. clicks, key presses), `Low` is shed first (e.g. mouse moves).
+  #[napi]
+  pub fn set_event_priority(&self, event: EventTypeJs, priority: PriorityJs) {
+    let index = event_type_bit(&event.into()).trailing_zeros() as usize;
+    self.shared.priorities[index].store(priority as u32, Ordering::Relaxed);
+  }
+
+  /// Only dispatch events matching `spec` (see `FilterSpecJs` for the grammar);
+  /// pass `null` to remove the filter. The spec is evaluated natively, after the
+  /// callback mask, so rejected events never reach JS.
+  #[napi]
+  pub fn set_filter(&self, spec: Option<FilterSpecJs>) {
+    *self.shared.filter.lock().unwrap() = spec.map(EventFilter::from);
+  }
+
+  /// Coalesce wheel events: consecutive events in the same direction less than
+  /// `gapMs` apart are delivered to `onWheel` as one event with the summed
+  /// `delta`, the latest position and the first event's `time`. A gesture is
+  /// delivered once the direction changes or no event arrives for `gapMs`.
+  /// Summed deltas are clamped to ±1,000,000. Pass `null` or 0 to deliver
+  /// every wheel event again (the default).
+  ///
+  /// Wheel events with a non-finite delta are always dropped, coalescing or not.
+  #[napi]
+  pub fn set_wheel_coalesce_gap(&self, gap_ms: Option<u32>) {
+    let gap_ms = gap_ms.unwrap_or(0);
+    self.shared.wheel_gap_ms.store(gap_ms, Ordering::Relaxed);
+    if gap_ms == 0 {
+      self.shared.flush_wheel(Duration::ZERO);
+      return;
+    }
+    spawn_wheel_flusher(&self.shared);
+  }
+
+  /// Call `callback` every `ms` while the hook is running, from a native
+  /// timer, with the time in the events' time base. Ticks are skipped while
+  /// the native hook thread has exited or the OS has disabled its tap (see
+  /// `onTapDisabled`), so missing heartbeats mean no events can arrive. It
+  /// stops with the hook and resumes on the next `start()`. Setting it again replaces the interval
+  /// and callback; `ms` 0 turns it off. Not copied by `cloneConfig()`.
+  #[napi]
+  pub fn set_heartbeat_ms(
+    &self,
+    ms: u32,
+    #[napi(ts_arg_type = "((data: HeartbeatEventJs) => void) | null | undefined")] callback: Option<
+      Function<(), ()>,
+    >,
+  ) -> Result<()> {
+    let tsfn = match callback {
+      Some(callback) if ms > 0 => Some(tracked_callback::<HeartbeatEventJs>(
+        &self.shared.backlog,
+        callback,
+      )?),
+      None if ms > 0 => {
+        return Err(Error::new(
+          Status::InvalidArg,
+          "setHeartbeatMs needs a callback unless ms is 0",
+        ))
+      }
+      _ => None,
+    };
+    {
+      let mut cbs = self.shared.callbacks.lock().unwrap();
+      cbs.heartbeat.clear();
+      if let Some(tsfn) = tsfn {
+        let id = self.shared.next_listener_id.fetch_add(1, Ordering::Relaxed);
+        cbs.heartbeat.push((id, tsfn));
+      }
+    }
+    self.shared.heartbeat_ms.store(ms, Ordering::Relaxed);
+    self.shared.heartbeat_gen.fetch_add(1, Ordering::Relaxed);
+    if self.hook.lock().unwrap().is_some() {
+      let session = self.shared.session.load(Ordering::Relaxed);
+      spawn_heartbeat(&self.hook, &self.shared, session);
+    }
+    Ok(())
+  }
+
+  /// Current wheel coalescing gap in ms (0 = off).
+  #[napi(getter)]
+  pub fn wheel_coalesce_gap_ms(&self) -> u32 {
+    self.shared.wheel_gap_ms.load(Ordering::Relaxed)
+  }
+}
+
+impl InputHook {
+  /// The settings half of `cloneConfig()`.
+  fn copy_config_to(&self, target: &InputHook) {
+    let (from, to) = (&self.shared, &target.shared);
+    let copy = |a: &AtomicU32, b: &AtomicU32| b.store(a.load(Ordering::Relaxed), Ordering::Relaxed);
+    copy(&from.source_pid, &to.source_pid);
+    copy(&from.auto_stop_ms, &to.auto_stop_ms);
+    copy(&from.auto_stop_events, &to.auto_stop_events);
+    copy(&from.slow_threshold, &to.slow_threshold);
+    copy(&from.dedup_keys_ms, &to.dedup_keys_ms);
+    target.set_trail_capacity(from.trail_capacity.load(Ordering::Relaxed));
+    for (a, b) in from.priorities.iter().zip(&to.priorities) {
+      copy(a, b);
+    }
+    to.fold_modifier_sides.store(
+      from.fold_modifier_sides.load(Ordering::Relaxed),
+      Ordering::Relaxed,
+    );
+    to.integer_coordinates.store(
+      from.integer_coordinates.load(Ordering::Relaxed),
+      Ordering::Relaxed,
+    );
+    to.normalize_wheel.store(
+      from.normalize_wheel.load(Ordering::Relaxed),
+      Ordering::Relaxed,
+    );
+    to.dedup_moves
+      .store(from.dedup_moves.load(Ordering::Relaxed), Ordering::Relaxed);
+    to.include_window_context.store(
+      from.include_window_context.load(Ordering::Relaxed),
+      Ordering::Relaxed,
+    );
+    to.include_raw
+      .store(from.include_raw.load(Ordering::Relaxed), Ordering::Relaxed);
+    to.blocking
+      .store(from.blocking.load(Ordering::Relaxed), Ordering::Relaxed);
+    to.clicks.lock().unwrap().tolerance = from.clicks.lock().unwrap().tolerance;
+    *to.filter.lock().unwrap() = from.filter.lock().unwrap().clone();
+    let rate = from.limiter.lock().unwrap().rate;
+    target.set_rate_limit(Some(rate));
+    let rates = from
+      .type_limiters
+      .lock()
+      .unwrap()
+      .each_ref()
+      .map(|l| l.rate);
+    for (limiter, rate) in to.type_limiters.lock().unwrap().iter_mut().zip(rates) {
+      limiter.reset(rate);
+    }
+    target.set_wheel_coalesce_gap(Some(from.wheel_gap_ms.load(Ordering::Relaxed)));
+  }
+}
+
+/// Wakes an `InputHook`'s background threads from their sleep so `shutdown()`
+/// can join them. Kept apart from `InputHookShared` so a sleeping thread
+/// doesn't keep the hook alive.
+type ThreadStop = Arc<(Mutex<bool>, Condvar)>;
+
+/// Sleep for `timeout` or until `stop` is raised; returns whether it was.
+fn sleep_or_stop(stop: &ThreadStop, timeout: Duration) -> bool {
+  let (raised, cv) = &**stop;
+  let (raised, _) = cv
+    .wait_timeout_while(raised.lock().unwrap(), timeout, |raised| !*raised)
+    .unwrap();
+  *raised
+}
+
+/// Start the thread flushing idle coalesced wheel gestures, unless it is
+/// already running. Trackpad momentum just stops, so idle gestures need a
+/// timer to flush them.
+fn spawn_wheel_flusher(shared: &Arc<InputHookShared>) {
+  if shared.wheel_flusher.swap(true, Ordering::Relaxed) {
+    return;
+  }
+  let stop = shared.thread_stop.clone();
+  let weak = Arc::downgrade(shared);
+  let handle = thread::spawn(move || loop {
+    let Some(shared) = weak.upgrade() else {
+      return;
+    };
+    let gap = shared.wheel_gap_ms.load(Ordering::Relaxed);
+    if gap == 0 {
+      shared.wheel_flusher.store(false, Ordering::Relaxed);
+      // Coalescing may have been re-enabled after the load above
+      if shared.wheel_gap_ms.load(Ordering::Relaxed) == 0
+        || shared.wheel_flusher.swap(true, Ordering::Relaxed)
+      {
+        return;
+      }
+      continue;
+    }
+    let gap = Duration::from_millis(gap as u64);
+    shared.flush_wheel(gap);
+    drop(shared);
+    if sleep_or_stop(&stop, (gap / 2).max(Duration::from_millis(1))) {
+      if let Some(shared) = weak.upgrade() {
+        shared.wheel_flusher.store(false, Ordering::Relaxed);
+      }
+      return;
+    }
+  });
+  *shared.wheel_flusher_thread.lock().unwrap() = Some(handle);
+}
+
+/// Deliver heartbeats until `session` ends or the heartbeat is reconfigured.
+/// A tick is skipped while the native hook thread is gone or its tap is
+/// disabled, so heartbeats only arrive while the hook can deliver events.
+fn spawn_heartbeat(hook: &Arc<Mutex<Option<Hook>>>, shared: &Arc<InputHookShared>, session: u32) {
+  let ms = shared.heartbeat_ms.load(Ordering::Relaxed);
+  if ms == 0 {
+    return;
+  }
+  let generation = shared.heartbeat_gen.load(Ordering::Relaxed);
+  let stop = shared.thread_stop.clone();
+  let hook = Arc::downgrade(hook);
+  let weak = Arc::downgrade(shared);
+  let handle = thread::spawn(move || loop {
+    if sleep_or_stop(&stop, Duration::from_millis(ms as u64)) {
+      return;
+    }
+    let (Some(hook), Some(shared)) = (hook.upgrade(), weak.upgrade()) else {
+      return;
+    };
+    if shared.session.load(Ordering::Relaxed) != session
+      || shared.heartbeat_gen.load(Ordering::Relaxed) != generation
+    {
+      return;
+    }
+    let alive = hook
+      .lock()
+      .unwrap()
+      .as_ref()
+      .is_some_and(|h| h.is_running());
+    if !alive || !shared.tap_enabled.load(Ordering::Relaxed) {
+      continue;
+    }
+    let last = shared.last_event_ms.load(Ordering::Relaxed);
+    let cbs = shared.callbacks.lock().unwrap();
+    shared.emit(&cbs.heartbeat, || HeartbeatEventJs {
+      time: js_time(now_secs()),
+      last_event_ago_ms: (last != 0).then(|| now_ms().saturating_sub(last) as f64),
+    });
+  });
+  *shared.heartbeat_thread.lock().unwrap() = Some(handle);
+}
+
+fn stop_input_hook(hook: &Mutex<Option<Hook>>, shared: &InputHookShared) -> Result<()> {
+  let mut hook_guard = hook.lock().unwrap();
+  let stopped = hook_guard.is_some();
+  if let Some(hook) = hook_guard.take() {
+    shared.stopping.store(true, Ordering::Relaxed);
+    hook.stop().map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to stop hook: {}", e),
+      )
+    })?;
+    // Still holding the slot lock, so a start() right after waits for the old
+    // hook thread to wind down instead of installing alongside it
+    let deadline = Instant::now() + STOP_TIMEOUT;
+    while hook.is_running() && Instant::now() < deadline {
+      thread::sleep(Duration::from_millis(1));
+    }
+    shared.held_modifiers.store(0, Ordering::Relaxed);
+    log_event("info", || "InputHook removed".to_string());
+  }
+  shared.flush_wheel(Duration::ZERO);
+  if stopped {
+    return release_held_input_on_stop();
+  }
+  Ok(())
+}
+
+/// How long `stop()` waits for the native hook thread to exit.
+const STOP_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Stop `session` because an auto-stop limit was hit, then notify `onAutoStop`.
+/// Runs on its own thread: stopping from inside the hook callback could block
+/// on the hook thread itself.
+fn auto_stop_input_hook(
+  hook: Arc<Mutex<Option<Hook>>>,
+  shared: Arc<InputHookShared>,
+  session: u32,
+  reason: &'static str,
+) {
+  thread::spawn(move || {
+    if shared
+      .session
+      .compare_exchange(
+        session,
+        session.wrapping_add(1),
+        Ordering::Relaxed,
+        Ordering::Relaxed,
+      )
+      .is_err()
+    {
+      return;
+    }
+    log_event("info", || format!("InputHook auto-stopped ({})", reason));
+    if let Err(e) = stop_input_hook(&hook, &shared) {
+      log_event("error", || format!("Failed to auto-stop InputHook: {}", e));
+    }
+    let cbs = shared.callbacks.lock().unwrap();
+    let events = shared.event_count.load(Ordering::Relaxed);
+    let time = js_time(now_secs());
+    shared.emit(&cbs.auto_stop, || AutoStopEventJs {
+      reason: reason.to_string(),
+      events,
+      time,
+    });
+  });
+}
+
+pub struct HookReadyTask {
+  hook: Arc<Mutex<Option<Hook>>>,
+  /// The session `startAsync` started, so a timeout only stops that one.
+  session: u32,
+  shared: Arc<InputHookShared>,
+  timeout: Duration,
+}
+
+#[napi]
+impl Task for HookReadyTask {
+  type Output = ();
+  type JsValue = ();
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    let ready = self.shared.ready.lock().unwrap();
+    let (ready, _) = self
+      .shared
+      .ready_cv
+      .wait_timeout_while(ready, self.timeout, |ready| !*ready)
+      .unwrap();
+    if *ready {
+      return Ok(());
+    }
+    drop(ready);
+    // Don't leave a hook the caller was told failed running
+    if self
+      .shared
+      .session
+      .compare_exchange(
+        self.session,
+        self.session.wrapping_add(1),
+        Ordering::Relaxed,
+        Ordering::Relaxed,
+      )
+      .is_ok()
+    {
+      stop_input_hook(&self.hook, &self.shared)?;
+    }
+    Err(Error::new(
+      Status::GenericFailure,
+      "Timed out waiting for hook to become ready",
+    ))
+  }
+
+  fn resolve(&mut self, _env: Env, _output: Self::Output) -> Result<Self::JsValue> {
+    Ok(())
+  }
+}
+
+pub struct KeyTapConfirmTask {
+  shared: Arc<InputHookShared>,
+  key: Key,
+  timeout: Duration,
+}
+
+#[napi]
+impl Task for KeyTapConfirmTask {
+  type Output = bool;
+  type JsValue = bool;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    let shared = &self.shared;
+    let id = shared.next_listener_id.fetch_add(1, Ordering::Relaxed);
+    shared
+      .key_watch
+      .lock()
+      .unwrap()
+      .insert(id, (key_id(self.key), false));
+    let tapped = key_tap(self.key);
+    let mut watch = shared.key_watch.lock().unwrap();
+    if tapped.is_ok() {
+      watch = shared
+        .key_watch_cv
+        .wait_timeout_while(watch, self.timeout, |w| !w[&id].1)
+        .unwrap()
+        .0;
+    }
+    let seen = watch.remove(&id).is_some_and(|(_, seen)| seen);
+    drop(watch);
+    tapped.map_err(|e| Error::new(Status::GenericFailure, format!("Failed to tap key: {}", e)))?;
+    Ok(seen)
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+}
+
+/// How long `shutdown()` waits for background threads and queued callbacks.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Teardown behind `HookJs.shutdown()` and `InputHook.shutdown()`.
+pub struct ShutdownTask {
+  hook: Arc<Mutex<Option<Hook>>>,
+  held_modifiers: ModifierTracker,
+  /// Set for `InputHook`, whose buffers and background threads need draining too.
+  shared: Option<Arc<InputHookShared>>,
+}
+
+#[napi]
+impl Task for ShutdownTask {
+  type Output = ();
+  type JsValue = ();
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    // Let queued simulation run first, so the release catches what it pressed.
+    // Its failures are left for `awaitSimulationDrained` to report.
+    let mut progress = SIMULATION_PROGRESS.lock().unwrap();
+    while progress.pending > 0 {
+      progress = SIMULATION_DRAINED.wait(progress).unwrap();
+    }
+    drop(progress);
+    let released = release_held_input();
+    let Some(shared) = &self.shared else {
+      stop_listener(&self.hook, &self.held_modifiers)?;
+      return released;
+    };
+    shared.session.fetch_add(1, Ordering::Relaxed);
+    // Flushes a coalesced wheel gesture; the coalescing setting is kept
+    stop_input_hook(&self.hook, shared)?;
+    shared.join_threads();
+    // The JS thread is free while this runs on a worker, so the backlog drains.
+    let deadline = Instant::now() + SHUTDOWN_TIMEOUT;
+    while shared.backlog.load(Ordering::Relaxed) > 0 && Instant::now() < deadline {
+      thread::sleep(Duration::from_millis(1));
+    }
+    released
+  }
+
+  fn resolve(&mut self, _env: Env, _output: Self::Output) -> Result<Self::JsValue> {
+    Ok(())
+  }
+}
+
+// ============================================================================
+// Hotkeys (chords matched in InputHook dispatch, see InputHook.hotkeys())
+// ============================================================================
+
+/// Folds applied before comparing hotkey keys: numpad twins and modifier sides
+/// count as the same key.
+const HOTKEY_FOLDS: NormalizeKeyOptionsJs = NormalizeKeyOptionsJs {
+  fold_numpad: Some(true),
+  fold_modifier_sides: Some(true),
+};
+
+/// Folded form of a `key_id`.
+fn hotkey_key_id(id: u32) -> u32 {
+  key_from_i32(id as i32).map_or(id, |key| normalize(key, &HOTKEY_FOLDS) as u32)
+}
+
+/// Sorted, deduplicated folded ids of a set of `key_id`s, for order-independent
+/// chord comparison.
+fn hotkey_chord(ids: impl Iterator<Item = u32>) -> Vec<u32> {
+  let mut chord: Vec<u32> = ids.map(hotkey_key_id).collect();
+  chord.sort_unstable();
+  chord.dedup();
+  chord
+}
+
+/// A registered chord and its callback.
+struct Hotkey {
+  /// As registered, for `HotkeyEventJs.keys`.
+  keys: Vec<Key>,
+  chord: Vec<u32>,
+  /// Folded id of the last key, which has to be the one pressed to fire.
+  trigger: u32,
+  cooldown: Duration,
+  last_fired: Option<Instant>,
+  callback: HotkeyTsFn,
+}
+
+/// Payload for hotkey callbacks.
+#[napi(object)]
+pub struct HotkeyEventJs {
+  /// Id returned by `HotkeyManager.register`.
+  pub id: u32,
+  pub keys: Vec<KeyJs>,
+  pub time: f64,
+}
+
+/// Hotkey registrations on an `InputHook`, from `InputHook.hotkeys()`.
+/// Hotkeys fire only while that hook is running.
+#[napi]
+pub struct HotkeyManager {
+  shared: Arc<InputHookShared>,
+}
+
+#[napi]
+impl HotkeyManager {
+  /// Register a chord, given like `simulateKeyCombo` keys: modifiers, then the
+  /// key that triggers it (e.g. `[ControlLeft, ShiftLeft, KeyP]`). It fires
+  /// once when the trigger goes down while exactly the other keys are held;
+  /// holding it does not fire again. Numpad twins and left/right modifiers
+  /// match each other (see `normalizeKey`).
+  ///
+  /// With `cooldownMs`, it also does not fire again until that long after it
+  /// last fired, even on a fresh press. Returns an id for `unregister` (or
+  /// `InputHook.removeListener`).
+  #[napi]
+  pub fn register(
+    &self,
+    keys: Vec<KeyJs>,
+    #[napi(ts_arg_type = "(data: HotkeyEventJs) => void")] callback: Function<(), ()>,
+    cooldown_ms: Option<u32>,
+  ) -> Result<u32> {
+    let keys: Vec<Key> = keys.into_iter().map(Key::from).collect();
+    validate_combo(&keys)?;
+    let callback = tracked_callback::<HotkeyEventJs>(&self.shared.backlog, callback)?;
+    let id = self.shared.next_listener_id.fetch_add(1, Ordering::Relaxed);
+    let hotkey = Hotkey {
+      chord: hotkey_chord(keys.iter().map(|&k| key_id(k))),
+      trigger: hotkey_key_id(key_id(keys[keys.len() - 1])),
+      keys,
+      cooldown: Duration::from_millis(cooldown_ms.unwrap_or(0) as u64),
+      last_fired: None,
+      callback,
+    };
+    self
+      .shared
+      .callbacks
+      .lock()
+      .unwrap()
+      .hotkeys
+      .push((id, hotkey));
+    Ok(id)
+  }
+
+  /// Remove one hotkey. Returns `false` if `id` is not a registered hotkey.
+  #[napi]
+  pub fn unregister(&self, id: u32) -> bool {
+    let mut cbs = self.shared.callbacks.lock().unwrap();
+    let before = cbs.hotkeys.len();
+    cbs.hotkeys.retain(|(i, _)| *i != id);
+    cbs.hotkeys.len() != before
+  }
+
+  /// Ids of registered hotkeys that `keys` would collide with: the same chord
+  /// and the same trigger (last key), compared with the folds `register`
+  /// matches with, so `[ControlRight, Numpad1]` conflicts with
+  /// `[ControlLeft, Num1]`. Modifier order is ignored. `keys` must be a valid
+  /// `register` chord; otherwise this throws `InvalidArg` like `register`.
+  /// Hotkeys are single chords, so there are no sequence prefixes to overlap.
+  #[napi]
+  pub fn find_conflicts(&self, keys: Vec<KeyJs>) -> Result<Vec<u32>> {
+    let keys: Vec<Key> = keys.into_iter().map(Key::from).collect();
+    validate_combo(&keys)?;
+    let chord = hotkey_chord(keys.iter().map(|&k| key_id(k)));
+    let trigger = hotkey_key_id(key_id(keys[keys.len() - 1]));
+    let cbs = self.shared.callbacks.lock().unwrap();
+    Ok(
+      cbs
+        .hotkeys
+        .iter()
+        .filter(|(_, hotkey)| hotkey.chord == chord && hotkey.trigger == trigger)
+        .map(|(id, _)| *id)
+        .collect(),
+    )
+  }
+
+  /// Remove every hotkey.
+  #[napi]
+  pub fn clear(&self) {
+    self.shared.callbacks.lock().unwrap().hotkeys.clear();
+  }
+}
+
+#[napi]
+impl InputHook {
+  /// Hotkey registrations matched against this hook's keyboard events. Every
+  /// call returns a view of the same registrations.
+  #[napi]
+  pub fn hotkeys(&self) -> HotkeyManager {
+    HotkeyManager {
+      shared: self.shared.clone(),
+    }
+  }
+}
+
+#[cfg(feature = "test-utils")]
+#[napi]
+impl InputHook {
+  /// Run a synthetic event through the same dispatch path as the native hook
+  /// (mask check and typed-callback routing), without touching OS input.
+  /// The hook does not need to be started.
+  ///
+  /// Only available in builds with the `test-utils` Cargo feature.
+  #[napi]
+  pub fn inject_event_for_test(&self, event: EventJs) {
+    self.shared.dispatch(&event.into());
+  }
+}
+
+// ============================================================================
+// Display Functions
+// ============================================================================
+
+/// Get all displays, primary first, then left to right, then top to bottom
+/// (see `DisplayInfoJs.index`).
+#[napi]
+pub fn get_displays() -> Result<Vec<DisplayInfoJs>> {
+  displays()
+    .map(|infos| ordered_displays(&infos))
+    .map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to get displays: {}", e),
+      )
+    })
+}
+
+/// Get the primary display
+#[napi]
+pub fn get_primary_display() -> Result<DisplayInfoJs> {
+  primary_display().map(|info| (&info).into()).map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to get primary display: {}", e),
+    )
+  })
+}
+
+/// Like `getDisplays`, but returns `[]` instead of throwing when displays
+/// can't be enumerated (e.g. on a headless machine).
+#[napi]
+pub fn get_displays_or_empty() -> Vec<DisplayInfoJs> {
+  displays()
+    .map(|infos| ordered_displays(&infos))
+    .unwrap_or_default()
+}
+
+/// Like `getPrimaryDisplay`, but returns `null` instead of throwing when there
+/// is no display to report (e.g. on a headless machine).
+#[napi]
+pub fn get_primary_display_or_null() -> Option<DisplayInfoJs> {
+  primary_display().ok().map(|info| (&info).into())
+}
+
+/// Highest refresh rate (Hz) of any connected display, or `null` when no
+/// display reports one.
+#[napi]
+pub fn get_highest_refresh_rate() -> Option<u32> {
+  displays().ok()?.iter().filter_map(|d| d.refresh_rate).max()
+}
+
+/// Get display at a specific point
+#[napi]
+pub fn get_display_at_point(x: f64, y: f64) -> Result<Option<DisplayInfoJs>> {
+  let Some(info) = display_at_point(x, y).map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to get display at point: {}", e),
+    )
+  })?
+  else {
+    return Ok(None);
+  };
+  // `index` is only meaningful relative to the full display list
+  let infos = displays().map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to get displays: {}", e),
+    )
+  })?;
+  Ok(
+    ordered_displays(&infos)
+      .into_iter()
+      .find(|d| d.id == info.id)
+      .or_else(|| Some((&info).into())),
+  )
+}
+
+/// Display ids that differ between two `getDisplays` snapshots.
+#[napi(object)]
+pub struct DisplayChangeSetJs {
+  pub added: Vec<u32>,
+  pub removed: Vec<u32>,
+  /// Present in both, with different bounds, scale factor, refresh rate or
+  /// primary flag. `index` alone changing is not a change.
+  pub changed: Vec<u32>,
+}
+
+/// Geometry and scale are compared with this tolerance, so float noise from
+/// the platform's unit conversions doesn't register as a change.
+const DISPLAY_EPSILON: f64 = 1e-6;
+
+fn same_display(a: &DisplayInfoJs, b: &DisplayInfoJs) -> bool {
+  let close = |a: f64, b: f64| (a - b).abs() <= DISPLAY_EPSILON;
+  close(a.bounds.x, b.bounds.x)
+    && close(a.bounds.y, b.bounds.y)
+    && close(a.bounds.width, b.bounds.width)
+    && close(a.bounds.height, b.bounds.height)
+    && close(a.scale_factor, b.scale_factor)
+    && a.refresh_rate == b.refresh_rate
+    && a.is_primary == b.is_primary
+}
+
+/// Compare two display lists by `id`. Ids are listed in the order they appear
+/// in `current` (for `added` and `changed`) or `previous` (for `removed`).
+#[napi]
+pub fn diff_displays(
+  previous: Vec<DisplayInfoJs>,
+  current: Vec<DisplayInfoJs>,
+) -> DisplayChangeSetJs {
+  let find = |list: &[DisplayInfoJs], id: u32| list.iter().position(|d| d.id == id);
+  let mut changes = DisplayChangeSetJs {
+    added: Vec::new(),
+    removed: Vec::new(),
+    changed: Vec::new(),
+  };
+  for display in &current {
+    match find(&previous, display.id) {
+      None => changes.added.push(display.id),
+      Some(i) if !same_display(&previous[i], display) => changes.changed.push(display.id),
+      Some(_) => {}
+    }
+  }
+  changes.removed = previous
+    .iter()
+    .filter(|d| find(&current, d.id).is_none())
+    .map(|d| d.id)
+    .collect();
+  changes
+}
+
+/// Scale factor of the display containing a point, falling back to the
+/// primary display's scale when the point is off every display.
+#[napi]
+pub fn get_scale_factor_at_point(x: f64, y: f64) -> Result<f64> {
+  let info = match display_at_point(x, y) {
+    Ok(Some(info)) => info,
+    Ok(None) => primary_display().map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to get primary display: {}", e),
+      )
+    })?,
+    Err(e) => {
+      return Err(Error::new(
+        Status::GenericFailure,
+        format!("Failed to get display at point: {}", e),
+      ))
+    }
+  };
+  Ok(info.scale_factor)
+}
+
+/// Get system settings
+#[napi]
+pub fn get_system_settings() -> Result<SystemSettingsJs> {
+  system_settings()
+    .map(|settings| (&settings).into())
+    .map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to get system settings: {}", e),
+      )
+    })
+}
+
+/// `Option<f64>` equality that treats two NaNs as the same reading.
+fn same_f64(a: Option<f64>, b: Option<f64>) -> bool {
+  match (a, b) {
+    (Some(a), Some(b)) => a == b || (a.is_nan() && b.is_nan()),
+    (None, None) => true,
+    _ => false,
+  }
+}
+
+/// Names (as in JS, e.g. `"doubleClickTime"`) of the fields that differ between
+/// two settings snapshots. A value appearing or disappearing counts as a change;
+/// two `NaN` readings do not.
+#[napi]
+pub fn diff_system_settings(a: SystemSettingsJs, b: SystemSettingsJs) -> Vec<String> {
+  let changes = [
+    (
+      "keyboardRepeatRate",
+      a.keyboard_repeat_rate == b.keyboard_repeat_rate,
+    ),
+    (
+      "keyboardRepeatDelay",
+      a.keyboard_repeat_delay == b.keyboard_repeat_delay,
+    ),
+    (
+      "mouseSensitivity",
+      same_f64(a.mouse_sensitivity, b.mouse_sensitivity),
+    ),
+    (
+      "mouseAcceleration",
+      same_f64(a.mouse_acceleration, b.mouse_acceleration),
+    ),
+    (
+      "mouseAccelerationThreshold",
+      same_f64(
+        a.mouse_acceleration_threshold,
+        b.mouse_acceleration_threshold,
+      ),
+    ),
+    (
+      "doubleClickTime",
+      a.double_click_time == b.double_click_time,
+    ),
+    ("keyboardLayout", a.keyboard_layout == b.keyboard_layout),
+  ];
+  changes
+    .iter()
+    .filter(|(_, same)| !same)
+    .map(|(name, _)| name.to_string())
+    .collect()
+}
+
+/// Get displays, primary display id, virtual desktop bounds and cursor position
+/// in one call. Everything comes from a single display enumeration, so the
+/// fields are consistent with each other even across a reconfiguration.
+#[napi]
+pub fn get_display_snapshot() -> Result<DisplaySnapshotJs> {
+  let infos = displays().map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to get displays: {}", e),
+    )
+  })?;
+  let (x, y) = mouse_position().map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to get mouse position: {}", e),
+    )
+  })?;
+
+  let primary = infos
+    .iter()
+    .find(|info| info.is_primary)
+    .or_else(|| infos.first())
+    .ok_or_else(|| Error::new(Status::GenericFailure, "Failed to get displays: none found"))?;
+  let (mut left, mut top) = (f64::MAX, f64::MAX);
+  let (mut right, mut bottom) = (f64::MIN, f64::MIN);
+  for info in &infos {
+    left = left.min(info.bounds.x);
+    top = top.min(info.bounds.y);
+    right = right.max(info.bounds.x + info.bounds.width);
+    bottom = bottom.max(info.bounds.y + info.bounds.height);
+  }
+
+  Ok(DisplaySnapshotJs {
+    primary_id: primary.id,
+    displays: ordered_displays(&infos),
+    virtual_bounds: RectJs {
+      x: left,
+      y: top,
+      width: right - left,
+      height: bottom - top,
+    },
+    cursor: PointJs { x, y },
+  })
+}
+
+// ============================================================================
+// Permissions
+// ============================================================================
+
+/// Whether the process has the Accessibility permission, which macOS requires
+/// for simulating input. Always `true` on Windows and Linux.
+#[napi]
+pub fn check_accessibility_permission() -> bool {
+  platform::accessibility_granted()
+}
+
+/// Whether the process has the Input Monitoring permission, which macOS
+/// requires for hooks (`startListen`, `InputHook`) to see key events. Checked
+/// separately from `checkAccessibilityPermission` because the two are granted
+/// independently. Never prompts. Always `true` on Windows and Linux.
+#[napi]
+pub fn check_input_monitoring_permission() -> bool {
+  platform::input_monitoring_granted()
+}
+
+// ============================================================================
+// Focused Window
+// ============================================================================
+
+/// The currently focused window, as reported by `getFocusedWindow()`.
+#[napi(object)]
+pub struct FocusedWindowJs {
+  pub title: String,
+  pub bounds: RectJs,
+  pub process_name: Option<String>,
+  pub process_id: Option<u32>,
+  /// Native window handle (an `HWND` on Windows), for `simulateKeyToWindow`
+  /// and `simulateClickToWindow`.
+  pub handle: Option<f64>,
+}
+
+/// Get the currently focused window's title, bounds and owning process.
+///
+/// This is best-effort: it is currently implemented on Windows only, and returns
+/// `None` on macOS and Linux (including Wayland, which does not expose other
+/// clients' windows), or when the platform refuses the query for lack of permissions.
+#[napi]
+pub fn get_focused_window() -> Option<FocusedWindowJs> {
+  platform::focused_window().map(|w| FocusedWindowJs {
+    title: w.title,
+    bounds: RectJs {
+      x: w.x,
+      y: w.y,
+      width: w.width,
+      height: w.height,
+    },
+    process_name: w.process_name,
+    process_id: w.process_id,
+    handle: w.handle.map(|h| h as f64),
+  })
+}
+
+/// The display sharing the most area with the focused window. Falls back to
+/// the primary display when there is no focused window (see
+/// `getFocusedWindow`) or it is entirely off-screen, and returns `null` only
+/// when displays cannot be listed.
+#[napi]
+pub fn get_display_for_focused_window() -> Option<DisplayInfoJs> {
+  let displays = ordered_displays(&displays().ok()?);
+  let overlap = |a: &RectJs, b: &RectJs| {
+    let w = (a.x + a.width).min(b.x + b.width) - a.x.max(b.x);
+    let h = (a.y + a.height).min(b.y + b.height) - a.y.max(b.y);
+    w.max(0.0) * h.max(0.0)
+  };
+  let best = platform::focused_window().and_then(|w| {
+    let window = RectJs {
+      x: w.x,
+      y: w.y,
+      width: w.width,
+      height: w.height,
+    };
+    displays
+      .iter()
+      .map(|d| (overlap(&d.bounds, &window), d.index))
+      .filter(|&(area, _)| area > 0.0)
+      .max_by(|a, b| a.0.total_cmp(&b.0))
+      .map(|(_, index)| index)
+  });
+  // Primary sorts first (see `ordered_displays`)
+  displays.into_iter().nth(best.unwrap_or(0) as usize)
+}
+
+/// Toggle state of the lock keys (whether the indicator is on, not whether the
+/// key is held).
+#[napi(object)]
+pub struct LockStateJs {
+  pub caps_lock: bool,
+  pub num_lock: bool,
+  pub scroll_lock: bool,
+}
+
+/// Query the OS for the current Caps/Num/Scroll Lock toggle state.
+///
+/// Implemented on Windows, macOS (which has no Num Lock or Scroll Lock, so
+/// those are always `false`) and Linux under X11, where Num Lock and Scroll
+/// Lock read as off unless the keymap binds them to a modifier. Returns `None`
+/// when no X display can be opened.
+#[napi]
+pub fn get_lock_state() -> Option<LockStateJs> {
+  platform::lock_state().map(|l| LockStateJs {
+    caps_lock: l.caps_lock,
+    num_lock: l.num_lock,
+    scroll_lock: l.scroll_lock,
+  })
+}
+
+// ============================================================================
+// Secure Input Guard (see setRequireForegroundApp)
+// ============================================================================
+
+static REQUIRE_FOREGROUND_APP: AtomicBool = AtomicBool::new(false);
+
+/// Lowercased process names that own secure prompts on Windows: UAC, the logon
+/// and lock screens and credential prompts.
+const SECURE_PROCESSES: &[&str] = &[
+  "consent.exe",
+  "logonui.exe",
+  "lockapp.exe",
+  "credentialuibroker.exe",
+];
+
+/// The foreground window as last seen by `secure_context_guard`, so typing a
+/// long string doesn't look it up once per character.
+static SECURE_FOCUS_CACHE: Mutex<FocusCache> = Mutex::new(FocusCache {
+  at: None,
+  window: None,
+});
+
+/// With `setRequireForegroundApp(true)`, refuse input while a secure prompt
+/// has focus. The foreground window is reused for up to `FOCUS_CACHE_MS`.
+fn secure_context_guard() -> std::result::Result<(), String> {
+  if !REQUIRE_FOREGROUND_APP.load(Ordering::Relaxed) {
+    return Ok(());
+  }
+  let mut cache = SECURE_FOCUS_CACHE.lock().unwrap();
+  if cache
+    .at
+    .is_none_or(|at| at.elapsed() >= Duration::from_millis(FOCUS_CACHE_MS))
+  {
+    cache.window = platform::focused_window();
+    cache.at = Some(Instant::now());
+  }
+  match &cache.window {
+    Some(window) => match &window.process_name {
+      Some(name) if SECURE_PROCESSES.contains(&name.to_lowercase().as_str()) => Err(format!(
+        "[SecureContext] refusing to send input to {}",
+        name
+      )),
+      _ => Ok(()),
+    },
+    // The secure desktop (UAC, lock screen) has no foreground window we can see
+    None if cfg!(target_os = "windows") => {
+      Err("[SecureContext] refusing to send input with no foreground window".to_string())
+    }
+    None => Ok(()),
+  }
+}
+
+/// `secure_context_guard` for callers returning a napi error.
+fn check_secure_context() -> Result<()> {
+  secure_context_guard().map_err(|e| Error::new(Status::GenericFailure, e))
+}
+
+/// Refuse key presses, clicks and typing while the focused window belongs to a
+/// secure system prompt (UAC, logon/lock screen, credential prompts), including
+/// input posted with `simulateKeyToWindow`/`simulateClickToWindow`. Refused
+/// calls throw an error containing `[SecureContext]`. Moves, scrolls and
+/// releases are never refused, so held input can always be let go. Off by
+/// default.
+///
+/// Detection uses `getFocusedWindow`, so it only works on Windows; there,
+/// having no foreground window at all (the secure desktop) also counts as a
+/// secure prompt. The foreground window is looked up at most every 100 ms, so
+/// a prompt may take that long to be noticed.
+#[napi]
+pub fn set_require_foreground_app(enabled: bool) {
+  REQUIRE_FOREGROUND_APP.store(enabled, Ordering::Relaxed);
+  SECURE_FOCUS_CACHE.lock().unwrap().at = None;
+}
+
+// ============================================================================
+// Input Blocking (see blockUserInput)
+// ============================================================================
+
+const DEFAULT_BLOCK_MS: u32 = 10_000;
+const MAX_BLOCK_MS: u32 = 60_000;
+
+/// Deadline of the active block, `None` when input is not blocked. The
+/// blocking thread owns the platform block and releases it once the deadline
+/// passes, so shortening the deadline is how `unblockUserInput` ends it.
+static INPUT_BLOCK: Mutex<Option<Instant>> = Mutex::new(None);
+static INPUT_BLOCK_CHANGED: Condvar = Condvar::new();
+
+/// Hold the platform block until the deadline in `INPUT_BLOCK` passes.
+fn hold_input_block() {
+  let mut deadline = INPUT_BLOCK.lock().unwrap();
+  while let Some(until) = *deadline {
+    let now = Instant::now();
+    if now >= until {
+      break;
+    }
+    deadline = INPUT_BLOCK_CHANGED
+      .wait_timeout(deadline, until - now)
+      .unwrap()
+      .0;
+  }
+  let _ = platform::block_input(false);
+  *deadline = None;
+  INPUT_BLOCK_CHANGED.notify_all();
+}
+
+/// Block keyboard and mouse input from real devices so the user can't
+/// interfere with a running script; simulated input still goes through. The
+/// block lifts by itself after `timeoutMs` (default 10000, at most 60000), so
+/// a crashed script can't lock the machine. Calling again while blocked
+/// resets the deadline.
+///
+/// Windows only, and the process must be elevated (run as Administrator).
+/// Ctrl+Alt+Del always lifts the block, and Windows lifts it if the process
+/// exits. Elsewhere this throws an `[Unsupported]` error.
+#[napi]
+pub fn block_user_input(timeout_ms: Option<u32>) -> Result<()> {
+  let timeout_ms = timeout_ms.unwrap_or(DEFAULT_BLOCK_MS);
+  if timeout_ms == 0 || timeout_ms > MAX_BLOCK_MS {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!("timeoutMs must be between 1 and {}", MAX_BLOCK_MS),
+    ));
+  }
+  let until = Instant::now() + Duration::from_millis(timeout_ms as u64);
+  {
+    let mut deadline = INPUT_BLOCK.lock().unwrap();
+    if deadline.is_some() {
+      *deadline = Some(until);
+      INPUT_BLOCK_CHANGED.notify_all();
+      return Ok(());
+    }
+    *deadline = Some(until);
+  }
+
+  // Windows only lets the blocking thread unblock, so a dedicated thread
+  // holds the block for its whole lifetime.
+  let (tx, rx) = mpsc::channel();
+  thread::spawn(move || {
+    let result = platform::block_input(true);
+    let blocked = result.is_ok();
+    let _ = tx.send(result);
+    if blocked {
+      hold_input_block();
+    }
+  });
+  match rx.recv() {
+    Ok(Ok(())) => Ok(()),
+    failure => {
+      *INPUT_BLOCK.lock().unwrap() = None;
+      INPUT_BLOCK_CHANGED.notify_all();
+      let reason = match failure {
+        Ok(Err(e)) => e,
+        _ => "the blocking thread exited".to_string(),
+      };
+      let code = if cfg!(target_os = "windows") {
+        "PermissionDenied"
+      } else {
+        "Unsupported"
+      };
+      Err(Error::new(
+        Status::GenericFailure,
+        format!("[{}] Failed to block user input: {}", code, reason),
+      ))
+    }
+  }
+}
+
+/// Lift a block started by `blockUserInput` and wait until real input is
+/// accepted again. Does nothing if input is not blocked.
+#[napi]
+pub fn unblock_user_input() {
+  let mut deadline = INPUT_BLOCK.lock().unwrap();
+  if deadline.is_none() {
+    return;
+  }
+  *deadline = Some(Instant::now());
+  INPUT_BLOCK_CHANGED.notify_all();
+  while deadline.is_some() {
+    deadline = INPUT_BLOCK_CHANGED.wait(deadline).unwrap();
+  }
+}
+
+// ============================================================================
+// Simulation Queue (see setSimulationQueue)
+// ============================================================================
+
+type SimulationJob = Box<dyn FnOnce() -> Result<()> + Send>;
+
+struct SimulationQueue {
+  /// Only calls from the thread that enabled the queue (the JS thread) are
+  /// queued; helpers already running on worker threads simulate directly.
+  js_thread: ThreadId,
+  sender: mpsc::Sender<SimulationJob>,
+}
+
+/// Jobs sent but not finished, and the first failure since the last drain.
+struct SimulationProgress {
+  pending: usize,
+  error: Option<String>,
+}
+
+static SIMULATION_QUEUE: Mutex<Option<SimulationQueue>> = Mutex::new(None);
+static SIMULATION_PROGRESS: Mutex<SimulationProgress> = Mutex::new(SimulationProgress {
+  pending: 0,
+  error: None,
+});
+static SIMULATION_DRAINED: Condvar = Condvar::new();
+
+/// Run `job` now, or hand it to the queue worker when the queue is on.
+fn queued(job: impl FnOnce() -> Result<()> + Send + 'static) -> Result<()> {
+  let sender = SIMULATION_QUEUE
+    .lock()
+    .unwrap()
+    .as_ref()
+    .filter(|q| q.js_thread == thread::current().id())
+    .map(|q| q.sender.clone());
+  // The queue lock is released before running inline, so a slow job can't
+  // block `setSimulationQueue` or other callers
+  let Some(sender) = sender else {
+    return job();
+  };
+  SIMULATION_PROGRESS.lock().unwrap().pending += 1;
+  if sender.send(Box::new(job)).is_err() {
+    let mut progress = SIMULATION_PROGRESS.lock().unwrap();
+    progress.pending -= 1;
+    if progress.pending == 0 {
+      SIMULATION_DRAINED.notify_all();
+    }
+    return Err(Error::new(
+      Status::GenericFailure,
+      "Simulation queue worker has stopped",
+    ));
+  }
+  Ok(())
+}
+
+fn run_simulation_queue(jobs: mpsc::Receiver<SimulationJob>) {
+  for job in jobs {
+    // A panicking job must not kill the worker and leave `pending` stuck
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(job)).unwrap_or_else(|_| {
+      Err(Error::new(
+        Status::GenericFailure,
+        "Queued simulation panicked",
+      ))
+    });
+    let mut progress = SIMULATION_PROGRESS.lock().unwrap();
+    progress.pending -= 1;
+    if let Err(e) = result {
+      log_event("warn", || format!("Queued simulation failed: {}", e.reason));
+      progress.error.get_or_insert(e.reason);
+    }
+    if progress.pending == 0 {
+      SIMULATION_DRAINED.notify_all();
+    }
+  }
+}
+
+/// Run the basic `simulate*` calls (mouse move/press/release/click, key
+/// press/release/tap, `simulateKeysPress`/`Release`, `simulateKeyTapIsolated`,
+/// `simulateKeyCombo`, `typeText`, scan code input) on a dedicated thread, in
+/// call order, so a slow injection never blocks the JS thread. While enabled
+/// those calls return as soon as the input is queued; argument validation
+/// still throws immediately, but injection failures are reported by
+/// `awaitSimulationDrained()`. Other simulation functions are not queued, so
+/// await the drain before mixing them in. Disabling lets queued input finish.
+#[napi]
+pub fn set_simulation_queue(enabled: bool) {
+  let mut queue = SIMULATION_QUEUE.lock().unwrap();
+  if !enabled {
+    *queue = None;
+    return;
+  }
+  if queue.is_none() {
+    let (sender, jobs) = mpsc::channel();
+    thread::spawn(move || run_simulation_queue(jobs));
+    *queue = Some(SimulationQueue {
+      js_thread: thread::current().id(),
+      sender,
+    });
+  }
+}
+
+pub struct SimulationDrainTask;
+
+#[napi]
+impl Task for SimulationDrainTask {
+  type Output = ();
+  type JsValue = ();
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    let mut progress = SIMULATION_PROGRESS.lock().unwrap();
+    while progress.pending > 0 {
+      progress = SIMULATION_DRAINED.wait(progress).unwrap();
+    }
+    match progress.error.take() {
+      Some(reason) => Err(Error::new(Status::GenericFailure, reason)),
+      None => Ok(()),
+    }
+  }
+
+  fn resolve(&mut self, _env: Env, _output: Self::Output) -> Result<Self::JsValue> {
+    Ok(())
+  }
+}
+
+/// Resolve once everything queued by `setSimulationQueue` has run. Rejects
+/// with the first failure since the previous drain, if any (later queued
+/// input still ran). Resolves immediately when nothing is queued.
+#[napi]
+pub fn await_simulation_drained() -> AsyncTask<SimulationDrainTask> {
+  AsyncTask::new(SimulationDrainTask)
+}
+
+// ============================================================================
+// Event Simulation
+// ============================================================================
+
+/// Reject a `deviceId` on a simulation call: no supported platform lets
+/// injected input name its source device.
+fn check_device_id(device_id: Option<u32>) -> Result<()> {
+  match device_id {
+    Some(id) => Err(Error::new(
+      Status::GenericFailure,
+      format!(
+        "[Unsupported] deviceId {}: simulated input cannot name a source device on this platform",
+        id
+      ),
+    )),
+    None => Ok(()),
+  }
+}
+
+/// Move the mouse to absolute coordinates. `deviceId` is accepted for
+/// forward compatibility but throws `[Unsupported]` when set.
+#[napi]
+pub fn simulate_mouse_move(x: f64, y: f64, device_id: Option<u32>) -> Result<()> {
+  check_device_id(device_id)?;
+  queued(move || move_mouse(x, y))
+}
+
+/// Move the mouse now, bypassing `setSimulationQueue`; for helpers that act on
+/// the result straight away.
+fn move_mouse(x: f64, y: f64) -> Result<()> {
+  mouse_move(x, y).map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to move mouse: {}", e),
+    )
+  })
+}
+
+/// Result of `simulateMouseMoveClamped`.
+#[napi(object)]
+pub struct ClampedMoveJs {
+  /// Where the cursor was actually moved.
+  pub x: f64,
+  pub y: f64,
+  /// `true` if the requested point was off every display and had to be redirected.
+  pub clamped: bool,
+}
+
+fn rect_contains(rect: &Rect, x: f64, y: f64) -> bool {
+  x >= rect.x && x < rect.x + rect.width && y >= rect.y && y < rect.y + rect.height
+}
+
+/// Nearest point to `(x, y)` that lies inside `rect`.
+fn clamp_to_rect(rect: &Rect, x: f64, y: f64) -> (f64, f64) {
+  (
+    x.clamp(rect.x, rect.x + (rect.width - 1.0).max(0.0)),
+    y.clamp(rect.y, rect.y + (rect.height - 1.0).max(0.0)),
+  )
+}
+
+/// Redirect `(x, y)` to the nearest point on the nearest rect in `layout`,
+/// unless it already lies on one. Nothing here assumes a (0, 0) origin, so
+/// displays left of or above the primary (negative `x`/`y`) work the same.
+fn clamp_to_layout(layout: &[Rect], x: f64, y: f64) -> ClampedMoveJs {
+  if layout.iter().any(|r| rect_contains(r, x, y)) {
+    return ClampedMoveJs {
+      x,
+      y,
+      clamped: false,
+    };
+  }
+  layout
+    .iter()
+    .map(|r| clamp_to_rect(r, x, y))
+    .min_by(|a, b| {
+      let da = (a.0 - x).powi(2) + (a.1 - y).powi(2);
+      let db = (b.0 - x).powi(2) + (b.1 - y).powi(2);
+      da.total_cmp(&db)
+    })
+    .map_or(
+      ClampedMoveJs {
+        x,
+        y,
+        clamped: false,
+      },
+      |(cx, cy)| ClampedMoveJs {
+        x: cx,
+        y: cy,
+        clamped: true,
+      },
+    )
+}
+
+/// Center pixel of `rect`, rounded down so it stays inside odd-sized rects.
+fn center_of(rect: &Rect) -> (f64, f64) {
+  (
+    (rect.x + rect.width / 2.0).floor(),
+    (rect.y + rect.height / 2.0).floor(),
+  )
+}
+
+/// Global-space distance between adjacent device pixels on a display. macOS
+/// reports positions in points, so a 2x display has half-point pixels;
+/// elsewhere the global space is already in device pixels.
+fn pixel_step(scale_factor: f64) -> f64 {
+  if cfg!(target_os = "macos") && scale_factor > 0.0 {
+    1.0 / scale_factor
+  } else {
+    1.0
+  }
+}
+
+/// Clamp `(x, y)` onto `layout` and snap it to the device-pixel grid of the
+/// display it lands on, rounding halves to even. `scales[i]` is the scale
+/// factor of `layout[i]`.
+fn normalize_in_layout(layout: &[Rect], scales: &[f64], x: f64, y: f64) -> (f64, f64) {
+  let clamped = clamp_to_layout(layout, x, y);
+  let Some((rect, scale)) = layout
+    .iter()
+    .zip(scales)
+    .find(|(rect, _)| rect_contains(rect, clamped.x, clamped.y))
+  else {
+    return (clamped.x, clamped.y);
+  };
+  let step = pixel_step(*scale);
+  let snap = |v: f64, origin: f64| origin + ((v - origin) / step).round_ties_even() * step;
+  clamp_to_rect(rect, snap(clamped.x, rect.x), snap(clamped.y, rect.y))
+}
+
+/// Convert a captured event position into the point `simulateMouseMove`
+/// will actually put the cursor on. On Windows the hook's physical pixels are
+/// first converted to this process's logical pixels, which differ on scaled
+/// monitors unless Node runs per-monitor DPI aware. Points off every display
+/// are then clamped as in `simulateMouseMoveClamped`, and the result is snapped
+/// to the device-pixel grid of its display. Moving to the result and reading
+/// `getMousePosition()` gives the same point, which is not guaranteed for the
+/// raw position on scaled displays or across a disconnected monitor.
+#[napi]
+pub fn normalize_coordinate_for_simulation(x: f64, y: f64) -> Result<PointJs> {
+  let (x, y) = platform::physical_to_logical(x, y);
+  let infos = displays().map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to get displays: {}", e),
+    )
+  })?;
+  let layout: Vec<Rect> = infos
+    .iter()
+    .map(|info| Rect {
+      x: info.bounds.x,
+      y: info.bounds.y,
+      width: info.bounds.width,
+      height: info.bounds.height,
+    })
+    .collect();
+  let scales: Vec<f64> = infos.iter().map(|info| info.scale_factor).collect();
+  let (x, y) = normalize_in_layout(&layout, &scales, x, y);
+  Ok(PointJs { x, y })
+}
+
+/// Move the mouse, redirecting points that fall outside every current display
+/// (e.g. coordinates from a monitor that has since been disconnected) to the
+/// nearest point on the nearest display.
+#[napi]
+pub fn simulate_mouse_move_clamped(x: f64, y: f64) -> Result<ClampedMoveJs> {
+  let infos = displays().map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to get displays: {}", e),
+    )
+  })?;
+  let layout: Vec<Rect> = infos.into_iter().map(|d| d.bounds).collect();
+
+  let target = clamp_to_layout(&layout, x, y);
+  move_mouse(target.x, target.y)?;
+  Ok(target)
+}
+
+/// Move the mouse to the center of the display with the given `id` (see
+/// `getDisplays`), wherever it sits in the global layout. Returns the point moved to.
+#[napi]
+pub fn simulate_mouse_move_to_display_center(display_id: u32) -> Result<PointJs> {
+  let display = display_by_id(display_id)?;
+  let (x, y) = center_of(&display.bounds);
+  move_mouse(x, y)?;
+  Ok(PointJs { x, y })
+}
+
+/// Move the mouse to logical (point) coordinates relative to the top-left of
+/// display `displayId`: they are multiplied by that display's `scaleFactor`
+/// and offset by its origin. Returns the global point moved to.
+#[napi]
+pub fn simulate_mouse_move_logical(
+  display_id: u32,
+  logical_x: f64,
+  logical_y: f64,
+) -> Result<PointJs> {
+  let display = display_by_id(display_id)?;
+  let x = display.bounds.x + logical_x * display.scale_factor;
+  let y = display.bounds.y + logical_y * display.scale_factor;
+  move_mouse(x, y)?;
+  Ok(PointJs { x, y })
+}
+
+fn display_by_id(display_id: u32) -> Result<DisplayInfo> {
+  let infos = displays().map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to get displays: {}", e),
+    )
+  })?;
+  infos
+    .into_iter()
+    .find(|d| d.id == display_id)
+    .ok_or_else(|| {
+      Error::new(
+        Status::InvalidArg,
+        format!("No display with id {}", display_id),
+      )
+    })
+}
+
+/// Chains relative cursor moves against its own tracked position instead of
+/// re-reading the OS cursor before each step, so paths stay deterministic even
+/// if the user nudges the mouse mid-gesture.
+///
+/// ```js
+/// const mover = new RelativeMover() // starts at the current cursor position
+/// mover.moveBy(10, 0)
+/// mover.moveBy(0, 10)
+/// mover.sync() // adopt the real position again
+/// ```
+#[napi]
+pub struct RelativeMover {
+  position: Mutex<(f64, f64)>,
+}
+
+#[napi]
+impl RelativeMover {
+  /// Start tracking from `(x, y)`, or from the current cursor position when
+  /// omitted. Nothing is moved until `moveBy`.
+  #[napi(constructor)]
+  pub fn new(x: Option<f64>, y: Option<f64>) -> Result<Self> {
+    let start = match (x, y) {
+      (Some(x), Some(y)) => (x, y),
+      _ => current_mouse_position()?,
+    };
+    Ok(Self {
+      position: Mutex::new(start),
+    })
+  }
+
+  /// Move by `(dx, dy)` from the tracked position (not the live cursor) and
+  /// return the new tracked position. If the move fails the position is kept.
+  #[napi]
+  pub fn move_by(&self, dx: f64, dy: f64) -> Result<PointJs> {
+    let mut position = self.position.lock().unwrap();
+    let (x, y) = (position.0 + dx, position.1 + dy);
+    move_mouse(x, y)?;
+    *position = (x, y);
+    Ok(PointJs { x, y })
+  }
+
+  /// Reset the tracked position to where the cursor really is, and return it.
+  #[napi]
+  pub fn sync(&self) -> Result<PointJs> {
+    let (x, y) = current_mouse_position()?;
+    *self.position.lock().unwrap() = (x, y);
+    Ok(PointJs { x, y })
+  }
+
+  /// The tracked position, i.e. where the last `moveBy` put the cursor.
+  #[napi(getter)]
+  pub fn position(&self) -> PointJs {
+    let (x, y) = *self.position.lock().unwrap();
+    PointJs { x, y }
+  }
+}
+
+fn current_mouse_position() -> Result<(f64, f64)> {
+  mouse_position().map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to get mouse position: {}", e),
+    )
+  })
+}
+
+/// Keys and buttons pressed through `simulateKeyPress`/`simulateMousePress` and
+/// not yet released, so they can be let go if the app stops or exits mid-way.
+struct HeldInput {
+  keys: Vec<Key>,
+  buttons: Vec<Button>,
+}
+
+static HELD_INPUT: Mutex<HeldInput> = Mutex::new(HeldInput {
+  keys: Vec::new(),
+  buttons: Vec::new(),
+});
+static EXIT_RELEASE_REGISTERED: AtomicBool = AtomicBool::new(false);
+static RELEASE_ON_STOP: AtomicBool = AtomicBool::new(false);
+
+/// Release held input when the Node environment tears down (process exit).
+fn register_exit_release(env: &mut Env) {
+  if !EXIT_RELEASE_REGISTERED.swap(true, Ordering::Relaxed) {
+    let _ = env.add_env_cleanup_hook((), |_| {
+      let _ = release_held_input();
+    });
+  }
+}
+
+fn release_held_input() -> Result<()> {
+  let (keys, buttons) = {
+    let mut held = HELD_INPUT.lock().unwrap();
+    (
+      std::mem::take(&mut held.keys),
+      std::mem::take(&mut held.buttons),
+    )
+  };
+  if !keys.is_empty() || !buttons.is_empty() {
+    log_event("info", || {
+      format!(
+        "Releasing {} held key(s) and {} held button(s)",
+        keys.len(),
+        buttons.len()
+      )
+    });
+  }
+
+  let mut result = Ok(());
+  for key in keys.into_iter().rev() {
+    if let Err(e) = key_release(key) {
+      if result.is_ok() {
+        result = Err(Error::new(
+          Status::GenericFailure,
+          format!("Failed to release key: {}", e),
+        ));
+      }
+    }
+  }
+  for button in buttons.into_iter().rev() {
+    if let Err(e) = mouse_release(button) {
+      if result.is_ok() {
+        result = Err(Error::new(
+          Status::GenericFailure,
+          format!("Failed to release mouse button: {}", e),
+        ));
+      }
+    }
+  }
+  result
+}
+
+/// `release_held_input`, if `setReleaseSimulatedOnStop` asked for it.
+fn release_held_input_on_stop() -> Result<()> {
+  if !RELEASE_ON_STOP.load(Ordering::Relaxed) {
+    return Ok(());
+  }
+  release_held_input()
+}
+
+/// Release every key and mouse button this process pressed via the simulation
+/// API without releasing. Also runs automatically on `shutdown()` and when the
+/// Node environment exits normally, and on every hook stop with
+/// `setReleaseSimulatedOnStop(true)`.
+#[napi]
+pub fn release_all_simulated() -> Result<()> {
+  release_held_input()
+}
+
+/// Also release held simulated input (see `releaseAllSimulated`) whenever a
+/// `startListen` or `InputHook` hook stops: `stop()`, an auto-stop, or a
+/// running hook being garbage-collected. Off by default, since the hook being
+/// stopped need not belong to the automation holding the keys.
+#[napi]
+pub fn set_release_simulated_on_stop(enabled: bool) {
+  RELEASE_ON_STOP.store(enabled, Ordering::Relaxed);
+}
+
+/// Press `button` and record it in `HELD_INPUT`.
+fn press_held_button(button: Button) -> Result<()> {
+  check_secure_context()?;
+  mouse_press(button).map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to press mouse button: {}", e),
+    )
+  })?;
+  let mut held = HELD_INPUT.lock().unwrap();
+  if !held
+    .buttons
+    .iter()
+    .any(|&b| button_id(b) == button_id(button))
+  {
+    held.buttons.push(button);
+  }
+  Ok(())
+}
+
+/// Release `button` and drop it from `HELD_INPUT`.
+fn release_held_button(button: Button) -> Result<()> {
+  HELD_INPUT
+    .lock()
+    .unwrap()
+    .buttons
+    .retain(|&b| button_id(b) != button_id(button));
+  mouse_release(button).map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to release mouse button: {}", e),
+    )
+  })
+}
+
+/// Press a mouse button. `deviceId` as on `simulateMouseMove`.
+#[napi]
+pub fn simulate_mouse_press(mut env: Env, button: ButtonJs, device_id: Option<u32>) -> Result<()> {
+  check_device_id(device_id)?;
+  register_exit_release(&mut env);
+  let button = Button::from(button);
+  queued(move || press_held_button(button))
+}
+
+/// Release a mouse button. `deviceId` as on `simulateMouseMove`.
+#[napi]
+pub fn simulate_mouse_release(button: ButtonJs, device_id: Option<u32>) -> Result<()> {
+  check_device_id(device_id)?;
+  let button = Button::from(button);
+  queued(move || release_held_button(button))
+}
+
+/// Click a mouse button (press + release). `deviceId` as on
+/// `simulateMouseMove`.
+#[napi]
+pub fn simulate_mouse_click(button: ButtonJs, device_id: Option<u32>) -> Result<()> {
+  check_device_id(device_id)?;
+  let button = Button::from(button);
+  queued(move || {
+    check_secure_context()?;
+    mouse_click(button).map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to click mouse button: {}", e),
+      )
+    })
+  })
+}
+
+/// Press `key` and record it in `HELD_INPUT`.
+fn press_held_key(key: Key) -> Result<()> {
+  key_press(key).map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to press key: {}", e),
+    )
+  })?;
+  let mut held = HELD_INPUT.lock().unwrap();
+  if !held.keys.iter().any(|&k| key_id(k) == key_id(key)) {
+    held.keys.push(key);
+  }
+  Ok(())
+}
+
+/// Release `key` and drop it from `HELD_INPUT`.
+fn release_held_key(key: Key) -> Result<()> {
+  HELD_INPUT
+    .lock()
+    .unwrap()
+    .keys
+    .retain(|&k| key_id(k) != key_id(key));
+  key_release(key).map_err(|e| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to release key: {}", e),
+    )
+  })
+}
+
+/// Press a key. `deviceId` as on `simulateMouseMove`.
+#[napi]
+pub fn simulate_key_press(mut env: Env, key: KeyJs, device_id: Option<u32>) -> Result<()> {
+  check_device_id(device_id)?;
+  register_exit_release(&mut env);
+  let key = Key::from(key);
+  queued(move || press_held_key(key))
+}
+
+/// Release a key. `deviceId` as on `simulateMouseMove`.
+#[napi]
+pub fn simulate_key_release(key: KeyJs, device_id: Option<u32>) -> Result<()> {
+  check_device_id(device_id)?;
+  let key = Key::from(key);
+  queued(move || release_held_key(key))
+}
+
+/// Press and hold several keys at once, in array order. Unlike
+/// `simulateKeyCombo` nothing is released until `simulateKeysRelease` (or
+/// `releaseAllSimulated`). If a press fails, keys this call newly pressed are
+/// released again before the error is returned; keys that were already held
+/// stay held.
+#[napi]
+pub fn simulate_keys_press(mut env: Env, keys: Vec<KeyJs>) -> Result<()> {
+  let keys: Vec<Key> = keys.into_iter().map(Key::from).collect();
+  register_exit_release(&mut env);
+  queued(move || press_keys(&keys))
+}
+
+fn press_keys(keys: &[Key]) -> Result<()> {
+  // Only what this call pressed, so a failure leaves earlier holds alone
+  let mut pressed = Vec::new();
+  for &key in keys {
+    let was_held = HELD_INPUT
+      .lock()
+      .unwrap()
+      .keys
+      .iter()
+      .any(|&k| key_id(k) == key_id(key));
+    if let Err(e) = press_held_key(key) {
+      for &key in pressed.iter().rev() {
+        let _ = release_held_key(key);
+      }
+      return Err(e);
+    }
+    if !was_held {
+      pressed.push(key);
+    }
+  }
+  Ok(())
+}
+
+/// Release several keys, in reverse array order, so passing the same array
+/// given to `simulateKeysPress` unwinds the chord. Every key is attempted; the
+/// first failure is returned.
+#[napi]
+pub fn simulate_keys_release(keys: Vec<KeyJs>) -> Result<()> {
+  let keys: Vec<Key> = keys.into_iter().map(Key::from).collect();
+  queued(move || release_keys(&keys))
+}
+
+fn release_keys(keys: &[Key]) -> Result<()> {
+  let mut result = Ok(());
+  for &key in keys.iter().rev() {
+    if let Err(e) = release_held_key(key) {
+      if result.is_ok() {
+        result = Err(e);
+      }
+    }
+  }
+  result
+}
+
+/// Native code for consumer-control keys (media, volume, browser, launch),
+/// which are injected through `platform::send_consumer_key` rather than
+/// monio. On Windows this is the virtual-key code: a scan code for these keys
+/// has no virtual-key mapping, so the shell never sees a media or browser
+/// command.
+#[cfg(target_os = "windows")]
+fn consumer_key_code(key: Key) -> Option<u16> {
+  Some(match key {
+    Key::BrowserBack => 0xA6,
+    Key::BrowserForward => 0xA7,
+    Key::BrowserRefresh => 0xA8,
+    Key::BrowserStop => 0xA9,
+    Key::BrowserSearch => 0xAA,
+    Key::BrowserFavorites => 0xAB,
+    Key::BrowserHome => 0xAC,
+    Key::VolumeMute => 0xAD,
+    Key::VolumeDown => 0xAE,
+    Key::VolumeUp => 0xAF,
+    Key::MediaNext => 0xB0,
+    Key::MediaPrevious => 0xB1,
+    Key::MediaStop => 0xB2,
+    Key::MediaPlayPause => 0xB3,
+    Key::LaunchMail => 0xB4,
+    Key::LaunchApp1 => 0xB6,
+    Key::LaunchApp2 => 0xB7,
+    _ => return None,
+  })
+}
+
+/// On macOS this is the `NX_KEYTYPE_*` code, sent as a system-defined event:
+/// the system ignores synthesized keycodes for these keys. macOS has no
+/// system-defined code for MediaStop, browser or launch keys.
+#[cfg(target_os = "macos")]
+fn consumer_key_code(key: Key) -> Option<u16> {
+  Some(match key {
+    Key::VolumeUp => 0,
+    Key::VolumeDown => 1,
+    Key::VolumeMute => 7,
+    Key::MediaPlayPause => 16,
+    Key::MediaNext => 17,
+    Key::MediaPrevious => 18,
+    _ => return None,
+  })
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn consumer_key_code(_key: Key) -> Option<u16> {
+  None
+}
+
+/// Press `key`, routing consumer-control keys through `consumer_key_code`.
+/// Every simulated key press in this crate goes through here.
+fn key_press(key: Key) -> std::result::Result<(), String> {
+  secure_context_guard()?;
+  match consumer_key_code(key) {
+    Some(code) => platform::send_consumer_key(code, false),
+    None => monio::key_press(key).map_err(|e| e.to_string()),
+  }
+}
+
+/// Release counterpart of `key_press`.
+fn key_release(key: Key) -> std::result::Result<(), String> {
+  match consumer_key_code(key) {
+    Some(code) => platform::send_consumer_key(code, true),
+    None => monio::key_release(key).map_err(|e| e.to_string()),
+  }
+}
+
+/// Tap counterpart of `key_press`.
+fn key_tap(key: Key) -> std::result::Result<(), String> {
+  secure_context_guard()?;
+  match consumer_key_code(key) {
+    Some(code) => {
+      platform::send_consumer_key(code, false)?;
+      platform::send_consumer_key(code, true)
+    }
+    None => monio::key_tap(key).map_err(|e| e.to_string()),
+  }
+}
+
+/// Whether `key_press`/`key_tap` can produce `key` on this OS.
+///
+/// This is a static per-platform allowlist: support can't be probed without
+/// actually sending input. Windows (SendInput, by virtual key for consumer
+/// keys) and Linux (XTest) can inject every named key. macOS injects through
+/// virtual keycodes, plus system-defined events for volume, play/pause, next
+/// and previous; there is no way to send MediaStop, browser and launch keys,
+/// ContextMenu, Insert, PrintScreen, ScrollLock, Pause, NumLock or F21–F24.
+/// `Unknown` is never simulatable.
+fn key_simulatable(key: &KeyJs) -> bool {
+  match key {
+    KeyJs::Unknown => false,
+    #[cfg(target_os = "macos")]
+    KeyJs::MediaStop
+    | KeyJs::BrowserBack
+    | KeyJs::BrowserForward
+    | KeyJs::BrowserRefresh
+    | KeyJs::BrowserStop
+    | KeyJs::BrowserSearch
+    | KeyJs::BrowserFavorites
+    | KeyJs::BrowserHome
+    | KeyJs::LaunchMail
+    | KeyJs::LaunchApp1
+    | KeyJs::LaunchApp2
+    | KeyJs::ContextMenu
+    | KeyJs::Insert
+    | KeyJs::PrintScreen
+    | KeyJs::ScrollLock
+    | KeyJs::Pause
+    | KeyJs::NumLock
+    | KeyJs::F21
+    | KeyJs::F22
+    | KeyJs::F23
+    | KeyJs::F24 => false,
+    _ => true,
+  }
+}
+
+/// Check whether a key can be simulated on the current OS. See
+/// `getSimulatableKeys` for how this is determined.
+#[napi]
+pub fn can_simulate_key(key: KeyJs) -> bool {
+  key_simulatable(&key)
+}
+
+/// All keys that `simulateKey*` can produce on the current OS. Based on a
+/// per-platform allowlist of what the OS injection API can express (Windows
+/// and Linux: every named key; macOS: all but MediaStop, browser and launch
+/// keys, ContextMenu, Insert, PrintScreen, ScrollLock, Pause, NumLock and
+/// F21–F24), not on probing, which would require sending input.
+#[napi]
+pub fn get_simulatable_keys() -> Vec<KeyJs> {
+  (0..KEY_JS_COUNT)
+    .filter_map(key_from_i32)
+    .filter(key_simulatable)
+    .collect()
+}
+
+/// Tap a key (press + release). `deviceId` as on `simulateMouseMove`.
+#[napi]
+pub fn simulate_key_tap(key: KeyJs, device_id: Option<u32>) -> Result<()> {
+  check_device_id(device_id)?;
+  let key = Key::from(key);
+  queued(move || {
+    key_tap(key)
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to tap key: {}", e)))
+  })
+}
+
+/// A hook's physically held modifiers (`modifier_key_bit` bits).
+type ModifierTracker = Arc<AtomicU32>;
+
+/// Each hook's `ModifierTracker` with its native hook slot, as `startListen`
+/// and `InputHook` register them. A hook zeroes its own bits when it stops or
+/// is disabled, and only hooks whose native hook is running are consulted, so
+/// a hook that died without saying so can't leave stale modifiers behind.
+#[allow(clippy::type_complexity)]
+static MODIFIER_TRACKERS: Mutex<Vec<(Weak<AtomicU32>, Weak<Mutex<Option<Hook>>>)>> =
+  Mutex::new(Vec::new());
+
+/// A new, empty tracker for the hook in `hook`, listed in `MODIFIER_TRACKERS`
+/// until either is dropped.
+fn register_modifier_tracker(hook: &Arc<Mutex<Option<Hook>>>) -> ModifierTracker {
+  let tracker = Arc::new(AtomicU32::new(0));
+  let mut trackers = MODIFIER_TRACKERS.lock().unwrap();
+  trackers.retain(|(t, h)| t.strong_count() > 0 && h.strong_count() > 0);
+  trackers.push((Arc::downgrade(&tracker), Arc::downgrade(hook)));
+  tracker
+}
+
+/// Update a hook's `MODIFIER_TRACKERS` entry from a native event.
+fn track_modifier(held: &AtomicU32, event: &Event) {
+  match (event.event_type, &event.keyboard) {
+    (EventType::KeyPressed, Some(kb)) => {
+      held.fetch_or(modifier_key_bit(kb.key), Ordering::Relaxed);
+    }
+    (EventType::KeyReleased, Some(kb)) => {
+      held.fetch_and(!modifier_key_bit(kb.key), Ordering::Relaxed);
+    }
+    (EventType::HookDisabled, _) => held.store(0, Ordering::Relaxed),
+    _ => {}
+  }
+}
+
+/// Modifiers held on the physical keyboard, as observed by running hooks.
+fn physically_held_modifiers() -> Vec<Key> {
+  let trackers: Vec<_> = MODIFIER_TRACKERS
+    .lock()
+    .unwrap()
+    .iter()
+    .filter_map(|(t, h)| Some((t.upgrade()?, h.upgrade()?)))
+    .collect();
+  let bits = trackers
+    .iter()
+    .filter(|(_, hook)| hook.lock().unwrap().as_ref().is_some_and(Hook::is_running))
+    .fold(0, |bits, (t, _)| bits | t.load(Ordering::Relaxed));
+  [
+    KeyJs::ShiftLeft,
+    KeyJs::ControlLeft,
+    KeyJs::AltLeft,
+    KeyJs::MetaLeft,
+    KeyJs::ShiftRight,
+    KeyJs::ControlRight,
+    KeyJs::AltRight,
+    KeyJs::MetaRight,
+  ]
+  .into_iter()
+  .map(Key::from)
+  .filter(|&k| bits & modifier_key_bit(k) != 0)
+  .collect()
+}
+
+/// Tap a key without the user's physically held modifiers leaking into it:
+/// held modifiers are released, the key is tapped, and they are pressed again.
+///
+/// Modifier state comes from running hooks (`startListen` or `InputHook`);
+/// with no hook running nothing is known and this behaves like `simulateKeyTap`.
+/// Modifiers held via `simulateKeyPress` are left alone.
+#[napi]
+pub fn simulate_key_tap_isolated(key: KeyJs) -> Result<()> {
+  let key = Key::from(key);
+  queued(move || tap_key_isolated(key))
+}
+
+fn tap_key_isolated(key: Key) -> Result<()> {
+  let modifiers: Vec<Key> = {
+    let simulated = HELD_INPUT.lock().unwrap();
+    physically_held_modifiers()
+      .into_iter()
+      .filter(|&m| !simulated.keys.iter().any(|&k| key_id(k) == key_id(m)))
+      .collect()
+  };
+
+  let mut released = 0;
+  let mut result = Ok(());
+  for &modifier in &modifiers {
+    if let Err(e) = key_release(modifier) {
+      result = Err(Error::new(
+        Status::GenericFailure,
+        format!("Failed to release key: {}", e),
+      ));
+      break;
+    }
+    released += 1;
+  }
+  if result.is_ok() {
+    result = key_tap(key)
+      .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to tap key: {}", e)));
+  }
+  for &modifier in &modifiers[..released] {
+    if let Err(e) = key_press(modifier) {
+      if result.is_ok() {
+        result = Err(Error::new(
+          Status::GenericFailure,
+          format!("Failed to press key: {}", e),
+        ));
+      }
+    }
+  }
+  result
+}
+
+/// Cursor position and held modifiers, from `captureInputState`.
+#[napi(object)]
+pub struct InputStateJs {
+  pub mouse_position: PointJs,
+  pub pressed_modifiers: Vec<KeyJs>,
+}
+
+/// Modifiers held physically (as seen by running hooks) or via
+/// `simulateKeyPress`, without duplicates.
+fn pressed_modifiers() -> Vec<Key> {
+  let mut keys = physically_held_modifiers();
+  for &key in HELD_INPUT.lock().unwrap().keys.iter() {
+    if key.is_modifier() && !keys.iter().any(|&k| key_id(k) == key_id(key)) {
+      keys.push(key);
+    }
+  }
+  keys
+}
+
+/// Snapshot the cursor position and held modifiers, for `restoreInputState`.
+/// Physically held modifiers are only known while a hook is running (see
+/// `simulateKeyTapIsolated`).
+#[napi]
+pub fn capture_input_state() -> Result<InputStateJs> {
+  let (x, y) = current_mouse_position()?;
+  Ok(InputStateJs {
+    mouse_position: PointJs { x, y },
+    pressed_modifiers: pressed_modifiers().into_iter().map(KeyJs::from).collect(),
+  })
+}
+
+/// Check a snapshot passed to `restoreInputState`: a finite cursor position and
+/// distinct modifier keys.
+fn validate_input_state(position: &PointJs, keys: &[Key]) -> Result<()> {
+  let invalid = |reason: String| {
+    Error::new(
+      Status::InvalidArg,
+      format!("Invalid input state: {}", reason),
+    )
+  };
+  if !position.x.is_finite() || !position.y.is_finite() {
+    return Err(invalid(format!(
+      "mouse position ({}, {}) is not finite",
+      position.x, position.y
+    )));
+  }
+  for (i, &key) in keys.iter().enumerate() {
+    if !key.is_modifier() {
+      return Err(invalid(format!(
+        "{} is not a modifier",
+        key_display_name_ascii(key.into())
+      )));
+    }
+    if keys[..i].iter().any(|&k| key_id(k) == key_id(key)) {
+      return Err(invalid(format!(
+        "{} appears more than once",
+        key_display_name_ascii(key.into())
+      )));
+    }
+  }
+  Ok(())
+}
+
+/// Return to a `captureInputState` snapshot: release modifiers held now but
+/// not then, press those held then but not now, and move the cursor back.
+/// The snapshot is validated first and nothing is changed if it is invalid;
+/// after that every step is attempted and the first failure is thrown
+/// afterwards.
+#[napi]
+pub fn restore_input_state(mut env: Env, state: InputStateJs) -> Result<()> {
+  let wanted: Vec<Key> = state.pressed_modifiers.into_iter().map(Key::from).collect();
+  validate_input_state(&state.mouse_position, &wanted)?;
+  let current = pressed_modifiers();
+  let simulated: Vec<u32> = HELD_INPUT
+    .lock()
+    .unwrap()
+    .keys
+    .iter()
+    .map(|&k| key_id(k))
+    .collect();
+  let held_in = |keys: &[Key], key: Key| keys.iter().any(|&k| key_id(k) == key_id(key));
+
+  let mut result = Ok(());
+  let mut keep_first = |r: Result<()>| {
+    if result.is_ok() {
+      result = r;
+    }
+  };
+  for &key in current.iter().filter(|&&k| !held_in(&wanted, k)) {
+    if simulated.contains(&key_id(key)) {
+      keep_first(release_held_key(key));
+    } else {
+      keep_first(key_release(key).map_err(|e| {
+        Error::new(
+          Status::GenericFailure,
+          format!("Failed to release key: {}", e),
+        )
+      }));
+    }
+  }
+  for &key in wanted.iter().filter(|&&k| !held_in(&current, k)) {
+    keep_first(press_held_key(key));
+  }
+  keep_first(move_mouse(state.mouse_position.x, state.mouse_position.y));
+  register_exit_release(&mut env);
+  result
+}
+
+/// Order in which `simulateKeyCombo` releases the keys it pressed.
+#[napi]
+pub enum ReleaseOrderJs {
+  /// Mirror of the press order: final key first, then modifiers innermost-out.
+  Reverse,
+  /// Same order as pressed: modifiers first, final key last.
+  Forward,
+  /// Final key first, then modifiers in the order they were pressed.
+  KeyFirst,
+}
+
+/// Check the `simulateKeyCombo` contract: a non-empty list of distinct keys in
+/// which every key but the last is a modifier.
+fn validate_combo(keys: &[Key]) -> Result<()> {
+  let invalid =
+    |reason: String| Error::new(Status::InvalidArg, format!("Invalid key combo: {}", reason));
+  let Some((_, leading)) = keys.split_last() else {
+    return Err(invalid("no keys given".to_string()));
+  };
+  if let Some(&key) = leading.iter().find(|k| !k.is_modifier()) {
+    return Err(invalid(format!(
+      "{} is not a modifier; only the last key may be a non-modifier",
+      key_display_name_ascii(key.into())
+    )));
+  }
+  for (i, &key) in keys.iter().enumerate() {
+    if keys[..i].iter().any(|&k| key_id(k) == key_id(key)) {
+      return Err(invalid(format!(
+        "{} appears more than once",
+        key_display_name_ascii(key.into())
+      )));
+    }
+  }
+  Ok(())
+}
+
+/// Press each key in order (e.g. `[ControlLeft, ShiftLeft, KeyT]`), then release them.
+///
+/// Every key except the last must be a modifier, and keys must not repeat;
+/// otherwise this fails with `InvalidArg` before anything is pressed. A combo of
+/// only modifiers (e.g. `[ControlLeft, ShiftLeft]`) is allowed and simply taps
+/// them together.
+///
+/// `release_order` defaults to `Reverse`. If a press fails part-way, the keys that
+/// were already pressed are still released before the error is returned.
+#[napi]
+pub fn simulate_key_combo(keys: Vec<KeyJs>, release_order: Option<ReleaseOrderJs>) -> Result<()> {
+  let keys: Vec<Key> = keys.into_iter().map(Key::from).collect();
+  validate_combo(&keys)?;
+  queued(move || press_key_combo(&keys, release_order))
+}
+
+fn press_key_combo(keys: &[Key], release_order: Option<ReleaseOrderJs>) -> Result<()> {
+  let mut result = Ok(());
+  let mut pressed = 0;
+  for &key in keys {
+    if let Err(e) = key_press(key) {
+      result = Err(Error::new(
+        Status::GenericFailure,
+        format!("Failed to press key: {}", e),
+      ));
+      break;
+    }
+    pressed += 1;
+  }
+
+  let held = &keys[..pressed];
+  let release: Vec<Key> = match release_order.unwrap_or(ReleaseOrderJs::Reverse) {
+    ReleaseOrderJs::Reverse => held.iter().rev().copied().collect(),
+    ReleaseOrderJs::Forward => held.to_vec(),
+    ReleaseOrderJs::KeyFirst => match held.split_last() {
+      Some((last, modifiers)) => std::iter::once(last).chain(modifiers).copied().collect(),
+      None => Vec::new(),
+    },
+  };
+
+  for key in release {
+    if let Err(e) = key_release(key) {
+      if result.is_ok() {
+        result = Err(Error::new(
+          Status::GenericFailure,
+          format!("Failed to release key: {}", e),
+        ));
+      }
+    }
+  }
+  result
+}
+
+/// Whether `c` attaches to the preceding character rather than starting a new
+/// user-perceived character: combining marks, variation selectors, emoji
+/// skin-tone modifiers, and the zero-width joiner itself.
+fn is_grapheme_extend(c: char) -> bool {
+  matches!(c as u32,
+    0x0300..=0x036F // combining diacritical marks
+    | 0x1AB0..=0x1AFF
+    | 0x1DC0..=0x1DFF
+    | 0x200D // zero-width joiner
+    | 0x20D0..=0x20FF
+    | 0xFE00..=0xFE0F // variation selectors
+    | 0xFE20..=0xFE2F
+    | 0x1F3FB..=0x1F3FF // emoji modifiers
+    | 0xE0100..=0xE01EF)
+}
+
+/// Split text into the units `typeText` injects atomically: Unicode scalar
+/// values (never UTF-16 halves or UTF-8 bytes), with combining marks,
+/// variation selectors and ZWJ sequences kept with their base character.
+///
+/// This approximates extended grapheme clusters without the full Unicode
+/// tables; it covers accents, emoji with modifiers, and joined emoji.
+fn text_units(text: &str) -> Vec<&str> {
+  let mut units = Vec::new();
+  let mut start = 0;
+  let mut joined = false;
+  for (i, c) in text.char_indices() {
+    if i > start && !joined && !is_grapheme_extend(c) {
+      units.push(&text[start..i]);
+      start = i;
+    }
+    joined = c == '\u{200D}';
+  }
+  if start < text.len() {
+    units.push(&text[start..]);
+  }
+  units
+}
+
+/// Split `text` the way `typeText` does, e.g. `"e\u0301😀"` → `["é", "😀"]`.
+/// Useful for progress reporting or for typing long text in chunks.
+#[napi]
+pub fn split_text_units(text: String) -> Vec<String> {
+  text_units(&text).into_iter().map(String::from).collect()
+}
+
+/// Every character a key types under the active layout, unshifted entries
+/// first, as `(key, shift, text)`.
+fn layout_key_chars() -> Vec<(Key, bool, String)> {
+  // Extended keys (arrows, media, ...) never produce characters
+  let keys: Vec<(Key, u32)> = (0..KEY_JS_COUNT)
+    .filter_map(|i| {
+      let key = key_from_i32(i)?;
+      let scan_code = key_scan_code(&key).filter(|&code| code < 0x100)?;
+      Some((Key::from(key), scan_code))
+    })
+    .collect();
+  let scan_codes: Vec<u32> = keys.iter().map(|&(_, code)| code).collect();
+  [false, true]
+    .into_iter()
+    .flat_map(|shift| {
+      keys
+        .iter()
+        .zip(platform::scan_codes_to_chars(&scan_codes, shift))
+        .filter_map(move |(&(key, _), text)| Some((key, shift, text?)))
+    })
+    .collect()
+}
+
+/// Type one unit with the key that produces it under the active layout
+/// (other platforms' fallback). `layout` comes from `layout_key_chars`.
+fn type_unit_with_keys(unit: &str, layout: &[(Key, bool, String)]) -> Result<()> {
+  let (key, shift) = match unit {
+    "\n" => (Key::Enter, false),
+    "\t" => (Key::Tab, false),
+    _ => layout
+      .iter()
+      .find(|(_, _, text)| text == unit)
+      .map(|&(key, shift, _)| (key, shift))
+      .ok_or_else(|| {
+        Error::new(
+          Status::InvalidArg,
+          format!(
+            "Failed to type text: no key produces \"{}\" on the active keyboard layout",
+            unit
+          ),
+        )
+      })?,
+  };
+  let fail = |e: String| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to type text: {}", e),
+    )
+  };
+  if shift {
+    key_press(Key::ShiftLeft).map_err(|e| fail(e.to_string()))?;
+  }
+  let result = key_tap(key).map_err(|e| fail(e.to_string()));
+  if shift {
+    key_release(Key::ShiftLeft).map_err(|e| fail(e.to_string()))?;
+  }
+  result
+}
+
+/// Type `text` as if entered on the keyboard.
+///
+/// Text is injected one user-perceived character at a time (see
+/// `splitTextUnits`), so surrogate pairs, emoji and combining sequences are
+/// never split. On Windows every unit is sent as Unicode input, independent of
+/// the keyboard layout. Elsewhere each unit is typed with the key that produces
+/// it under the active layout (see `keyToChar`), with Shift if needed, so only
+/// those characters plus `\n` and `\t` can be typed; anything else (AltGr or
+/// Option levels, dead-key compositions) fails with `InvalidArg` after the
+/// preceding text has been typed.
+#[napi]
+pub fn type_text(text: String) -> Result<()> {
+  queued(move || type_text_units(&text))
+}
+
+fn type_text_units(text: &str) -> Result<()> {
+  check_secure_context()?;
+  let mut layout = None;
+  for unit in text_units(text) {
+    if cfg!(target_os = "windows") {
+      platform::send_text(unit).map_err(|e| {
+        Error::new(
+          Status::GenericFailure,
+          format!("Failed to type text: {}", e),
+        )
+      })?;
+    } else {
+      let layout = layout.get_or_insert_with(layout_key_chars);
+      type_unit_with_keys(unit, layout)?;
+    }
+  }
+  Ok(())
+}
+
+/// Pause between moving the cursor and acting on the new position, giving the
+/// target application time to see the pointer arrive (hover state, focus).
+/// Set with `setDefaultSettleMs`.
+static SETTLE_DELAY_MS: AtomicU32 = AtomicU32::new(15);
+
+/// Set the pause (default 15 ms) that move-then-act helpers such as
+/// `simulateScrollAt` and `simulateMouseMoveVerified` wait after moving the
+/// cursor, for helpers not given their own `settleMs`. Raise it where the
+/// target reacts slowly, e.g. over remote desktop.
+#[napi]
+pub fn set_default_settle_ms(ms: u32) {
+  SETTLE_DELAY_MS.store(ms, Ordering::Relaxed);
+}
+
+#[napi]
+pub fn get_default_settle_ms() -> u32 {
+  SETTLE_DELAY_MS.load(Ordering::Relaxed)
+}
+
+/// `settle_ms`, or the `setDefaultSettleMs` value when not given.
+fn settle_delay(settle_ms: Option<u32>) -> Duration {
+  Duration::from_millis(settle_ms.unwrap_or_else(get_default_settle_ms) as u64)
+}
+
+/// Emit a single wheel event at `(x, y)` through monio's generic `simulate`.
+fn simulate_wheel(x: f64, y: f64, direction: ScrollDirection, delta: f64) -> Result<()> {
+  let event = Event {
+    event_type: EventType::MouseWheel,
+    time: SystemTime::now(),
+    keyboard: None,
+    mouse: None,
+    wheel: Some(WheelData {
+      x,
+      y,
+      direction,
+      delta,
+    }),
+  };
+  simulate(&event)
+    .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to scroll: {}", e)))
+}
+
+pub struct ScrollAtTask {
+  x: f64,
+  y: f64,
+  direction: ScrollDirection,
+  delta: f64,
+  settle: Duration,
+}
+
+#[napi]
+impl Task for ScrollAtTask {
+  type Output = ();
+  type JsValue = ();
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    move_mouse(self.x, self.y)?;
+    thread::sleep(self.settle);
+    simulate_wheel(self.x, self.y, self.direction, self.delta)
+  }
+
+  fn resolve(&mut self, _env: Env, _output: Self::Output) -> Result<Self::JsValue> {
+    Ok(())
+  }
 }
 
-/// EventEmitter-style input hook with per-event-type callbacks.
-///
-/// Unlike `startListen()` which sends all events through a single callback,
-/// `InputHook` dispatches events to typed callbacks registered via `onKeyDown()`,
-/// `onMouseMove()`, etc. Only registered event types cross the NAPI boundary —
-/// the event mask is computed automatically from which callbacks are set.
-///
-/// ```js
-/// const hook = new InputHook();
-/// hook.onKeyDown((data) => console.log("key:", data.key, data.rawCode));
-/// hook.onMouseMove((data) => console.log("mouse:", data.x, data.y));
-/// hook.start();
-/// // ... later:
-/// hook.stop();
-/// ```
+/// Move the cursor to `(x, y)`, let it settle for `settleMs` (default: see
+/// `setDefaultSettleMs`), then scroll there. Runs off the JS thread; the
+/// returned Promise resolves once the wheel event is sent.
 #[napi]
-pub struct InputHook {
-  hook: Arc<Mutex<Option<Hook>>>,
-  callbacks: Arc<Mutex<InputHookCallbacks>>,
-  mask: Arc<AtomicU32>,
+pub fn simulate_scroll_at(
+  x: f64,
+  y: f64,
+  direction: ScrollDirectionJs,
+  delta: f64,
+  settle_ms: Option<u32>,
+) -> AsyncTask<ScrollAtTask> {
+  AsyncTask::new(ScrollAtTask {
+    x,
+    y,
+    direction: direction.into(),
+    delta,
+    settle: settle_delay(settle_ms),
+  })
 }
 
-impl Default for InputHook {
-  fn default() -> Self {
-    Self::new()
+/// Whether `simulatePinch` and `simulateRotate` can synthesize trackpad
+/// gestures on this platform. Currently `false` everywhere: neither monio nor
+/// the platform shims can inject gesture events.
+#[napi]
+pub fn can_simulate_gestures() -> bool {
+  false
+}
+
+fn check_gesture_point(x: f64, y: f64) -> Result<()> {
+  if !x.is_finite() || !y.is_finite() {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!("Gesture position must be finite, got ({}, {})", x, y),
+    ));
   }
+  Ok(())
+}
+
+fn gesture_unsupported(gesture: &str) -> Error {
+  Error::new(
+    Status::GenericFailure,
+    format!(
+      "[Unsupported] {} gestures cannot be simulated on this platform",
+      gesture
+    ),
+  )
 }
 
+/// Pinch by `scale` (>1 zooms in, <1 zooms out) centred on `(x, y)`. Throws
+/// with an `[Unsupported]` prefix where `canSimulateGestures()` is `false`.
 #[napi]
-impl InputHook {
+pub fn simulate_pinch(scale: f64, x: f64, y: f64) -> Result<()> {
+  if !scale.is_finite() || scale <= 0.0 {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!("Pinch scale must be a positive number, got {}", scale),
+    ));
+  }
+  check_gesture_point(x, y)?;
+  Err(gesture_unsupported("Pinch"))
+}
+
+/// Rotate by `degrees` (clockwise positive) around `(x, y)`. Throws with an
+/// `[Unsupported]` prefix where `canSimulateGestures()` is `false`.
+#[napi]
+pub fn simulate_rotate(degrees: f64, x: f64, y: f64) -> Result<()> {
+  if !degrees.is_finite() {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!("Rotation must be finite, got {}", degrees),
+    ));
+  }
+  check_gesture_point(x, y)?;
+  Err(gesture_unsupported("Rotate"))
+}
+
+pub struct MoveVerifiedTask {
+  x: f64,
+  y: f64,
+  tolerance: f64,
+  retries: u32,
+  settle: Duration,
+}
+
+#[napi]
+impl Task for MoveVerifiedTask {
+  type Output = bool;
+  type JsValue = bool;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    for _ in 0..=self.retries {
+      move_mouse(self.x, self.y)?;
+      thread::sleep(self.settle);
+      let (x, y) = mouse_position().map_err(|e| {
+        Error::new(
+          Status::GenericFailure,
+          format!("Failed to get mouse position: {}", e),
+        )
+      })?;
+      if (x - self.x).abs() <= self.tolerance && (y - self.y).abs() <= self.tolerance {
+        return Ok(true);
+      }
+    }
+    Ok(false)
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+}
+
+/// Move the cursor to `(x, y)` and read the position back, retrying up to
+/// `retries` times (default 3) while it is more than `tolerancePx` (default 1)
+/// away on either axis. Resolves with whether the cursor ended up in range.
+/// Each attempt waits `settleMs` (default: see `setDefaultSettleMs`) before
+/// reading back.
+#[napi]
+pub fn simulate_mouse_move_verified(
+  x: f64,
+  y: f64,
+  tolerance_px: Option<f64>,
+  retries: Option<u32>,
+  settle_ms: Option<u32>,
+) -> AsyncTask<MoveVerifiedTask> {
+  AsyncTask::new(MoveVerifiedTask {
+    x,
+    y,
+    tolerance: tolerance_px.unwrap_or(1.0).max(0.0),
+    retries: retries.unwrap_or(3),
+    settle: settle_delay(settle_ms),
+  })
+}
+
+/// Cancels Promise-returning simulation helpers (`simulateMouseMoveSmooth`,
+/// `simulateKeyHold`) it was passed to. One token can be shared by several
+/// helpers; once cancelled it stays cancelled.
+#[napi]
+pub struct CancelToken {
+  cancelled: Arc<AtomicBool>,
+}
+
+#[napi]
+impl CancelToken {
   #[napi(constructor)]
   pub fn new() -> Self {
     Self {
-      hook: Arc::new(Mutex::new(None)),
-      callbacks: Arc::new(Mutex::new(InputHookCallbacks::new())),
-      mask: Arc::new(AtomicU32::new(0)),
+      cancelled: Arc::new(AtomicBool::new(false)),
     }
   }
 
-  // ─── Per-event-type callback registration ──────────────────────────
-
+  /// Stop every helper using this token at its next step boundary. Helpers
+  /// then resolve with `SimulationStatusJs.Cancelled`.
   #[napi]
-  pub fn on_key_down(
-    &self,
-    #[napi(ts_arg_type = "(data: KeyboardEventJs) => void")] callback: Function<(), ()>,
-  ) -> Result<()> {
-    let tsfn = callback
-      .build_threadsafe_function()
-      .build_callback(|ctx: ThreadsafeCallContext<KeyboardEventJs>| Ok(vec![ctx.value]))?;
-    let mut cbs = self.callbacks.lock().unwrap();
-    cbs.key_down = Some(tsfn);
-    self.mask.store(cbs.compute_mask(), Ordering::Relaxed);
-    Ok(())
+  pub fn cancel(&self) {
+    self.cancelled.store(true, Ordering::Relaxed);
   }
 
-  #[napi]
-  pub fn on_key_up(
-    &self,
-    #[napi(ts_arg_type = "(data: KeyboardEventJs) => void")] callback: Function<(), ()>,
-  ) -> Result<()> {
-    let tsfn = callback
-      .build_threadsafe_function()
-      .build_callback(|ctx: ThreadsafeCallContext<KeyboardEventJs>| Ok(vec![ctx.value]))?;
-    let mut cbs = self.callbacks.lock().unwrap();
-    cbs.key_up = Some(tsfn);
-    self.mask.store(cbs.compute_mask(), Ordering::Relaxed);
-    Ok(())
+  #[napi(getter)]
+  pub fn is_cancelled(&self) -> bool {
+    self.cancelled.load(Ordering::Relaxed)
   }
+}
 
-  #[napi]
-  pub fn on_mouse_down(
-    &self,
-    #[napi(ts_arg_type = "(data: MouseButtonEventJs) => void")] callback: Function<(), ()>,
-  ) -> Result<()> {
-    let tsfn = callback
-      .build_threadsafe_function()
-      .build_callback(|ctx: ThreadsafeCallContext<MouseButtonEventJs>| Ok(vec![ctx.value]))?;
-    let mut cbs = self.callbacks.lock().unwrap();
-    cbs.mouse_down = Some(tsfn);
-    self.mask.store(cbs.compute_mask(), Ordering::Relaxed);
-    Ok(())
+impl Default for CancelToken {
+  fn default() -> Self {
+    Self::new()
   }
+}
 
-  #[napi]
-  pub fn on_mouse_up(
-    &self,
-    #[napi(ts_arg_type = "(data: MouseButtonEventJs) => void")] callback: Function<(), ()>,
-  ) -> Result<()> {
-    let tsfn = callback
-      .build_threadsafe_function()
-      .build_callback(|ctx: ThreadsafeCallContext<MouseButtonEventJs>| Ok(vec![ctx.value]))?;
-    let mut cbs = self.callbacks.lock().unwrap();
-    cbs.mouse_up = Some(tsfn);
-    self.mask.store(cbs.compute_mask(), Ordering::Relaxed);
-    Ok(())
-  }
+/// How a cancellable simulation helper finished.
+#[napi]
+pub enum SimulationStatusJs {
+  Completed,
+  Cancelled,
+}
 
-  #[napi]
-  pub fn on_click(
-    &self,
-    #[napi(ts_arg_type = "(data: MouseButtonEventJs) => void")] callback: Function<(), ()>,
-  ) -> Result<()> {
-    let tsfn = callback
-      .build_threadsafe_function()
-      .build_callback(|ctx: ThreadsafeCallContext<MouseButtonEventJs>| Ok(vec![ctx.value]))?;
-    let mut cbs = self.callbacks.lock().unwrap();
-    cbs.mouse_click = Some(tsfn);
-    self.mask.store(cbs.compute_mask(), Ordering::Relaxed);
-    Ok(())
+/// Longest a cancellable helper sleeps before checking its token again.
+const CANCEL_POLL_MS: u64 = 10;
+
+/// Sleep for `duration`, waking early if `cancelled` is set. Returns whether
+/// the full duration elapsed.
+fn sleep_unless_cancelled(duration: Duration, cancelled: &Option<Arc<AtomicBool>>) -> bool {
+  let deadline = Instant::now() + duration;
+  loop {
+    if cancelled
+      .as_ref()
+      .is_some_and(|c| c.load(Ordering::Relaxed))
+    {
+      return false;
+    }
+    let now = Instant::now();
+    if now >= deadline {
+      return true;
+    }
+    thread::sleep((deadline - now).min(Duration::from_millis(CANCEL_POLL_MS)));
   }
+}
 
-  #[napi]
-  pub fn on_mouse_move(
-    &self,
-    #[napi(ts_arg_type = "(data: MouseMoveEventJs) => void")] callback: Function<(), ()>,
-  ) -> Result<()> {
-    let tsfn = callback
-      .build_threadsafe_function()
-      .build_callback(|ctx: ThreadsafeCallContext<MouseMoveEventJs>| Ok(vec![ctx.value]))?;
-    let mut cbs = self.callbacks.lock().unwrap();
-    cbs.mouse_move = Some(tsfn);
-    self.mask.store(cbs.compute_mask(), Ordering::Relaxed);
-    Ok(())
+/// Interval between intermediate positions of `simulateMouseMoveSmooth` when
+/// no display reports a refresh rate.
+const SMOOTH_STEP_MS: u64 = 8;
+
+pub struct SmoothMoveTask {
+  x: f64,
+  y: f64,
+  duration: Duration,
+  /// Positions per second; `None` derives it from the displays.
+  frame_rate: Option<u32>,
+  cancelled: Option<Arc<AtomicBool>>,
+}
+
+#[napi]
+impl Task for SmoothMoveTask {
+  type Output = SimulationStatusJs;
+  type JsValue = SimulationStatusJs;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    let (start_x, start_y) = mouse_position().map_err(|e| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to get mouse position: {}", e),
+      )
+    })?;
+    let frame_rate = self
+      .frame_rate
+      .or_else(get_highest_refresh_rate)
+      .filter(|&rate| rate > 0)
+      .unwrap_or((1000 / SMOOTH_STEP_MS) as u32);
+    let steps = ((self.duration.as_secs_f64() * frame_rate as f64).ceil() as u64).max(1);
+    let step = self.duration / steps as u32;
+    for i in 1..=steps {
+      let t = i as f64 / steps as f64;
+      move_mouse(
+        start_x + (self.x - start_x) * t,
+        start_y + (self.y - start_y) * t,
+      )?;
+      if i < steps && !sleep_unless_cancelled(step, &self.cancelled) {
+        return Ok(SimulationStatusJs::Cancelled);
+      }
+    }
+    Ok(SimulationStatusJs::Completed)
   }
 
-  #[napi]
-  pub fn on_wheel(
-    &self,
-    #[napi(ts_arg_type = "(data: WheelEventJs) => void")] callback: Function<(), ()>,
-  ) -> Result<()> {
-    let tsfn = callback
-      .build_threadsafe_function()
-      .build_callback(|ctx: ThreadsafeCallContext<WheelEventJs>| Ok(vec![ctx.value]))?;
-    let mut cbs = self.callbacks.lock().unwrap();
-    cbs.mouse_wheel = Some(tsfn);
-    self.mask.store(cbs.compute_mask(), Ordering::Relaxed);
-    Ok(())
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
   }
+}
 
-  // ─── Callback removal ──────────────────────────────────────────────
+/// Glide the cursor in a straight line from its current position to `(x, y)`
+/// over `durationMs`, moving once per frame at `frameRate` (default: the
+/// highest display refresh rate, see `getHighestRefreshRate`, else 125 Hz).
+/// With a `CancelToken` the cursor stops where it is on cancel and the Promise
+/// resolves `Cancelled`.
+#[napi]
+pub fn simulate_mouse_move_smooth(
+  x: f64,
+  y: f64,
+  duration_ms: u32,
+  token: Option<ClassInstance<CancelToken>>,
+  frame_rate: Option<u32>,
+) -> AsyncTask<SmoothMoveTask> {
+  AsyncTask::new(SmoothMoveTask {
+    x,
+    y,
+    duration: Duration::from_millis(duration_ms as u64),
+    frame_rate,
+    cancelled: token.map(|t| t.cancelled.clone()),
+  })
+}
 
-  #[napi]
-  pub fn off_key_down(&self) {
-    let mut cbs = self.callbacks.lock().unwrap();
-    cbs.key_down = None;
-    self.mask.store(cbs.compute_mask(), Ordering::Relaxed);
+pub struct KeyHoldTask {
+  key: Key,
+  duration: Duration,
+  cancelled: Option<Arc<AtomicBool>>,
+}
+
+#[napi]
+impl Task for KeyHoldTask {
+  type Output = SimulationStatusJs;
+  type JsValue = SimulationStatusJs;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    press_held_key(self.key)?;
+    let completed = sleep_unless_cancelled(self.duration, &self.cancelled);
+    release_held_key(self.key)?;
+    Ok(if completed {
+      SimulationStatusJs::Completed
+    } else {
+      SimulationStatusJs::Cancelled
+    })
   }
 
-  #[napi]
-  pub fn off_key_up(&self) {
-    let mut cbs = self.callbacks.lock().unwrap();
-    cbs.key_up = None;
-    self.mask.store(cbs.compute_mask(), Ordering::Relaxed);
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
   }
+}
 
-  #[napi]
-  pub fn off_mouse_down(&self) {
-    let mut cbs = self.callbacks.lock().unwrap();
-    cbs.mouse_down = None;
-    self.mask.store(cbs.compute_mask(), Ordering::Relaxed);
+/// Hold `key` down for `durationMs`, then release it. Cancelling via the
+/// `CancelToken` releases the key immediately and resolves `Cancelled`.
+#[napi]
+pub fn simulate_key_hold(
+  mut env: Env,
+  key: KeyJs,
+  duration_ms: u32,
+  token: Option<ClassInstance<CancelToken>>,
+) -> AsyncTask<KeyHoldTask> {
+  register_exit_release(&mut env);
+  AsyncTask::new(KeyHoldTask {
+    key: key.into(),
+    duration: Duration::from_millis(duration_ms as u64),
+    cancelled: token.map(|t| t.cancelled.clone()),
+  })
+}
+
+// ============================================================================
+// Binary Scripts (see `simulateBinary` for the format)
+// ============================================================================
+
+/// Format version `simulateBinary` understands (the script's first byte).
+const BINARY_SCRIPT_VERSION: u8 = 1;
+
+#[derive(Clone)]
+enum ScriptOp {
+  MouseMove(f64, f64),
+  MousePress(Button),
+  MouseRelease(Button),
+  MouseClick(Button),
+  KeyPress(Key),
+  KeyRelease(Key),
+  KeyTap(Key),
+  Wheel(f64, f64, ScrollDirection, f64),
+  /// Type text as `typeText` would. Only built by `Automation`.
+  Text(String),
+  /// Do nothing; the step exists for its delay. Only built by `Automation`.
+  Wait,
+}
+
+/// One decoded record: an action and how long to wait after it.
+#[derive(Clone)]
+struct ScriptStep {
+  op: ScriptOp,
+  delay: Duration,
+}
+
+/// Cursor over a binary script that reports errors with the byte offset.
+struct ScriptReader<'a> {
+  bytes: &'a [u8],
+  pos: usize,
+}
+
+impl<'a> ScriptReader<'a> {
+  fn error(&self, what: impl std::fmt::Display) -> Error {
+    Error::new(
+      Status::InvalidArg,
+      format!("Invalid binary script at byte {}: {}", self.pos, what),
+    )
   }
 
-  #[napi]
-  pub fn off_mouse_up(&self) {
-    let mut cbs = self.callbacks.lock().unwrap();
-    cbs.mouse_up = None;
-    self.mask.store(cbs.compute_mask(), Ordering::Relaxed);
+  fn take<const N: usize>(&mut self) -> Result<[u8; N]> {
+    let bytes = self
+      .bytes
+      .get(self.pos..self.pos + N)
+      .ok_or_else(|| self.error("unexpected end of script"))?;
+    self.pos += N;
+    Ok(bytes.try_into().unwrap())
   }
 
-  #[napi]
-  pub fn off_click(&self) {
-    let mut cbs = self.callbacks.lock().unwrap();
-    cbs.mouse_click = None;
-    self.mask.store(cbs.compute_mask(), Ordering::Relaxed);
+  fn u8(&mut self) -> Result<u8> {
+    Ok(self.take::<1>()?[0])
   }
 
-  #[napi]
-  pub fn off_mouse_move(&self) {
-    let mut cbs = self.callbacks.lock().unwrap();
-    cbs.mouse_move = None;
-    self.mask.store(cbs.compute_mask(), Ordering::Relaxed);
+  fn f32(&mut self) -> Result<f64> {
+    Ok(f32::from_le_bytes(self.take()?) as f64)
   }
 
-  #[napi]
-  pub fn off_wheel(&self) {
-    let mut cbs = self.callbacks.lock().unwrap();
-    cbs.mouse_wheel = None;
-    self.mask.store(cbs.compute_mask(), Ordering::Relaxed);
+  fn key(&mut self) -> Result<Key> {
+    let code = u16::from_le_bytes(self.take()?);
+    key_from_i32(code as i32)
+      .map(Key::from)
+      .ok_or_else(|| self.error(format!("unknown key code {}", code)))
   }
 
-  #[napi]
-  pub fn remove_all_listeners(&self) {
-    let mut cbs = self.callbacks.lock().unwrap();
-    *cbs = InputHookCallbacks::new();
-    self.mask.store(0, Ordering::Relaxed);
+  fn button(&mut self) -> Result<Button> {
+    let code = self.u8()?;
+    button_from_code(code as u32)
+      .map(Button::from)
+      .ok_or_else(|| self.error(format!("unknown button code {}", code)))
   }
 
-  // ─── Lifecycle ─────────────────────────────────────────────────────
+  fn direction(&mut self) -> Result<ScrollDirection> {
+    Ok(match self.u8()? {
+      0 => ScrollDirection::Up,
+      1 => ScrollDirection::Down,
+      2 => ScrollDirection::Left,
+      3 => ScrollDirection::Right,
+      code => return Err(self.error(format!("unknown scroll direction {}", code))),
+    })
+  }
 
-  #[napi]
-  pub fn start(&self) -> Result<()> {
-    let mut hook_guard = self.hook.lock().unwrap();
-    if hook_guard.is_some() {
-      return Err(Error::new(
-        Status::GenericFailure,
-        "Hook is already running",
-      ));
+  /// Unsigned LEB128, at most 32 bits.
+  fn varint(&mut self) -> Result<u32> {
+    let mut value = 0u32;
+    for shift in (0..35).step_by(7) {
+      let byte = self.u8()?;
+      let bits = (byte & 0x7f) as u32;
+      if shift == 28 && bits > 0xf {
+        return Err(self.error("delay does not fit in 32 bits"));
+      }
+      value |= bits << shift;
+      if byte & 0x80 == 0 {
+        return Ok(value);
+      }
     }
+    Err(self.error("delay does not fit in 32 bits"))
+  }
+}
 
-    let callbacks = self.callbacks.clone();
-    let mask = self.mask.clone();
-
-    let hook = Hook::new();
-    hook
-      .run_async(move |event: &Event| {
-        // Check the mask BEFORE acquiring the lock
-        let bit = event_type_bit(&event.event_type);
-        if mask.load(Ordering::Relaxed) & bit == 0 {
-          return;
-        }
-
-        let time = event
-          .time
-          .duration_since(UNIX_EPOCH)
-          .map(|d| d.as_secs_f64())
-          .unwrap_or(0.0);
-
-        let cbs = callbacks.lock().unwrap();
-
-        match event.event_type {
-          EventType::KeyPressed => {
-            if let (Some(ref tsfn), Some(ref kb)) = (&cbs.key_down, &event.keyboard) {
-              let data = KeyboardEventJs {
-                key: kb.key.into(),
-                raw_code: kb.raw_code,
-                time,
-              };
-              let _ = tsfn.call(data, ThreadsafeFunctionCallMode::NonBlocking);
-            }
-          }
-          EventType::KeyReleased => {
-            if let (Some(ref tsfn), Some(ref kb)) = (&cbs.key_up, &event.keyboard) {
-              let data = KeyboardEventJs {
-                key: kb.key.into(),
-                raw_code: kb.raw_code,
-                time,
-              };
-              let _ = tsfn.call(data, ThreadsafeFunctionCallMode::NonBlocking);
-            }
-          }
-          EventType::MousePressed => {
-            if let (Some(ref tsfn), Some(ref m)) = (&cbs.mouse_down, &event.mouse) {
-              let data = MouseButtonEventJs {
-                x: m.x,
-                y: m.y,
-                button: m.button.unwrap_or(Button::Left).into(),
-                time,
-              };
-              let _ = tsfn.call(data, ThreadsafeFunctionCallMode::NonBlocking);
-            }
-          }
-          EventType::MouseReleased => {
-            if let (Some(ref tsfn), Some(ref m)) = (&cbs.mouse_up, &event.mouse) {
-              let data = MouseButtonEventJs {
-                x: m.x,
-                y: m.y,
-                button: m.button.unwrap_or(Button::Left).into(),
-                time,
-              };
-              let _ = tsfn.call(data, ThreadsafeFunctionCallMode::NonBlocking);
-            }
+/// Decode a whole script up front, so a malformed one runs nothing.
+fn decode_binary_script(bytes: &[u8]) -> Result<Vec<ScriptStep>> {
+  let mut reader = ScriptReader { bytes, pos: 0 };
+  let version = reader.u8()?;
+  if version != BINARY_SCRIPT_VERSION {
+    return Err(reader.error(format!("unsupported version {}", version)));
+  }
+  let mut steps = Vec::new();
+  while reader.pos < bytes.len() {
+    let op = match reader.u8()? {
+      0x01 => ScriptOp::MouseMove(reader.f32()?, reader.f32()?),
+      0x02 => ScriptOp::MousePress(reader.button()?),
+      0x03 => ScriptOp::MouseRelease(reader.button()?),
+      0x04 => ScriptOp::MouseClick(reader.button()?),
+      0x05 => ScriptOp::KeyPress(reader.key()?),
+      0x06 => ScriptOp::KeyRelease(reader.key()?),
+      0x07 => ScriptOp::KeyTap(reader.key()?),
+      0x08 => ScriptOp::Wheel(
+        reader.f32()?,
+        reader.f32()?,
+        reader.direction()?,
+        reader.f32()?,
+      ),
+      opcode => {
+        reader.pos -= 1;
+        return Err(reader.error(format!("unknown opcode {:#04x}", opcode)));
+      }
+    };
+    let delay = Duration::from_millis(reader.varint()? as u64);
+    steps.push(ScriptStep { op, delay });
+  }
+  Ok(steps)
+}
+
+pub struct BinaryScriptTask {
+  steps: Vec<ScriptStep>,
+  /// Passes over `steps` (at least 1).
+  loops: u32,
+  cancelled: Option<Arc<AtomicBool>>,
+}
+
+impl BinaryScriptTask {
+  /// Run the steps, recording what is still held in `keys`/`buttons`.
+  fn run(&self, keys: &mut Vec<Key>, buttons: &mut Vec<Button>) -> Result<SimulationStatusJs> {
+    for _ in 0..self.loops {
+      for step in &self.steps {
+        match &step.op {
+          ScriptOp::MouseMove(x, y) => move_mouse(*x, *y)?,
+          ScriptOp::MousePress(button) => {
+            press_held_button(*button)?;
+            buttons.push(*button);
           }
-          EventType::MouseClicked => {
-            if let (Some(ref tsfn), Some(ref m)) = (&cbs.mouse_click, &event.mouse) {
-              let data = MouseButtonEventJs {
-                x: m.x,
-                y: m.y,
-                button: m.button.unwrap_or(Button::Left).into(),
-                time,
-              };
-              let _ = tsfn.call(data, ThreadsafeFunctionCallMode::NonBlocking);
-            }
+          ScriptOp::MouseRelease(button) => {
+            buttons.retain(|&b| button_id(b) != button_id(*button));
+            release_held_button(*button)?;
           }
-          EventType::MouseMoved | EventType::MouseDragged => {
-            if let (Some(ref tsfn), Some(ref m)) = (&cbs.mouse_move, &event.mouse) {
-              let data = MouseMoveEventJs {
-                x: m.x,
-                y: m.y,
-                time,
-              };
-              let _ = tsfn.call(data, ThreadsafeFunctionCallMode::NonBlocking);
-            }
+          ScriptOp::MouseClick(button) => simulate_mouse_click((*button).into(), None)?,
+          ScriptOp::KeyPress(key) => {
+            press_held_key(*key)?;
+            keys.push(*key);
           }
-          EventType::MouseWheel => {
-            if let (Some(ref tsfn), Some(ref w)) = (&cbs.mouse_wheel, &event.wheel) {
-              let data = WheelEventJs {
-                x: w.x,
-                y: w.y,
-                direction: w.direction.into(),
-                delta: w.delta,
-                time,
-              };
-              let _ = tsfn.call(data, ThreadsafeFunctionCallMode::NonBlocking);
-            }
+          ScriptOp::KeyRelease(key) => {
+            keys.retain(|&k| key_id(k) != key_id(*key));
+            release_held_key(*key)?;
           }
-          _ => {} // HookEnabled, HookDisabled, KeyTyped — ignored
+          ScriptOp::KeyTap(key) => key_tap(*key)
+            .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to tap key: {}", e)))?,
+          ScriptOp::Wheel(x, y, direction, delta) => simulate_wheel(*x, *y, *direction, *delta)?,
+          ScriptOp::Text(text) => type_text_units(text)?,
+          ScriptOp::Wait => {}
         }
-      })
-      .map_err(|e| {
-        Error::new(
-          Status::GenericFailure,
-          format!("Failed to start hook: {}", e),
-        )
-      })?;
-
-    *hook_guard = Some(hook);
-    Ok(())
-  }
-
-  #[napi]
-  pub fn stop(&self) -> Result<()> {
-    let mut hook_guard = self.hook.lock().unwrap();
-    if let Some(hook) = hook_guard.take() {
-      hook.stop().map_err(|e| {
-        Error::new(
-          Status::GenericFailure,
-          format!("Failed to stop hook: {}", e),
-        )
-      })?;
+        if !sleep_unless_cancelled(step.delay, &self.cancelled) {
+          return Ok(SimulationStatusJs::Cancelled);
+        }
+      }
     }
-    Ok(())
+    Ok(SimulationStatusJs::Completed)
   }
+}
 
-  #[napi(getter)]
-  pub fn is_running(&self) -> bool {
-    let guard = self.hook.lock().unwrap();
-    guard.as_ref().is_some_and(|h| h.is_running())
+#[napi]
+impl Task for BinaryScriptTask {
+  type Output = SimulationStatusJs;
+  type JsValue = SimulationStatusJs;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    let (mut keys, mut buttons) = (Vec::new(), Vec::new());
+    let result = self.run(&mut keys, &mut buttons);
+    // A script that stops early releases what it pressed
+    if !matches!(result, Ok(SimulationStatusJs::Completed)) {
+      for &key in keys.iter().rev() {
+        let _ = release_held_key(key);
+      }
+      for &button in buttons.iter().rev() {
+        let _ = release_held_button(button);
+      }
+    }
+    result
   }
 
-  #[napi(getter)]
-  pub fn event_mask(&self) -> u32 {
-    self.mask.load(Ordering::Relaxed)
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
   }
 }
 
-// ============================================================================
-// Display Functions
-// ============================================================================
-
-/// Get all displays
+/// Run a compact binary input script on a background thread, resolving
+/// `Completed` or, if `token` is cancelled, `Cancelled`. Input the script
+/// pressed is released if it is cancelled or fails; otherwise keys and buttons
+/// it leaves down stay held as with `simulateKeyPress`.
+///
+/// Format version 1: one version byte (`1`), then records of an opcode byte,
+/// its payload, and an unsigned LEB128 varint delay in ms to wait afterwards.
+/// Numbers are little-endian; coordinates and wheel deltas are `f32`, keys are
+/// `u16` `KeyJs` values and buttons are `u8` `buttonCode` values.
+///
+/// | Opcode | Action        | Payload                                           |
+/// |--------|---------------|---------------------------------------------------|
+/// | `0x01` | move          | x, y                                              |
+/// | `0x02` | mouse press   | button                                            |
+/// | `0x03` | mouse release | button                                            |
+/// | `0x04` | click         | button                                            |
+/// | `0x05` | key press     | key                                               |
+/// | `0x06` | key release   | key                                               |
+/// | `0x07` | key tap       | key                                               |
+/// | `0x08` | wheel         | x, y, direction (`u8` `ScrollDirectionJs`), delta |
+///
+/// The whole script is validated before anything runs; a malformed one
+/// throws with the offending byte offset.
 #[napi]
-pub fn get_displays() -> Result<Vec<DisplayInfoJs>> {
-  displays()
-    .map(|infos| infos.iter().map(|info| info.into()).collect())
-    .map_err(|e| {
-      Error::new(
-        Status::GenericFailure,
-        format!("Failed to get displays: {}", e),
-      )
-    })
+pub fn simulate_binary(
+  mut env: Env,
+  buffer: Buffer,
+  token: Option<ClassInstance<CancelToken>>,
+) -> Result<AsyncTask<BinaryScriptTask>> {
+  let steps = decode_binary_script(&buffer)?;
+  register_exit_release(&mut env);
+  Ok(AsyncTask::new(BinaryScriptTask {
+    steps,
+    loops: 1,
+    cancelled: token.map(|t| t.cancelled.clone()),
+  }))
 }
 
-/// Get the primary display
+/// Set 1 scan code for a key (`0xE0xx` for extended keys such as the arrows),
+/// for use with `simulateScancodePress`/`Release`/`Tap`. `null` for keys
+/// without a single scan code (Pause, Unknown).
 #[napi]
-pub fn get_primary_display() -> Result<DisplayInfoJs> {
-  primary_display().map(|info| (&info).into()).map_err(|e| {
+pub fn key_to_scancode(key: KeyJs) -> Option<u32> {
+  key_scan_code(&key)
+}
+
+fn send_scancode(scancode: u32, up: bool) -> Result<()> {
+  if !up {
+    check_secure_context()?;
+  }
+  platform::send_scancode(scancode, up).map_err(|e| {
+    let action = if up { "release" } else { "press" };
     Error::new(
       Status::GenericFailure,
-      format!("Failed to get primary display: {}", e),
+      format!("Failed to {} scancode {:#x}: {}", action, scancode, e),
     )
   })
 }
 
-/// Get display at a specific point
+/// Press a key by hardware scan code (see `keyToScancode`), for games that
+/// read scan codes and ignore virtual-key input. Windows only for now; other
+/// platforms return an error.
 #[napi]
-pub fn get_display_at_point(x: f64, y: f64) -> Result<Option<DisplayInfoJs>> {
-  display_at_point(x, y)
-    .map(|opt| opt.as_ref().map(|info| info.into()))
-    .map_err(|e| {
-      Error::new(
-        Status::GenericFailure,
-        format!("Failed to get display at point: {}", e),
-      )
-    })
+pub fn simulate_scancode_press(scancode: u32) -> Result<()> {
+  queued(move || send_scancode(scancode, false))
 }
 
-/// Get system settings
+/// Release a key by hardware scan code. See `simulateScancodePress`.
 #[napi]
-pub fn get_system_settings() -> Result<SystemSettingsJs> {
-  system_settings()
-    .map(|settings| (&settings).into())
-    .map_err(|e| {
-      Error::new(
-        Status::GenericFailure,
-        format!("Failed to get system settings: {}", e),
-      )
-    })
+pub fn simulate_scancode_release(scancode: u32) -> Result<()> {
+  queued(move || send_scancode(scancode, true))
 }
 
-// ============================================================================
-// Event Simulation
-// ============================================================================
-
-/// Move the mouse to absolute coordinates
+/// Press and release a key by hardware scan code. See `simulateScancodePress`.
 #[napi]
-pub fn simulate_mouse_move(x: f64, y: f64) -> Result<()> {
-  mouse_move(x, y).map_err(|e| {
-    Error::new(
-      Status::GenericFailure,
-      format!("Failed to move mouse: {}", e),
-    )
+pub fn simulate_scancode_tap(scancode: u32) -> Result<()> {
+  queued(move || {
+    send_scancode(scancode, false)?;
+    send_scancode(scancode, true)
   })
 }
 
-/// Press a mouse button
+/// Tap `key` in a specific window by posting it to the window's message queue,
+/// so the window does not need focus. `windowHandle` comes from
+/// `getFocusedWindow().handle`.
+///
+/// Windows only; other platforms return an error. Posted input bypasses the
+/// system input queue: modifier state is not faked, and apps that read raw
+/// input or poll key state (many games, some Chromium/Electron and UWP apps)
+/// ignore it entirely.
 #[napi]
-pub fn simulate_mouse_press(button: ButtonJs) -> Result<()> {
-  mouse_press(button.into()).map_err(|e| {
+pub fn simulate_key_to_window(window_handle: f64, key: KeyJs) -> Result<()> {
+  check_secure_context()?;
+  let scancode =
+    key_scan_code(&key).ok_or_else(|| Error::new(Status::InvalidArg, "Key has no scan code"))?;
+  platform::post_key(window_handle as isize, scancode).map_err(|e| {
     Error::new(
       Status::GenericFailure,
-      format!("Failed to press mouse button: {}", e),
+      format!("Failed to post key to window: {}", e),
     )
   })
 }
 
-/// Release a mouse button
+/// Click at `(x, y)` relative to a window's client area by posting button
+/// messages to it, so the window does not need focus. Same platform support and
+/// caveats as `simulateKeyToWindow`; the real cursor does not move.
 #[napi]
-pub fn simulate_mouse_release(button: ButtonJs) -> Result<()> {
-  mouse_release(button.into()).map_err(|e| {
+pub fn simulate_click_to_window(
+  window_handle: f64,
+  x: f64,
+  y: f64,
+  button: ButtonJs,
+) -> Result<()> {
+  check_secure_context()?;
+  platform::post_click(
+    window_handle as isize,
+    x.round() as i32,
+    y.round() as i32,
+    button_code(button),
+  )
+  .map_err(|e| {
     Error::new(
       Status::GenericFailure,
-      format!("Failed to release mouse button: {}", e),
+      format!("Failed to post click to window: {}", e),
     )
   })
 }
 
-/// Click a mouse button (press + release)
+/// Get the current mouse cursor position
 #[napi]
-pub fn simulate_mouse_click(button: ButtonJs) -> Result<()> {
-  mouse_click(button.into()).map_err(|e| {
+pub fn get_mouse_position() -> Result<MouseDataJs> {
+  let (x, y) = mouse_position().map_err(|e| {
     Error::new(
       Status::GenericFailure,
-      format!("Failed to click mouse button: {}", e),
+      format!("Failed to get mouse position: {}", e),
     )
-  })
+  })?;
+  Ok(MouseDataJs { x, y, button: None })
+}
+
+/// Cursor position relative to the display it is on.
+#[napi(object)]
+pub struct LocalPointJs {
+  pub display_id: u32,
+  pub x: f64,
+  pub y: f64,
 }
 
-/// Press a key
+/// Get the cursor position relative to the top-left of the display containing
+/// it, or `null` when it is off every display. With `unscale` the offset is
+/// divided by that display's scale factor.
 #[napi]
-pub fn simulate_key_press(key: KeyJs) -> Result<()> {
-  key_press(key.into()).map_err(|e| {
+pub fn get_mouse_position_local(unscale: Option<bool>) -> Result<Option<LocalPointJs>> {
+  let (x, y) = mouse_position().map_err(|e| {
     Error::new(
       Status::GenericFailure,
-      format!("Failed to press key: {}", e),
+      format!("Failed to get mouse position: {}", e),
     )
-  })
-}
-
-/// Release a key
-#[napi]
-pub fn simulate_key_release(key: KeyJs) -> Result<()> {
-  key_release(key.into()).map_err(|e| {
+  })?;
+  let Some(info) = display_at_point(x, y).map_err(|e| {
     Error::new(
       Status::GenericFailure,
-      format!("Failed to release key: {}", e),
+      format!("Failed to get display at point: {}", e),
     )
-  })
+  })?
+  else {
+    return Ok(None);
+  };
+  let scale = if unscale.unwrap_or(false) && info.scale_factor > 0.0 {
+    info.scale_factor
+  } else {
+    1.0
+  };
+  Ok(Some(LocalPointJs {
+    display_id: info.id,
+    x: (x - info.bounds.x) / scale,
+    y: (y - info.bounds.y) / scale,
+  }))
+}
+
+// ============================================================================
+// Event Replay (captured `EventJs` streams, see `replayEvents`)
+// ============================================================================
+
+impl From<InputEvent> for EventJs {
+  fn from(event: InputEvent) -> Self {
+    EventJs {
+      event_type: event.event_type.into(),
+      time: event.time,
+      keyboard: event.keyboard.map(|kb| KeyboardDataJs {
+        key: kb.key.into(),
+        raw_code: kb.raw_code,
+        char: None,
+      }),
+      mouse: event.mouse.map(|m| MouseDataJs {
+        x: m.x,
+        y: m.y,
+        button: m.button.map(|b| b.into()),
+      }),
+      wheel: event.wheel.map(|w| WheelDataJs {
+        x: w.x,
+        y: w.y,
+        direction: w.direction.into(),
+        delta: w.delta,
+      }),
+      device_id: None,
+    }
+  }
+}
+
+/// Rebuild a captured stream into the events a replay should simulate:
+///
+/// - Hook lifecycle and `KeyTyped` events are dropped (the key presses produce
+///   the text again).
+/// - A drag with no button down gets a press of its button (left if unknown)
+///   first, and a move after it, or the end of the stream, releases it.
+/// - A press or release away from where the replayed pointer is gets a move
+///   (a drag while a button is down) to its position first.
+/// - A release of a button that is not down is dropped.
+/// - A click directly covered by a preceding release of its button is dropped;
+///   a click on its own becomes a press and a release.
+/// - Events missing the data their type needs are dropped.
+fn reconstruct_replay(events: impl IntoIterator<Item = InputEvent>) -> Vec<InputEvent> {
+  let mut replay = ReplayBuilder::default();
+  // Buttons released since their last press, whose click is still to come
+  let mut released: Vec<u32> = Vec::new();
+  let mut last_mouse = None;
+  let mut last_time = 0.0;
+  for event in events {
+    last_time = event.time;
+    match event.event_type {
+      // An `Unknown` key has nothing to simulate (see `KeyboardDataJs.rawCode`)
+      EventType::KeyPressed | EventType::KeyReleased => {
+        if event
+          .keyboard
+          .is_some_and(|kb| !matches!(kb.key, Key::Unknown(_)))
+        {
+          replay.out.push(event);
+        }
+      }
+      EventType::MouseWheel if event.wheel.is_some() => replay.out.push(event),
+      _ => {
+        let Some(m) = event.mouse else {
+          continue;
+        };
+        last_mouse = Some(m);
+        let button = m.button.unwrap_or(Button::Left);
+        let id = button_id(button);
+        match event.event_type {
+          EventType::MousePressed => {
+            released.retain(|&b| b != id);
+            replay.button(EventType::MousePressed, event.time, m, button, false);
+          }
+          EventType::MouseReleased if replay.held.iter().any(|&(b, _)| button_id(b) == id) => {
+            released.push(id);
+            replay.button(EventType::MouseReleased, event.time, m, button, false);
+          }
+          EventType::MouseClicked => {
+            if let Some(i) = released.iter().position(|&b| b == id) {
+              released.remove(i);
+            } else {
+              replay.button(EventType::MousePressed, event.time, m, button, false);
+              replay.button(EventType::MouseReleased, event.time, m, button, false);
+            }
+          }
+          EventType::MouseDragged => {
+            if replay.held.is_empty() {
+              replay.button(EventType::MousePressed, event.time, m, button, true);
+            }
+            replay.motion(event);
+          }
+          EventType::MouseMoved => {
+            replay.release_synthesized(event.time, m);
+            replay.motion(event);
+          }
+          _ => {}
+        }
+      }
+    }
+  }
+  if let Some(m) = last_mouse {
+    replay.release_synthesized(last_time, m);
+  }
+  replay.out
+}
+
+/// Output of `reconstruct_replay` with the pointer state needed to keep it
+/// consistent.
+#[derive(Default)]
+struct ReplayBuilder {
+  out: Vec<InputEvent>,
+  /// Buttons down during the replay, and whether they were synthesized.
+  held: Vec<(Button, bool)>,
+  /// Where the replayed pointer is.
+  position: Option<(f64, f64)>,
+}
+
+impl ReplayBuilder {
+  /// Push a move or drag and remember where it leaves the pointer.
+  fn motion(&mut self, event: InputEvent) {
+    if let Some(m) = event.mouse {
+      self.position = Some((m.x, m.y));
+    }
+    self.out.push(event);
+  }
+
+  /// Press or release `button` at `m`'s position, moving (or dragging, if a
+  /// button is down) there first if the replayed pointer is elsewhere.
+  fn button(
+    &mut self,
+    event_type: EventType,
+    time: f64,
+    m: MouseInput,
+    button: Button,
+    synthesized: bool,
+  ) {
+    if self.position != Some((m.x, m.y)) {
+      let motion = if self.held.is_empty() {
+        EventType::MouseMoved
+      } else {
+        EventType::MouseDragged
+      };
+      self.motion(InputEvent {
+        event_type: motion,
+        time,
+        keyboard: None,
+        mouse: Some(MouseInput { button: None, ..m }),
+        wheel: None,
+      });
+    }
+    match event_type {
+      EventType::MousePressed => self.held.push((button, synthesized)),
+      _ => self
+        .held
+        .retain(|&(b, _)| button_id(b) != button_id(button)),
+    }
+    self.out.push(InputEvent {
+      event_type,
+      time,
+      keyboard: None,
+      mouse: Some(MouseInput {
+        button: Some(button),
+        ..m
+      }),
+      wheel: None,
+    });
+  }
+
+  /// Release the buttons pressed for drags without one, where the pointer is
+  /// now (or at `m` if it never moved).
+  fn release_synthesized(&mut self, time: f64, m: MouseInput) {
+    let at = match self.position {
+      Some((x, y)) => MouseInput { x, y, ..m },
+      None => m,
+    };
+    let synthesized: Vec<Button> = self
+      .held
+      .iter()
+      .filter(|&&(_, synthesized)| synthesized)
+      .map(|&(button, _)| button)
+      .collect();
+    for button in synthesized {
+      self.button(EventType::MouseReleased, time, at, button, true);
+    }
+  }
+}
+
+/// Milliseconds per unit of event `time` under the current time base.
+fn time_unit_ms() -> f64 {
+  if TIME_BASE.lock().unwrap().base == TimeBaseJs::PerformanceNow as u32 {
+    1.0
+  } else {
+    1000.0
+  }
 }
 
-/// Tap a key (press + release)
+/// The events `replayEvents` would simulate for a captured stream, with their
+/// original `time`s. Replaying these and capturing the result gives back the
+/// same press, release, move, drag and wheel events (the OS adds `KeyTyped`
+/// and `MouseClicked` events of its own), except for `Unknown` keys, which are
+/// left out.
 #[napi]
-pub fn simulate_key_tap(key: KeyJs) -> Result<()> {
-  key_tap(key.into())
-    .map_err(|e| Error::new(Status::GenericFailure, format!("Failed to tap key: {}", e)))
+pub fn plan_replay(events: Vec<EventJs>) -> Vec<EventJs> {
+  reconstruct_replay(events.into_iter().map(InputEvent::from))
+    .into_iter()
+    .map(EventJs::from)
+    .collect()
 }
 
-/// Get the current mouse cursor position
+/// Replay a stream captured from `startListen` (or built by hand) on a
+/// background thread, keeping the gaps between event `time`s divided by
+/// `speed` (default 1). Times are read in the current time base (see
+/// `setTimeBase`), so capture and replay under the same one. The stream is
+/// rebuilt as described by `planReplay` first, and wheel events keep their
+/// captured direction and delta. Resolves and releases like `simulateBinary`.
 #[napi]
-pub fn get_mouse_position() -> Result<MouseDataJs> {
-  let (x, y) = mouse_position().map_err(|e| {
-    Error::new(
-      Status::GenericFailure,
-      format!("Failed to get mouse position: {}", e),
-    )
-  })?;
-  Ok(MouseDataJs { x, y, button: None })
+pub fn replay_events(
+  mut env: Env,
+  events: Vec<EventJs>,
+  speed: Option<f64>,
+  token: Option<ClassInstance<CancelToken>>,
+) -> Result<AsyncTask<BinaryScriptTask>> {
+  let speed = speed.unwrap_or(1.0);
+  if !speed.is_finite() || speed <= 0.0 {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!("Replay speed must be a positive number, got {}", speed),
+    ));
+  }
+  let events = reconstruct_replay(events.into_iter().map(InputEvent::from));
+  let ms_per_unit = time_unit_ms() / speed;
+  let steps = events
+    .iter()
+    .enumerate()
+    .filter_map(|(i, event)| {
+      let op = match (event.event_type, event.keyboard, event.mouse, event.wheel) {
+        (EventType::KeyPressed, Some(kb), _, _) => ScriptOp::KeyPress(kb.key),
+        (EventType::KeyReleased, Some(kb), _, _) => ScriptOp::KeyRelease(kb.key),
+        (EventType::MousePressed, _, Some(m), _) => ScriptOp::MousePress(m.button?),
+        (EventType::MouseReleased, _, Some(m), _) => ScriptOp::MouseRelease(m.button?),
+        (EventType::MouseMoved | EventType::MouseDragged, _, Some(m), _) => {
+          ScriptOp::MouseMove(m.x, m.y)
+        }
+        (EventType::MouseWheel, _, _, Some(w)) => ScriptOp::Wheel(w.x, w.y, w.direction, w.delta),
+        _ => return None,
+      };
+      let gap = events
+        .get(i + 1)
+        .map_or(0.0, |next| (next.time - event.time) * ms_per_unit);
+      let delay = if gap.is_finite() && gap > 0.0 {
+        Duration::from_secs_f64(gap / 1000.0)
+      } else {
+        Duration::ZERO
+      };
+      Some(ScriptStep { op, delay })
+    })
+    .collect();
+  register_exit_release(&mut env);
+  Ok(AsyncTask::new(BinaryScriptTask {
+    steps,
+    loops: 1,
+    cancelled: token.map(|t| t.cancelled.clone()),
+  }))
+}
+
+// ============================================================================
+// Automation (fluent builder over the Binary Scripts runner)
+// ============================================================================
+
+/// A reusable sequence of input steps, built fluently and run in order on a
+/// background thread:
+///
+/// ```js
+/// await new Automation().move(100, 200).click().type('hello').wait(500).key(KeyJs.Enter).run()
+/// ```
+///
+/// Steps run back to back, so add `wait` wherever the target application
+/// needs time to react. Running does not consume the builder.
+#[napi]
+pub struct Automation {
+  steps: Vec<ScriptStep>,
+  loops: u32,
+}
+
+impl Automation {
+  fn push(&mut self, op: ScriptOp) {
+    self.steps.push(ScriptStep {
+      op,
+      delay: Duration::ZERO,
+    });
+  }
+}
+
+#[napi]
+impl Automation {
+  #[napi(constructor)]
+  pub fn new() -> Self {
+    Automation {
+      steps: Vec::new(),
+      loops: 1,
+    }
+  }
+
+  /// Move the mouse to absolute coordinates.
+  #[napi(js_name = "move")]
+  pub fn move_to<'a>(&mut self, this: This<'a>, x: f64, y: f64) -> This<'a> {
+    self.push(ScriptOp::MouseMove(x, y));
+    this
+  }
+
+  /// Click a mouse button (default `Left`).
+  #[napi]
+  pub fn click<'a>(&mut self, this: This<'a>, button: Option<ButtonJs>) -> This<'a> {
+    self.push(ScriptOp::MouseClick(
+      button.map_or(Button::Left, Button::from),
+    ));
+    this
+  }
+
+  /// Type text, as `typeText`.
+  #[napi(js_name = "type")]
+  pub fn type_text<'a>(&mut self, this: This<'a>, text: String) -> This<'a> {
+    self.push(ScriptOp::Text(text));
+    this
+  }
+
+  /// Tap a key.
+  #[napi]
+  pub fn key<'a>(&mut self, this: This<'a>, key: KeyJs) -> This<'a> {
+    self.push(ScriptOp::KeyTap(key.into()));
+    this
+  }
+
+  /// Pause for `ms` before the next step.
+  #[napi]
+  pub fn wait<'a>(&mut self, this: This<'a>, ms: u32) -> This<'a> {
+    self.steps.push(ScriptStep {
+      op: ScriptOp::Wait,
+      delay: Duration::from_millis(ms as u64),
+    });
+    this
+  }
+
+  /// Run the whole sequence `times` times (default 1). Throws if `times` is 0.
+  #[napi(js_name = "loop")]
+  pub fn repeat<'a>(&mut self, this: This<'a>, times: u32) -> Result<This<'a>> {
+    if times == 0 {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "Automation loop count must be at least 1",
+      ));
+    }
+    self.loops = times;
+    Ok(this)
+  }
+
+  /// Number of steps one pass runs, waits included.
+  #[napi(getter)]
+  pub fn length(&self) -> u32 {
+    self.steps.len() as u32
+  }
+
+  /// Run the steps on a background thread, resolving `Completed` or, if
+  /// `token` is cancelled, `Cancelled`. As with `simulateBinary`, keys and
+  /// buttons are released if the run stops early.
+  #[napi]
+  pub fn run(
+    &self,
+    mut env: Env,
+    token: Option<ClassInstance<CancelToken>>,
+  ) -> AsyncTask<BinaryScriptTask> {
+    register_exit_release(&mut env);
+    AsyncTask::new(BinaryScriptTask {
+      steps: self.steps.clone(),
+      loops: self.loops,
+      cancelled: token.map(|t| t.cancelled.clone()),
+    })
+  }
+}
+
+impl Default for Automation {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn key_codes_round_trip_through_monio() {
+    for code in 0..KEY_JS_COUNT {
+      let key = key_from_i32(code).unwrap_or_else(|| panic!("{} has no KeyJs variant", code));
+      assert_eq!(key as i32, code);
+      let back = KeyJs::from(Key::from(key_from_i32(code).unwrap()));
+      assert_eq!(back as i32, code, "{} changes through monio's Key", code);
+    }
+    assert!(
+      key_from_i32(KEY_JS_COUNT).is_none(),
+      "KEY_JS_COUNT is too low"
+    );
+  }
+
+  #[test]
+  fn named_keys_have_display_names_and_categories() {
+    for code in 0..KEY_JS_COUNT {
+      let key = key_from_i32(code).unwrap();
+      if matches!(key, KeyJs::Unknown) {
+        continue;
+      }
+      let name = key_name(&key);
+      assert_ne!(
+        key_display_name(&key),
+        "Unknown",
+        "{} has no display name",
+        name
+      );
+      assert!(
+        !matches!(key_category_of(key), KeyCategoryJs::Unknown),
+        "{} has no category",
+        name
+      );
+    }
+  }
+
+  #[test]
+  fn buttons_round_trip_through_monio() {
+    for code in (0..5).chain([255]) {
+      let button = button_from_code(code).unwrap_or_else(|| panic!("{} has no button", code));
+      assert_eq!(button_code(ButtonJs::from(Button::from(button))), code);
+    }
+    assert!(button_from_code(5).is_none());
+  }
+
+  #[test]
+  fn event_types_round_trip_through_monio() {
+    for code in 0..ALL_EVENT_TYPES.len() as u32 {
+      let event_type = event_type_from_code(code).unwrap();
+      assert_eq!(
+        event_type_code(EventTypeJs::from(EventType::from(event_type))),
+        code
+      );
+    }
+    assert!(event_type_from_code(ALL_EVENT_TYPES.len() as u32).is_none());
+  }
+
+  #[test]
+  fn scroll_directions_round_trip_through_monio() {
+    let directions = [
+      ScrollDirectionJs::Up,
+      ScrollDirectionJs::Down,
+      ScrollDirectionJs::Left,
+      ScrollDirectionJs::Right,
+    ];
+    for (code, direction) in directions.into_iter().enumerate() {
+      let back = ScrollDirectionJs::from(ScrollDirection::from(direction));
+      assert_eq!(back as usize, code);
+    }
+  }
 }
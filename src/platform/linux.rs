@@ -0,0 +1,122 @@
+//! Linux (X11) implementations of the platform shims.
+
+use std::os::raw::{c_char, c_int, c_uint, c_ulong, c_void};
+
+use super::LockState;
+
+/// `XkbStateRec` from `XKBstr.h`.
+#[repr(C)]
+#[derive(Default)]
+struct XkbState {
+  group: u8,
+  locked_group: u8,
+  base_group: u16,
+  latched_group: u16,
+  mods: u8,
+  base_mods: u8,
+  latched_mods: u8,
+  locked_mods: u8,
+  compat_state: u8,
+  grab_mods: u8,
+  compat_grab_mods: u8,
+  lookup_mods: u8,
+  compat_lookup_mods: u8,
+  ptr_buttons: u16,
+}
+
+const XKB_USE_CORE_KBD: c_uint = 0x0100;
+const LOCK_MASK: u8 = 0x02;
+const XK_NUM_LOCK: c_ulong = 0xff7f;
+const XK_SCROLL_LOCK: c_ulong = 0xff14;
+
+/// X11 keycodes are evdev codes plus 8; for the main block evdev codes equal
+/// set 1 scan codes.
+const X11_KEYCODE_OFFSET: u32 = 8;
+
+#[link(name = "X11")]
+extern "C" {
+  fn XOpenDisplay(name: *const c_char) -> *mut c_void;
+  fn XCloseDisplay(display: *mut c_void) -> c_int;
+  fn XkbGetState(display: *mut c_void, device: c_uint, state: *mut XkbState) -> c_int;
+  fn XkbKeycodeToKeysym(display: *mut c_void, keycode: u8, group: c_int, level: c_int) -> c_ulong;
+  fn XkbKeysymToModifiers(display: *mut c_void, keysym: c_ulong) -> c_uint;
+}
+
+/// An open X display, closed on drop.
+struct Display(*mut c_void);
+
+impl Display {
+  fn open() -> Option<Self> {
+    // SAFETY: a null name means `$DISPLAY`; null is returned on failure.
+    let display = unsafe { XOpenDisplay(std::ptr::null()) };
+    (!display.is_null()).then_some(Self(display))
+  }
+}
+
+impl Drop for Display {
+  fn drop(&mut self) {
+    // SAFETY: `self.0` came from a successful `XOpenDisplay`.
+    unsafe { XCloseDisplay(self.0) };
+  }
+}
+
+/// The character a keysym stands for: Latin-1 keysyms are their code point,
+/// Unicode keysyms are `0x01000000 | code point`. Legacy non-Latin-1 keysyms
+/// and dead keys have no single character here.
+fn keysym_char(keysym: c_ulong) -> Option<char> {
+  match keysym {
+    0x20..=0x7e | 0xa0..=0xff => char::from_u32(keysym as u32),
+    0x0100_00a0..=0x0110_ffff => char::from_u32((keysym - 0x0100_0000) as u32),
+    // Keypad digits and operators
+    0xffb0..=0xffb9 => char::from_u32((keysym - 0xffb0) as u32 + '0' as u32),
+    0xffaa => Some('*'),
+    0xffab => Some('+'),
+    0xffad => Some('-'),
+    0xffae => Some('.'),
+    0xffaf => Some('/'),
+    0xffbd => Some('='),
+    _ => None,
+  }
+}
+
+pub(super) fn lock_state() -> Option<LockState> {
+  let display = Display::open()?;
+  let mut state = XkbState::default();
+  // SAFETY: `state` is a valid `XkbStateRec` for the call to fill in.
+  if unsafe { XkbGetState(display.0, XKB_USE_CORE_KBD, &mut state) } != 0 {
+    return None;
+  }
+  // Num Lock and Scroll Lock are bound to whichever modifier the keymap says
+  // (usually Mod2 and none); a lock without a modifier reads as off.
+  let locked = |keysym| {
+    // SAFETY: `display` is open; an unbound keysym yields 0.
+    let mask = unsafe { XkbKeysymToModifiers(display.0, keysym) } as u8;
+    mask != 0 && state.locked_mods & mask == mask
+  };
+  Some(LockState {
+    caps_lock: state.locked_mods & LOCK_MASK != 0,
+    num_lock: locked(XK_NUM_LOCK),
+    scroll_lock: locked(XK_SCROLL_LOCK),
+  })
+}
+
+pub(super) fn scan_codes_to_chars(scan_codes: &[u32], shift: bool) -> Vec<Option<String>> {
+  let Some(display) = Display::open() else {
+    return vec![None; scan_codes.len()];
+  };
+  let mut state = XkbState::default();
+  // SAFETY: `state` is a valid `XkbStateRec` for the call to fill in.
+  let group = match unsafe { XkbGetState(display.0, XKB_USE_CORE_KBD, &mut state) } {
+    0 => state.group as c_int,
+    _ => 0,
+  };
+  scan_codes
+    .iter()
+    .map(|&scan_code| {
+      let keycode = u8::try_from(scan_code + X11_KEYCODE_OFFSET).ok()?;
+      // SAFETY: `display` is open; out-of-range arguments return NoSymbol.
+      let keysym = unsafe { XkbKeycodeToKeysym(display.0, keycode, group, shift as c_int) };
+      keysym_char(keysym).map(String::from)
+    })
+    .collect()
+}
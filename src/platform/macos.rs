@@ -0,0 +1,286 @@
+//! macOS implementations of the platform shims.
+
+use std::os::raw::{c_char, c_void};
+
+use super::LockState;
+
+#[link(name = "ApplicationServices", kind = "framework")]
+extern "C" {
+  fn AXIsProcessTrusted() -> u8;
+}
+
+#[link(name = "CoreGraphics", kind = "framework")]
+extern "C" {
+  fn CGPreflightListenEventAccess() -> bool;
+  fn CGEventPost(tap: u32, event: *mut c_void);
+  fn CGEventSourceFlagsState(state_id: i32) -> u64;
+}
+
+const K_CG_EVENT_SOURCE_STATE_COMBINED_SESSION_STATE: i32 = 0;
+const K_CG_EVENT_FLAG_MASK_ALPHA_SHIFT: u64 = 0x10000;
+
+pub(super) fn lock_state() -> LockState {
+  // SAFETY: takes a plain state id and only reads the current modifier flags.
+  let flags = unsafe { CGEventSourceFlagsState(K_CG_EVENT_SOURCE_STATE_COMBINED_SESSION_STATE) };
+  // Mac keyboards have no Num Lock or Scroll Lock
+  LockState {
+    caps_lock: flags & K_CG_EVENT_FLAG_MASK_ALPHA_SHIFT != 0,
+    num_lock: false,
+    scroll_lock: false,
+  }
+}
+
+pub(super) fn accessibility_granted() -> bool {
+  // SAFETY: takes no arguments and only reads the TCC database.
+  unsafe { AXIsProcessTrusted() != 0 }
+}
+
+pub(super) fn input_monitoring_granted() -> bool {
+  // SAFETY: as above; unlike `CGRequestListenEventAccess` it never prompts.
+  unsafe { CGPreflightListenEventAccess() }
+}
+
+#[link(name = "Carbon", kind = "framework")]
+extern "C" {
+  static kTISPropertyUnicodeKeyLayoutData: *const c_void;
+  fn TISCopyCurrentKeyboardLayoutInputSource() -> *const c_void;
+  fn TISGetInputSourceProperty(source: *const c_void, key: *const c_void) -> *const c_void;
+  fn LMGetKbdType() -> u8;
+  #[allow(clippy::too_many_arguments)]
+  fn UCKeyTranslate(
+    layout: *const c_void,
+    virtual_key: u16,
+    action: u16,
+    modifier_state: u32,
+    keyboard_type: u32,
+    options: u32,
+    dead_key_state: *mut u32,
+    max_len: usize,
+    actual_len: *mut usize,
+    chars: *mut u16,
+  ) -> i32;
+}
+
+#[link(name = "CoreFoundation", kind = "framework")]
+extern "C" {
+  fn CFDataGetBytePtr(data: *const c_void) -> *const u8;
+  fn CFRelease(cf: *const c_void);
+}
+
+const K_UC_KEY_ACTION_DISPLAY: u16 = 3;
+const K_UC_KEY_TRANSLATE_NO_DEAD_KEYS_MASK: u32 = 1;
+/// `shiftKey >> 8`, as `UCKeyTranslate` expects modifier state.
+const SHIFT_MODIFIER_STATE: u32 = 0x02;
+
+/// macOS virtual keycode (`kVK_*`) at the position of a set 1 scan code.
+fn scan_code_to_vk(scan_code: u32) -> Option<u16> {
+  let vk = match scan_code {
+    0x02 => 0x12,
+    0x03 => 0x13,
+    0x04 => 0x14,
+    0x05 => 0x15,
+    0x06 => 0x17,
+    0x07 => 0x16,
+    0x08 => 0x1a,
+    0x09 => 0x1c,
+    0x0a => 0x19,
+    0x0b => 0x1d,
+    0x0c => 0x1b,
+    0x0d => 0x18,
+    0x10 => 0x0c,
+    0x11 => 0x0d,
+    0x12 => 0x0e,
+    0x13 => 0x0f,
+    0x14 => 0x11,
+    0x15 => 0x10,
+    0x16 => 0x20,
+    0x17 => 0x22,
+    0x18 => 0x1f,
+    0x19 => 0x23,
+    0x1a => 0x21,
+    0x1b => 0x1e,
+    0x1e => 0x00,
+    0x1f => 0x01,
+    0x20 => 0x02,
+    0x21 => 0x03,
+    0x22 => 0x05,
+    0x23 => 0x04,
+    0x24 => 0x26,
+    0x25 => 0x28,
+    0x26 => 0x25,
+    0x27 => 0x29,
+    0x28 => 0x27,
+    0x29 => 0x32,
+    0x2b => 0x2a,
+    0x2c => 0x06,
+    0x2d => 0x07,
+    0x2e => 0x08,
+    0x2f => 0x09,
+    0x30 => 0x0b,
+    0x31 => 0x2d,
+    0x32 => 0x2e,
+    0x33 => 0x2b,
+    0x34 => 0x2f,
+    0x35 => 0x2c,
+    0x37 => 0x43,
+    0x39 => 0x31,
+    0x47 => 0x59,
+    0x48 => 0x5b,
+    0x49 => 0x5c,
+    0x4a => 0x4e,
+    0x4b => 0x56,
+    0x4c => 0x57,
+    0x4d => 0x58,
+    0x4e => 0x45,
+    0x4f => 0x53,
+    0x50 => 0x54,
+    0x51 => 0x55,
+    0x52 => 0x52,
+    0x53 => 0x41,
+    0x56 => 0x0a,
+    0x59 => 0x51,
+    _ => return None,
+  };
+  Some(vk)
+}
+
+pub(super) fn scan_codes_to_chars(scan_codes: &[u32], shift: bool) -> Vec<Option<String>> {
+  // SAFETY: the input source is released below; the layout data it owns is
+  // only read while the source is alive. `UCKeyTranslate` writes at most
+  // `buf.len()` units and leaves dead-key state alone with NoDeadKeys.
+  unsafe {
+    let source = TISCopyCurrentKeyboardLayoutInputSource();
+    if source.is_null() {
+      return vec![None; scan_codes.len()];
+    }
+    let data = TISGetInputSourceProperty(source, kTISPropertyUnicodeKeyLayoutData);
+    let chars = if data.is_null() {
+      vec![None; scan_codes.len()]
+    } else {
+      let layout = CFDataGetBytePtr(data) as *const c_void;
+      let keyboard_type = LMGetKbdType() as u32;
+      let modifiers = if shift { SHIFT_MODIFIER_STATE } else { 0 };
+      scan_codes
+        .iter()
+        .map(|&scan_code| {
+          let vk = scan_code_to_vk(scan_code)?;
+          let mut dead_key_state = 0u32;
+          let mut buf = [0u16; 8];
+          let mut len = 0usize;
+          let status = UCKeyTranslate(
+            layout,
+            vk,
+            K_UC_KEY_ACTION_DISPLAY,
+            modifiers,
+            keyboard_type,
+            K_UC_KEY_TRANSLATE_NO_DEAD_KEYS_MASK,
+            &mut dead_key_state,
+            buf.len(),
+            &mut len,
+            buf.as_mut_ptr(),
+          );
+          if status != 0 || len == 0 {
+            return None;
+          }
+          let text = String::from_utf16_lossy(&buf[..len.min(buf.len())]);
+          (!text.chars().all(char::is_control)).then_some(text)
+        })
+        .collect()
+    };
+    CFRelease(source);
+    chars
+  }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct CGPoint {
+  x: f64,
+  y: f64,
+}
+
+#[link(name = "objc")]
+extern "C" {
+  fn objc_getClass(name: *const c_char) -> *mut c_void;
+  fn sel_registerName(name: *const c_char) -> *mut c_void;
+  fn objc_msgSend();
+  fn objc_autoreleasePoolPush() -> *mut c_void;
+  fn objc_autoreleasePoolPop(pool: *mut c_void);
+}
+
+// Links AppKit so the NSEvent class is registered with the runtime.
+#[link(name = "AppKit", kind = "framework")]
+extern "C" {}
+
+const NS_EVENT_TYPE_SYSTEM_DEFINED: u64 = 14;
+/// `NSEvent` subtype for auxiliary control buttons (media and volume keys).
+const NX_SUBTYPE_AUX_CONTROL_BUTTONS: i16 = 8;
+const NX_KEYDOWN: i64 = 0x0a;
+const NX_KEYUP: i64 = 0x0b;
+const K_CG_HID_EVENT_TAP: u32 = 0;
+const OTHER_EVENT_SELECTOR: &str = concat!(
+  "otherEventWithType:location:modifierFlags:timestamp:",
+  "windowNumber:context:subtype:data1:data2:\0",
+);
+
+/// Post a media or volume key (`NX_KEYTYPE_*`) as the system-defined event the
+/// hardware keys produce; a plain keycode for them is ignored.
+pub(super) fn send_system_defined_key(key_type: u16, up: bool) -> Result<(), String> {
+  type OtherEvent = unsafe extern "C" fn(
+    *mut c_void,
+    *mut c_void,
+    u64,
+    CGPoint,
+    u64,
+    f64,
+    i64,
+    *mut c_void,
+    i16,
+    i64,
+    i64,
+  ) -> *mut c_void;
+  type GetCGEvent = unsafe extern "C" fn(*mut c_void, *mut c_void) -> *mut c_void;
+
+  let state = if up { NX_KEYUP } else { NX_KEYDOWN };
+  let data1 = ((key_type as i64) << 16) | (state << 8);
+  // SAFETY: the selectors match the signatures `objc_msgSend` is cast to
+  // (`+[NSEvent otherEventWithType:location:modifierFlags:timestamp:
+  // windowNumber:context:subtype:data1:data2:]` and `-[NSEvent CGEvent]`).
+  // The NSEvent is autoreleased into our pool, and its CGEvent is owned by it,
+  // so both stay valid until the pool is popped after posting.
+  unsafe {
+    let class = objc_getClass(c"NSEvent".as_ptr());
+    if class.is_null() {
+      return Err("NSEvent is not available".to_string());
+    }
+    let pool = objc_autoreleasePoolPush();
+    let other_event: OtherEvent = std::mem::transmute(objc_msgSend as unsafe extern "C" fn());
+    let event = other_event(
+      class,
+      sel_registerName(OTHER_EVENT_SELECTOR.as_ptr() as *const c_char),
+      NS_EVENT_TYPE_SYSTEM_DEFINED,
+      CGPoint { x: 0.0, y: 0.0 },
+      (state << 8) as u64,
+      0.0,
+      0,
+      std::ptr::null_mut(),
+      NX_SUBTYPE_AUX_CONTROL_BUTTONS,
+      data1,
+      -1,
+    );
+    let result = if event.is_null() {
+      Err("failed to create system-defined event".to_string())
+    } else {
+      let get_cg_event: GetCGEvent = std::mem::transmute(objc_msgSend as unsafe extern "C" fn());
+      let cg_event = get_cg_event(event, sel_registerName(c"CGEvent".as_ptr()));
+      if cg_event.is_null() {
+        Err("failed to create system-defined event".to_string())
+      } else {
+        CGEventPost(K_CG_HID_EVENT_TAP, cg_event);
+        Ok(())
+      }
+    };
+    objc_autoreleasePoolPop(pool);
+    result
+  }
+}
@@ -0,0 +1,257 @@
+//! Thin platform shims for queries monio does not cover.
+//!
+//! Everything here is best-effort: unsupported platforms return `None` rather
+//! than an error, and callers are expected to degrade gracefully.
+
+#[cfg(target_os = "linux")]
+mod linux;
+#[cfg(target_os = "macos")]
+mod macos;
+#[cfg(target_os = "windows")]
+mod windows;
+
+/// The window that currently has keyboard focus.
+#[derive(Clone)]
+pub(crate) struct FocusedWindow {
+  pub title: String,
+  pub x: f64,
+  pub y: f64,
+  pub width: f64,
+  pub height: f64,
+  pub process_name: Option<String>,
+  pub process_id: Option<u32>,
+  /// Native window handle (an `HWND` on Windows).
+  pub handle: Option<isize>,
+}
+
+/// Query the focused window. Only implemented on Windows for now.
+pub(crate) fn focused_window() -> Option<FocusedWindow> {
+  #[cfg(target_os = "windows")]
+  {
+    windows::focused_window()
+  }
+  #[cfg(not(target_os = "windows"))]
+  {
+    None
+  }
+}
+
+/// Toggle state of the lock keys.
+pub(crate) struct LockState {
+  pub caps_lock: bool,
+  pub num_lock: bool,
+  pub scroll_lock: bool,
+}
+
+/// Query the lock keys' toggle state. `None` where it can't be read (no X
+/// display, or an unsupported platform).
+pub(crate) fn lock_state() -> Option<LockState> {
+  #[cfg(target_os = "windows")]
+  {
+    Some(windows::lock_state())
+  }
+  #[cfg(target_os = "macos")]
+  {
+    Some(macos::lock_state())
+  }
+  #[cfg(target_os = "linux")]
+  {
+    linux::lock_state()
+  }
+  #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+  {
+    None
+  }
+}
+
+/// An installed keyboard layout.
+pub(crate) struct KeyboardLayout {
+  pub id: String,
+  pub name: String,
+}
+
+/// List installed keyboard layouts. Only implemented on Windows for now.
+pub(crate) fn keyboard_layouts() -> Vec<KeyboardLayout> {
+  #[cfg(target_os = "windows")]
+  {
+    windows::keyboard_layouts()
+  }
+  #[cfg(not(target_os = "windows"))]
+  {
+    Vec::new()
+  }
+}
+
+/// Request a layout switch (by `KeyboardLayout::id`) for the focused window.
+/// Only implemented on Windows for now.
+pub(crate) fn set_keyboard_layout(id: &str) -> Result<(), String> {
+  #[cfg(target_os = "windows")]
+  {
+    windows::set_keyboard_layout(id)
+  }
+  #[cfg(not(target_os = "windows"))]
+  {
+    let _ = id;
+    Err("switching keyboard layouts is not supported on this platform".to_string())
+  }
+}
+
+/// Translate a set 1 scan code to the text it produces under the active
+/// keyboard layout: the focused window's on Windows, the current input source
+/// on macOS, and the core keyboard's XKB group on Linux (X11 only).
+pub(crate) fn scan_code_to_char(scan_code: u32, shift: bool) -> Option<String> {
+  scan_codes_to_chars(&[scan_code], shift).pop().flatten()
+}
+
+/// `scan_code_to_char` for many keys, looking the layout up once.
+pub(crate) fn scan_codes_to_chars(scan_codes: &[u32], shift: bool) -> Vec<Option<String>> {
+  #[cfg(target_os = "windows")]
+  {
+    scan_codes
+      .iter()
+      .map(|&scan_code| windows::scan_code_to_char(scan_code, shift))
+      .collect()
+  }
+  #[cfg(target_os = "macos")]
+  {
+    macos::scan_codes_to_chars(scan_codes, shift)
+  }
+  #[cfg(target_os = "linux")]
+  {
+    linux::scan_codes_to_chars(scan_codes, shift)
+  }
+  #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+  {
+    let _ = shift;
+    vec![None; scan_codes.len()]
+  }
+}
+
+pub(crate) fn send_scancode(scancode: u32, up: bool) -> Result<(), String> {
+  #[cfg(target_os = "windows")]
+  {
+    windows::send_scancode(scancode, up)
+  }
+  #[cfg(not(target_os = "windows"))]
+  {
+    let _ = (scancode, up);
+    Err("scan code injection is not supported on this platform".to_string())
+  }
+}
+
+/// Inject a consumer-control key (media, volume, browser, launch) by its
+/// native code: a virtual-key code on Windows, an `NX_KEYTYPE_*` code sent as
+/// a system-defined event on macOS.
+pub(crate) fn send_consumer_key(code: u16, up: bool) -> Result<(), String> {
+  #[cfg(target_os = "windows")]
+  {
+    windows::send_virtual_key(code, up)
+  }
+  #[cfg(target_os = "macos")]
+  {
+    macos::send_system_defined_key(code, up)
+  }
+  #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+  {
+    let _ = (code, up);
+    Err("consumer key injection is not supported on this platform".to_string())
+  }
+}
+
+/// Post a key tap (by set 1 scan code) to a window's message queue, without
+/// it needing focus. Only implemented on Windows for now.
+pub(crate) fn post_key(window: isize, scancode: u32) -> Result<(), String> {
+  #[cfg(target_os = "windows")]
+  {
+    windows::post_key(window, scancode)
+  }
+  #[cfg(not(target_os = "windows"))]
+  {
+    let _ = (window, scancode);
+    Err("posting input to a window is not supported on this platform".to_string())
+  }
+}
+
+/// Post a click at window-relative client coordinates to a window's message
+/// queue. `button` is a `buttonCode` value. Only implemented on Windows for now.
+pub(crate) fn post_click(window: isize, x: i32, y: i32, button: u32) -> Result<(), String> {
+  #[cfg(target_os = "windows")]
+  {
+    windows::post_click(window, x, y, button)
+  }
+  #[cfg(not(target_os = "windows"))]
+  {
+    let _ = (window, x, y, button);
+    Err("posting input to a window is not supported on this platform".to_string())
+  }
+}
+
+/// Inject `text` as Unicode keyboard input in one batch, so multi-unit
+/// characters arrive together. Only implemented on Windows for now.
+pub(crate) fn send_text(text: &str) -> Result<(), String> {
+  #[cfg(target_os = "windows")]
+  {
+    windows::send_text(text)
+  }
+  #[cfg(not(target_os = "windows"))]
+  {
+    let _ = text;
+    Err("Unicode text injection is not supported on this platform".to_string())
+  }
+}
+
+/// Block or unblock keyboard and mouse input from real devices; injected
+/// input still goes through. Windows only unblocks from the thread that
+/// blocked. Only implemented on Windows for now.
+pub(crate) fn block_input(block: bool) -> Result<(), String> {
+  #[cfg(target_os = "windows")]
+  {
+    windows::block_input(block)
+  }
+  #[cfg(not(target_os = "windows"))]
+  {
+    let _ = block;
+    Err("blocking user input is not supported on this platform".to_string())
+  }
+}
+
+/// Convert a point in the hook's physical-pixel space into the coordinates
+/// the cursor APIs take in this process. The Windows low-level hook always
+/// reports physical pixels, while a process that isn't per-monitor DPI aware
+/// positions the cursor in scaled (logical) pixels; elsewhere both agree.
+pub(crate) fn physical_to_logical(x: f64, y: f64) -> (f64, f64) {
+  #[cfg(target_os = "windows")]
+  {
+    windows::physical_to_logical(x, y)
+  }
+  #[cfg(not(target_os = "windows"))]
+  {
+    (x, y)
+  }
+}
+
+/// Whether the process may simulate input (macOS Accessibility). `true` on
+/// platforms without such a permission.
+pub(crate) fn accessibility_granted() -> bool {
+  #[cfg(target_os = "macos")]
+  {
+    macos::accessibility_granted()
+  }
+  #[cfg(not(target_os = "macos"))]
+  {
+    true
+  }
+}
+
+/// Whether the process may listen to input (macOS Input Monitoring). `true`
+/// on platforms without such a permission.
+pub(crate) fn input_monitoring_granted() -> bool {
+  #[cfg(target_os = "macos")]
+  {
+    macos::input_monitoring_granted()
+  }
+  #[cfg(not(target_os = "macos"))]
+  {
+    true
+  }
+}
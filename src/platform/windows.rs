@@ -0,0 +1,445 @@
+//! Win32 implementations of the platform shims.
+
+use super::{FocusedWindow, KeyboardLayout, LockState};
+
+type Hwnd = isize;
+type Handle = isize;
+type Hkl = isize;
+
+#[repr(C)]
+struct Point {
+  x: i32,
+  y: i32,
+}
+
+#[repr(C)]
+#[derive(Default)]
+struct Rect {
+  left: i32,
+  top: i32,
+  right: i32,
+  bottom: i32,
+}
+
+const PROCESS_QUERY_LIMITED_INFORMATION: u32 = 0x1000;
+const MAPVK_VSC_TO_VK: u32 = 1;
+const VK_SHIFT: usize = 0x10;
+const VK_CAPITAL: i32 = 0x14;
+const VK_NUMLOCK: i32 = 0x90;
+const VK_SCROLL: i32 = 0x91;
+/// GetLocaleInfoW: the locale's full name in the UI language.
+const LOCALE_SLOCALIZEDDISPLAYNAME: u32 = 0x2;
+const WM_INPUTLANGCHANGEREQUEST: u32 = 0x0050;
+/// ToUnicodeEx flag: do not modify the keyboard (dead-key) state.
+const TU_NO_STATE_CHANGE: u32 = 0x4;
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct KeybdInput {
+  vk: u16,
+  scan: u16,
+  flags: u32,
+  time: u32,
+  extra_info: usize,
+}
+
+/// Only here so `InputUnion` has the size SendInput expects.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[allow(dead_code)]
+struct MouseInput {
+  dx: i32,
+  dy: i32,
+  mouse_data: u32,
+  flags: u32,
+  time: u32,
+  extra_info: usize,
+}
+
+#[repr(C)]
+union InputUnion {
+  ki: KeybdInput,
+  _mi: MouseInput,
+}
+
+#[repr(C)]
+struct Input {
+  kind: u32,
+  u: InputUnion,
+}
+
+const INPUT_KEYBOARD: u32 = 1;
+const KEYEVENTF_EXTENDEDKEY: u32 = 0x0001;
+const KEYEVENTF_KEYUP: u32 = 0x0002;
+const KEYEVENTF_UNICODE: u32 = 0x0004;
+const KEYEVENTF_SCANCODE: u32 = 0x0008;
+
+const MAPVK_VSC_TO_VK_EX: u32 = 3;
+const WM_KEYDOWN: u32 = 0x0100;
+const WM_KEYUP: u32 = 0x0101;
+const WM_LBUTTONDOWN: u32 = 0x0201;
+const WM_RBUTTONDOWN: u32 = 0x0204;
+const WM_MBUTTONDOWN: u32 = 0x0207;
+const WM_XBUTTONDOWN: u32 = 0x020B;
+const MK_LBUTTON: usize = 0x0001;
+const MK_RBUTTON: usize = 0x0002;
+const MK_MBUTTON: usize = 0x0010;
+const MK_XBUTTON1: usize = 0x0020;
+const MK_XBUTTON2: usize = 0x0040;
+
+#[link(name = "user32")]
+extern "system" {
+  fn GetForegroundWindow() -> Hwnd;
+  fn GetWindowRect(hwnd: Hwnd, rect: *mut Rect) -> i32;
+  fn GetWindowTextW(hwnd: Hwnd, text: *mut u16, max_count: i32) -> i32;
+  fn GetWindowThreadProcessId(hwnd: Hwnd, process_id: *mut u32) -> u32;
+  fn GetKeyboardLayout(thread_id: u32) -> Hkl;
+  fn GetKeyboardLayoutList(count: i32, list: *mut Hkl) -> i32;
+  fn GetKeyState(virt_key: i32) -> i16;
+  fn SendInput(count: u32, inputs: *const Input, size: i32) -> u32;
+  fn BlockInput(block: i32) -> i32;
+  fn GetDesktopWindow() -> Hwnd;
+  fn PhysicalToLogicalPointForPerMonitorDPI(hwnd: Hwnd, point: *mut Point) -> i32;
+  fn IsWindow(hwnd: Hwnd) -> i32;
+  fn PostMessageW(hwnd: Hwnd, msg: u32, wparam: usize, lparam: isize) -> i32;
+  fn MapVirtualKeyExW(code: u32, map_type: u32, hkl: Hkl) -> u32;
+  fn ToUnicodeEx(
+    virt_key: u32,
+    scan_code: u32,
+    key_state: *const u8,
+    buf: *mut u16,
+    buf_len: i32,
+    flags: u32,
+    hkl: Hkl,
+  ) -> i32;
+}
+
+#[link(name = "kernel32")]
+extern "system" {
+  fn OpenProcess(desired_access: u32, inherit_handle: i32, process_id: u32) -> Handle;
+  fn QueryFullProcessImageNameW(process: Handle, flags: u32, name: *mut u16, size: *mut u32)
+    -> i32;
+  fn CloseHandle(handle: Handle) -> i32;
+  fn GetLocaleInfoW(locale: u32, lc_type: u32, data: *mut u16, len: i32) -> i32;
+}
+
+pub(super) fn focused_window() -> Option<FocusedWindow> {
+  // SAFETY: plain Win32 calls on a window handle we just obtained; every
+  // buffer passed in is sized by the length argument that accompanies it.
+  unsafe {
+    let hwnd = GetForegroundWindow();
+    if hwnd == 0 {
+      return None;
+    }
+
+    let mut rect = Rect::default();
+    if GetWindowRect(hwnd, &mut rect) == 0 {
+      return None;
+    }
+
+    let mut title = [0u16; 512];
+    let len = GetWindowTextW(hwnd, title.as_mut_ptr(), title.len() as i32);
+    let title = String::from_utf16_lossy(&title[..len.max(0) as usize]);
+
+    let mut pid = 0u32;
+    GetWindowThreadProcessId(hwnd, &mut pid);
+
+    Some(FocusedWindow {
+      title,
+      x: rect.left as f64,
+      y: rect.top as f64,
+      width: (rect.right - rect.left) as f64,
+      height: (rect.bottom - rect.top) as f64,
+      process_name: process_name(pid),
+      process_id: (pid != 0).then_some(pid),
+      handle: Some(hwnd),
+    })
+  }
+}
+
+/// Executable file name (e.g. `notepad.exe`) for a process id.
+fn process_name(pid: u32) -> Option<String> {
+  if pid == 0 {
+    return None;
+  }
+  // SAFETY: the handle is checked before use and always closed; `size` tells
+  // the API how large `buf` is and is updated with the written length.
+  unsafe {
+    let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+    if process == 0 {
+      return None;
+    }
+    let mut buf = [0u16; 1024];
+    let mut size = buf.len() as u32;
+    let ok = QueryFullProcessImageNameW(process, 0, buf.as_mut_ptr(), &mut size);
+    CloseHandle(process);
+    if ok == 0 {
+      return None;
+    }
+    let path = String::from_utf16_lossy(&buf[..size as usize]);
+    path.rsplit('\\').next().map(|name| name.to_string())
+  }
+}
+
+pub(super) fn lock_state() -> LockState {
+  // The low bit of GetKeyState is the toggle state.
+  // SAFETY: GetKeyState takes a plain key code and touches no memory of ours.
+  let toggled = |vk| unsafe { GetKeyState(vk) } & 1 != 0;
+  LockState {
+    caps_lock: toggled(VK_CAPITAL),
+    num_lock: toggled(VK_NUMLOCK),
+    scroll_lock: toggled(VK_SCROLL),
+  }
+}
+
+pub(super) fn keyboard_layouts() -> Vec<KeyboardLayout> {
+  // SAFETY: the first call only reports the count; the second writes at most
+  // `list.len()` handles into `list`.
+  let list = unsafe {
+    let count = GetKeyboardLayoutList(0, std::ptr::null_mut());
+    let mut list = vec![0; count.max(0) as usize];
+    let count = GetKeyboardLayoutList(list.len() as i32, list.as_mut_ptr());
+    list.truncate(count.max(0) as usize);
+    list
+  };
+  list
+    .into_iter()
+    .map(|hkl| KeyboardLayout {
+      id: format!("{:x}", hkl as usize),
+      // The low word of a layout handle is its language id
+      name: locale_name((hkl & 0xffff) as u32).unwrap_or_else(|| format!("{:x}", hkl as usize)),
+    })
+    .collect()
+}
+
+fn locale_name(lcid: u32) -> Option<String> {
+  let mut buf = [0u16; 128];
+  // SAFETY: `buf` is sized by the length argument.
+  let len = unsafe {
+    GetLocaleInfoW(
+      lcid,
+      LOCALE_SLOCALIZEDDISPLAYNAME,
+      buf.as_mut_ptr(),
+      buf.len() as i32,
+    )
+  };
+  // The returned length includes the terminating NUL
+  (len > 1).then(|| String::from_utf16_lossy(&buf[..len as usize - 1]))
+}
+
+pub(super) fn set_keyboard_layout(id: &str) -> Result<(), String> {
+  let hkl =
+    usize::from_str_radix(id, 16).map_err(|_| format!("invalid layout id {:?}", id))? as Hkl;
+  let canonical = format!("{:x}", hkl as usize);
+  if !keyboard_layouts().iter().any(|l| l.id == canonical) {
+    return Err(format!("layout {:?} is not installed", id));
+  }
+  // SAFETY: plain Win32 call with no pointers.
+  let hwnd = unsafe { GetForegroundWindow() };
+  if hwnd == 0 {
+    return Err("no foreground window".to_string());
+  }
+  post(hwnd, WM_INPUTLANGCHANGEREQUEST, 0, hkl)
+}
+
+pub(super) fn scan_code_to_char(scan_code: u32, shift: bool) -> Option<String> {
+  // SAFETY: `key_state` is the 256-byte array ToUnicodeEx requires and `buf`
+  // is sized by the length argument; the call leaves dead-key state untouched.
+  unsafe {
+    // Use the focused window's layout: layouts are per-thread on Windows and
+    // ours is not necessarily the one the user is typing into.
+    let thread_id = GetWindowThreadProcessId(GetForegroundWindow(), std::ptr::null_mut());
+    let hkl = GetKeyboardLayout(thread_id);
+
+    let vk = MapVirtualKeyExW(scan_code, MAPVK_VSC_TO_VK, hkl);
+    if vk == 0 {
+      return None;
+    }
+
+    let mut key_state = [0u8; 256];
+    if shift {
+      key_state[VK_SHIFT] = 0x80;
+    }
+    let mut buf = [0u16; 8];
+    let len = ToUnicodeEx(
+      vk,
+      scan_code,
+      key_state.as_ptr(),
+      buf.as_mut_ptr(),
+      buf.len() as i32,
+      TU_NO_STATE_CHANGE,
+      hkl,
+    );
+    // Negative means a dead key; its standalone accent is in buf[0].
+    let len = match len {
+      0 => return None,
+      n if n < 0 => 1,
+      n => n as usize,
+    };
+    let text = String::from_utf16_lossy(&buf[..len]);
+    if text.chars().all(char::is_control) {
+      return None;
+    }
+    Some(text)
+  }
+}
+
+pub(super) fn send_scancode(scancode: u32, up: bool) -> Result<(), String> {
+  let mut flags = KEYEVENTF_SCANCODE;
+  if scancode & 0xff00 == 0xe000 {
+    flags |= KEYEVENTF_EXTENDEDKEY;
+  }
+  if up {
+    flags |= KEYEVENTF_KEYUP;
+  }
+  send_key_input(0, (scancode & 0xff) as u16, flags)
+}
+
+pub(super) fn send_virtual_key(vk: u16, up: bool) -> Result<(), String> {
+  // Consumer keys are all extended keys
+  let mut flags = KEYEVENTF_EXTENDEDKEY;
+  if up {
+    flags |= KEYEVENTF_KEYUP;
+  }
+  send_key_input(vk, 0, flags)
+}
+
+fn send_key_input(vk: u16, scan: u16, flags: u32) -> Result<(), String> {
+  let input = Input {
+    kind: INPUT_KEYBOARD,
+    u: InputUnion {
+      ki: KeybdInput {
+        vk,
+        scan,
+        flags,
+        time: 0,
+        extra_info: 0,
+      },
+    },
+  };
+  // SAFETY: one fully initialised INPUT, with its size passed alongside.
+  let sent = unsafe { SendInput(1, &input, std::mem::size_of::<Input>() as i32) };
+  if sent == 1 {
+    Ok(())
+  } else {
+    Err("SendInput was blocked".to_string())
+  }
+}
+
+fn post(hwnd: Hwnd, msg: u32, wparam: usize, lparam: isize) -> Result<(), String> {
+  // SAFETY: PostMessageW only queues the message; an invalid handle fails
+  // with a zero return rather than touching memory.
+  if unsafe { PostMessageW(hwnd, msg, wparam, lparam) } == 0 {
+    Err("PostMessage failed".to_string())
+  } else {
+    Ok(())
+  }
+}
+
+fn check_window(hwnd: Hwnd) -> Result<(), String> {
+  // SAFETY: IsWindow accepts any value and only reports whether it is a window.
+  if unsafe { IsWindow(hwnd) } == 0 {
+    Err(format!("{:#x} is not a window", hwnd))
+  } else {
+    Ok(())
+  }
+}
+
+pub(super) fn post_key(hwnd: Hwnd, scancode: u32) -> Result<(), String> {
+  check_window(hwnd)?;
+  // SAFETY: plain lookup in the window's own thread layout.
+  let vk = unsafe {
+    let thread_id = GetWindowThreadProcessId(hwnd, std::ptr::null_mut());
+    MapVirtualKeyExW(scancode, MAPVK_VSC_TO_VK_EX, GetKeyboardLayout(thread_id))
+  };
+  if vk == 0 {
+    return Err(format!("no virtual key for scancode {:#x}", scancode));
+  }
+  // lParam: repeat count 1, scan code in bits 16-23, extended flag in bit 24;
+  // key-up additionally sets the previous-state and transition bits.
+  let mut lparam = 1 | ((scancode & 0xff) << 16);
+  if scancode & 0xff00 == 0xe000 {
+    lparam |= 1 << 24;
+  }
+  post(hwnd, WM_KEYDOWN, vk as usize, lparam as isize)?;
+  post(
+    hwnd,
+    WM_KEYUP,
+    vk as usize,
+    (lparam | 0xc000_0000) as i32 as isize,
+  )
+}
+
+pub(super) fn post_click(hwnd: Hwnd, x: i32, y: i32, button: u32) -> Result<(), String> {
+  check_window(hwnd)?;
+  let (down, mask, xbutton) = match button {
+    0 => (WM_LBUTTONDOWN, MK_LBUTTON, 0),
+    1 => (WM_RBUTTONDOWN, MK_RBUTTON, 0),
+    2 => (WM_MBUTTONDOWN, MK_MBUTTON, 0),
+    3 => (WM_XBUTTONDOWN, MK_XBUTTON1, 1),
+    4 => (WM_XBUTTONDOWN, MK_XBUTTON2, 2),
+    _ => return Err("unsupported button".to_string()),
+  };
+  let lparam = ((y as u32 & 0xffff) << 16 | (x as u32 & 0xffff)) as i32 as isize;
+  // Every *BUTTONUP message directly follows its *BUTTONDOWN.
+  post(hwnd, down, mask | (xbutton << 16), lparam)?;
+  post(hwnd, down + 1, xbutton << 16, lparam)
+}
+
+pub(super) fn send_text(text: &str) -> Result<(), String> {
+  // KEYEVENTF_UNICODE takes UTF-16 code units; a surrogate pair is sent as two
+  // consecutive units, which Windows recombines for the target window.
+  let inputs: Vec<Input> = text
+    .encode_utf16()
+    .flat_map(|unit| [0, KEYEVENTF_KEYUP].map(|up| (unit, up)))
+    .map(|(unit, up)| Input {
+      kind: INPUT_KEYBOARD,
+      u: InputUnion {
+        ki: KeybdInput {
+          vk: 0,
+          scan: unit,
+          flags: KEYEVENTF_UNICODE | up,
+          time: 0,
+          extra_info: 0,
+        },
+      },
+    })
+    .collect();
+  // SAFETY: `inputs` is a fully initialised slice whose length and element
+  // size are passed alongside.
+  let sent = unsafe {
+    SendInput(
+      inputs.len() as u32,
+      inputs.as_ptr(),
+      std::mem::size_of::<Input>() as i32,
+    )
+  };
+  if sent as usize == inputs.len() {
+    Ok(())
+  } else {
+    Err("SendInput was blocked".to_string())
+  }
+}
+
+pub(super) fn block_input(block: bool) -> Result<(), String> {
+  // SAFETY: BlockInput takes a plain BOOL and touches no caller memory.
+  if unsafe { BlockInput(block as i32) } != 0 {
+    Ok(())
+  } else {
+    Err("BlockInput failed (it requires an elevated process)".to_string())
+  }
+}
+
+pub(super) fn physical_to_logical(x: f64, y: f64) -> (f64, f64) {
+  let mut point = Point {
+    x: x.round() as i32,
+    y: y.round() as i32,
+  };
+  // SAFETY: `point` is a valid POINT for the call to convert in place; the
+  // desktop window handle is always valid.
+  if unsafe { PhysicalToLogicalPointForPerMonitorDPI(GetDesktopWindow(), &mut point) } != 0 {
+    (point.x as f64, point.y as f64)
+  } else {
+    (x, y)
+  }
+}